@@ -0,0 +1,671 @@
+//! Report of the main circuit constraints by operation type.
+//!
+//! The constraints are counted for the circuits of the blocks consisting of a single operation,
+//! created with the same witness helpers as the real blocks. Every chunk of the block circuit
+//! contains the gadgets of all the operations, since the operation type is only known to the
+//! prover, so the gadget constraints are counted in the first chunk of the block.
+
+// Built-in deps
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+// External deps
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_circuit::circuit::ZkSyncCircuit;
+use zksync_circuit::witness::{
+    ChangePubkeyOffChainWitness, DepositWitness, ForcedExitWitness, FullExitWitness,
+    MintNFTWitness, SigDataInput, SwapWitness, TransferToNewWitness, TransferWitness,
+    WithdrawNFTWitness, WithdrawWitness, Witness, WitnessBuilder,
+};
+use zksync_crypto::bellman::{
+    Circuit, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+};
+use zksync_crypto::circuit::{account::CircuitAccount, CircuitAccountTree};
+use zksync_crypto::params::{
+    account_tree_depth, MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ID, NFT_TOKEN_ID,
+};
+use zksync_crypto::{Engine, Fr};
+use zksync_test_account::ZkSyncAccount;
+use zksync_types::operations::{
+    ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, MintNFTOp, NoopOp, SwapOp, TransferOp,
+    TransferToNewOp, WithdrawNFTOp, WithdrawOp,
+};
+use zksync_types::tx::ChangePubKeyType;
+use zksync_types::{Account, AccountId, Address, BlockNumber, Deposit, FullExit, TokenId, H256};
+
+/// Adds the sample operation to the block, using the accounts of the block.
+type AddOperation = fn(&mut WitnessBuilder<'_>, &[ZkSyncAccount]);
+
+/// Operations along with their chunks, named after the gadget namespaces in the circuit.
+const OPERATIONS: [(&str, usize, AddOperation); 11] = [
+    ("noop", NoopOp::CHUNKS, add_noop),
+    ("deposit", DepositOp::CHUNKS, add_deposit),
+    (
+        "transfer_to_new",
+        TransferToNewOp::CHUNKS,
+        add_transfer_to_new,
+    ),
+    ("withdraw", WithdrawOp::CHUNKS, add_withdraw),
+    ("transfer", TransferOp::CHUNKS, add_transfer),
+    ("full_exit", FullExitOp::CHUNKS, add_full_exit),
+    (
+        "change_pubkey_offchain",
+        ChangePubKeyOp::CHUNKS,
+        add_change_pubkey,
+    ),
+    ("forced_exit", ForcedExitOp::CHUNKS, add_forced_exit),
+    ("mint_nft", MintNFTOp::CHUNKS, add_mint_nft),
+    ("withdraw_nft", WithdrawNFTOp::CHUNKS, add_withdraw_nft),
+    ("swap", SwapOp::CHUNKS, add_swap),
+];
+
+/// Number of the accounts available to the sample operations, their IDs start from 1.
+const ACCOUNTS: u32 = 3;
+/// Account that is not in the tree yet, e.g. the recipient of the `TransferToNew`.
+const NEW_ACCOUNT_ID: AccountId = AccountId(ACCOUNTS + 1);
+/// NFT owned by the second account and created by the first one.
+const NFT_ID: TokenId = TokenId(MIN_NFT_TOKEN_ID);
+
+fn add_operation<W: Witness>(
+    witness_builder: &mut WitnessBuilder<'_>,
+    op: &W::OperationType,
+    input: W::CalculateOpsInput,
+) {
+    let witness = W::apply_tx(witness_builder.account_tree, op);
+    let operations = witness.calculate_operations(input);
+    witness_builder.add_operation_with_pubdata(
+        operations,
+        witness.get_pubdata(),
+        witness.get_offset_commitment_data(),
+    );
+}
+
+fn add_noop(_witness_builder: &mut WitnessBuilder<'_>, _accounts: &[ZkSyncAccount]) {}
+
+fn add_deposit(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let op = DepositOp {
+        priority_op: Deposit {
+            from: accounts[0].address,
+            token: TokenId(0),
+            amount: BigUint::from(10u32),
+            to: accounts[0].address,
+        },
+        account_id: AccountId(1),
+    };
+    add_operation::<DepositWitness<Engine>>(witness_builder, &op, ());
+}
+
+fn add_transfer_to_new(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let op = TransferToNewOp {
+        tx: accounts[0]
+            .sign_transfer(
+                TokenId(0),
+                "",
+                BigUint::from(10u32),
+                BigUint::from(1u32),
+                &Address::repeat_byte(0x11),
+                None,
+                true,
+                Default::default(),
+            )
+            .0,
+        from: AccountId(1),
+        to: NEW_ACCOUNT_ID,
+    };
+    let input = SigDataInput::from_transfer_to_new_op(&op).expect("Failed to sign transfer");
+    add_operation::<TransferToNewWitness<Engine>>(witness_builder, &op, input);
+}
+
+fn add_withdraw(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let op = WithdrawOp {
+        tx: accounts[0]
+            .sign_withdraw(
+                TokenId(0),
+                "",
+                BigUint::from(10u32),
+                BigUint::from(1u32),
+                &accounts[0].address,
+                None,
+                true,
+                Default::default(),
+            )
+            .0,
+        account_id: AccountId(1),
+    };
+    let input = SigDataInput::from_withdraw_op(&op).expect("Failed to sign withdraw");
+    add_operation::<WithdrawWitness<Engine>>(witness_builder, &op, input);
+}
+
+fn add_transfer(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let op = TransferOp {
+        tx: accounts[0]
+            .sign_transfer(
+                TokenId(0),
+                "",
+                BigUint::from(10u32),
+                BigUint::from(1u32),
+                &accounts[1].address,
+                None,
+                true,
+                Default::default(),
+            )
+            .0,
+        from: AccountId(1),
+        to: AccountId(2),
+    };
+    let input = SigDataInput::from_transfer_op(&op).expect("Failed to sign transfer");
+    add_operation::<TransferWitness<Engine>>(witness_builder, &op, input);
+}
+
+fn add_full_exit(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let op = FullExitOp {
+        priority_op: FullExit {
+            account_id: AccountId(1),
+            eth_address: accounts[0].address,
+            token: TokenId(0),
+            is_legacy: false,
+        },
+        withdraw_amount: Some(BigUint::from(10u32).into()),
+        creator_account_id: None,
+        creator_address: None,
+        serial_id: None,
+        content_hash: None,
+    };
+    add_operation::<FullExitWitness<Engine>>(witness_builder, &(op, true), ());
+}
+
+fn add_change_pubkey(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let op = ChangePubKeyOp {
+        tx: accounts[0].sign_change_pubkey_tx(
+            None,
+            true,
+            TokenId(0),
+            BigUint::from(1u32),
+            ChangePubKeyType::ECDSA,
+            Default::default(),
+        ),
+        account_id: AccountId(1),
+    };
+    let input = SigDataInput::from_change_pubkey_op(&op).expect("Failed to sign change pubkey");
+    add_operation::<ChangePubkeyOffChainWitness<Engine>>(witness_builder, &op, input);
+}
+
+fn add_forced_exit(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let op = ForcedExitOp {
+        tx: accounts[0].sign_forced_exit(
+            TokenId(0),
+            BigUint::from(1u32),
+            &accounts[2].address,
+            None,
+            true,
+            Default::default(),
+        ),
+        target_account_id: AccountId(3),
+        withdraw_amount: Some(BigUint::from(10u32).into()),
+    };
+    let input = SigDataInput::from_forced_exit_op(&op).expect("Failed to sign forced exit");
+    add_operation::<ForcedExitWitness<Engine>>(witness_builder, &op, input);
+}
+
+fn add_mint_nft(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let op = MintNFTOp {
+        tx: accounts[0]
+            .sign_mint_nft(
+                TokenId(0),
+                "",
+                H256::zero(),
+                BigUint::from(1u32),
+                &accounts[1].address,
+                None,
+                true,
+            )
+            .0,
+        creator_account_id: AccountId(1),
+        recipient_account_id: AccountId(2),
+    };
+    let input = SigDataInput::from_mint_nft_op(&op).expect("Failed to sign mint NFT");
+    add_operation::<MintNFTWitness<Engine>>(witness_builder, &op, input);
+}
+
+fn add_withdraw_nft(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let op = WithdrawNFTOp {
+        tx: accounts[1]
+            .sign_withdraw_nft(
+                NFT_ID,
+                TokenId(0),
+                "",
+                BigUint::from(1u32),
+                &accounts[1].address,
+                None,
+                true,
+                Default::default(),
+            )
+            .0,
+        creator_id: AccountId(1),
+        creator_address: accounts[0].address,
+        serial_id: 0,
+        content_hash: H256::zero(),
+    };
+    let input = SigDataInput::from_withdraw_nft_op(&op).expect("Failed to sign withdraw NFT");
+    add_operation::<WithdrawNFTWitness<Engine>>(witness_builder, &op, input);
+}
+
+fn add_swap(witness_builder: &mut WitnessBuilder<'_>, accounts: &[ZkSyncAccount]) {
+    let amount = BigUint::from(10u32);
+    let orders = (
+        accounts[0].sign_order(
+            TokenId(0),
+            TokenId(1),
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+            amount.clone(),
+            &accounts[0].address,
+            None,
+            true,
+            Default::default(),
+        ),
+        accounts[1].sign_order(
+            TokenId(1),
+            TokenId(0),
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+            amount.clone(),
+            &accounts[1].address,
+            None,
+            true,
+            Default::default(),
+        ),
+    );
+    let input = (
+        SigDataInput::from_order(&orders.0).expect("Failed to sign order"),
+        SigDataInput::from_order(&orders.1).expect("Failed to sign order"),
+    );
+    let op = SwapOp {
+        tx: accounts[2]
+            .sign_swap(
+                orders,
+                (amount.clone(), amount),
+                None,
+                true,
+                TokenId(0),
+                "",
+                BigUint::from(1u32),
+            )
+            .0,
+        submitter: AccountId(3),
+        accounts: (AccountId(1), AccountId(2)),
+        recipients: (AccountId(1), AccountId(2)),
+    };
+    let swap_input = SigDataInput::from_swap_op(&op).expect("Failed to sign swap");
+    add_operation::<SwapWitness<Engine>>(witness_builder, &op, (input.0, input.1, swap_input));
+}
+
+/// Creates the circuit of the block of the given size containing the single operation.
+fn single_operation_circuit(
+    add_operation: AddOperation,
+    block_chunks: usize,
+) -> ZkSyncCircuit<'static, Engine> {
+    let mut account_tree = CircuitAccountTree::new(account_tree_depth());
+    // The fee account.
+    account_tree.insert(0, Account::default().into());
+
+    let accounts: Vec<ZkSyncAccount> = (1..=ACCOUNTS)
+        .map(|id| {
+            let zksync_account = ZkSyncAccount::rand();
+            zksync_account.set_account_id(Some(AccountId(id)));
+
+            let mut account = Account::default_with_address(&zksync_account.address);
+            account.pub_key_hash = zksync_account.pubkey_hash;
+            account.add_balance(TokenId(0), &BigUint::from(1000u32));
+            account.add_balance(TokenId(1), &BigUint::from(1000u32));
+            if id == 2 {
+                account.add_balance(NFT_ID, &BigUint::from(1u32));
+            }
+            account_tree.insert(id, CircuitAccount::from(account));
+            zksync_account
+        })
+        .collect();
+
+    let mut nft_storage = Account::default_with_address(&Address::zero());
+    nft_storage.add_balance(NFT_TOKEN_ID, &BigUint::from(MIN_NFT_TOKEN_ID + 1));
+    account_tree.insert(*NFT_STORAGE_ACCOUNT_ID, CircuitAccount::from(nft_storage));
+
+    let mut witness_builder =
+        WitnessBuilder::new(&mut account_tree, AccountId(0), BlockNumber(1), 0);
+    add_operation(&mut witness_builder, &accounts);
+    witness_builder.extend_pubdata_with_noops(block_chunks);
+    witness_builder.collect_fees(&[]);
+    witness_builder.calculate_pubdata_commitment();
+    witness_builder.into_circuit_instance()
+}
+
+/// Constraints of the operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationCost {
+    pub chunks: usize,
+    /// Constraints of the operation gadget, which is a part of every chunk.
+    pub gadget_constraints: usize,
+    /// Constraints of the circuit for the block consisting of this operation only.
+    pub total_constraints: usize,
+}
+
+/// Constraints of the main circuit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitReport {
+    /// Constraints that don't depend on the block size, e.g. the fees collection.
+    pub block_constraints: usize,
+    /// Constraints of a single chunk.
+    pub chunk_constraints: usize,
+    pub operations: BTreeMap<String, OperationCost>,
+}
+
+/// Constraint system that only counts the constraints without evaluating the witness.
+struct ConstraintCounter {
+    inputs: usize,
+    aux: usize,
+    namespace: Vec<String>,
+    constraints: usize,
+    /// Constraints of the operation gadgets in the first chunk.
+    gadget_constraints: BTreeMap<String, usize>,
+}
+
+impl ConstraintCounter {
+    fn new() -> Self {
+        Self {
+            // The first input is reserved for the constant one.
+            inputs: 1,
+            aux: 0,
+            namespace: Vec::new(),
+            constraints: 0,
+            gadget_constraints: BTreeMap::new(),
+        }
+    }
+}
+
+impl ConstraintSystem<Engine> for ConstraintCounter {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(&mut self, _annotation: A, _f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.aux += 1;
+        Ok(Variable::new_unchecked(Index::Aux(self.aux - 1)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _annotation: A, _f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.inputs += 1;
+        Ok(Variable::new_unchecked(Index::Input(self.inputs - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _annotation: A, _a: LA, _b: LB, _c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<Engine>) -> LinearCombination<Engine>,
+        LB: FnOnce(LinearCombination<Engine>) -> LinearCombination<Engine>,
+        LC: FnOnce(LinearCombination<Engine>) -> LinearCombination<Engine>,
+    {
+        self.constraints += 1;
+        // Operation gadgets are placed at `chunk number {i}/execute_op/{operation}`.
+        if let [chunk, execute_op, gadget, ..] = self.namespace.as_slice() {
+            if chunk == "chunk number 0" && execute_op == "execute_op" {
+                *self.gadget_constraints.entry(gadget.clone()).or_default() += 1;
+            }
+        }
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.namespace.push(name_fn().into());
+    }
+
+    fn pop_namespace(&mut self) {
+        self.namespace.pop();
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+fn count_constraints(circuit: ZkSyncCircuit<'static, Engine>) -> ConstraintCounter {
+    let mut counter = ConstraintCounter::new();
+    circuit
+        .synthesize(&mut counter)
+        .expect("Failed to synthesize the circuit");
+    counter
+}
+
+/// Counts the constraints of the main circuit by operation type.
+pub fn make_circuit_report() -> CircuitReport {
+    let single_chunk = count_constraints(single_operation_circuit(add_noop, 1));
+    let two_chunks = count_constraints(single_operation_circuit(add_noop, 2));
+    let chunk_constraints = two_chunks.constraints - single_chunk.constraints;
+
+    let operations = OPERATIONS
+        .iter()
+        .map(|&(name, chunks, add_operation)| {
+            let block = count_constraints(single_operation_circuit(add_operation, chunks));
+            let cost = OperationCost {
+                chunks,
+                gadget_constraints: block
+                    .gadget_constraints
+                    .get(name)
+                    .copied()
+                    .unwrap_or_default(),
+                total_constraints: block.constraints,
+            };
+            (name.to_string(), cost)
+        })
+        .collect();
+
+    CircuitReport {
+        block_constraints: single_chunk.constraints - chunk_constraints,
+        chunk_constraints,
+        operations,
+    }
+}
+
+pub fn load_circuit_report(path: &Path) -> anyhow::Result<CircuitReport> {
+    Ok(serde_json::from_reader(File::open(path)?)?)
+}
+
+pub fn save_circuit_report(report: &CircuitReport, path: &Path) -> anyhow::Result<()> {
+    serde_json::to_writer_pretty(File::create(path)?, report)?;
+    Ok(())
+}
+
+/// Change of a value of the constraints report compared to the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportChange {
+    /// Name of the value, e.g. `transfer.total_constraints`.
+    pub name: String,
+    /// Value in the baseline, `None` if it's added.
+    pub old: Option<usize>,
+    /// Value in the new report, `None` if it's removed.
+    pub new: Option<usize>,
+}
+
+impl ReportChange {
+    /// Checks whether the value has grown by more than `threshold` percent of the baseline one.
+    pub fn exceeds_threshold(&self, threshold: f64) -> bool {
+        match (self.old, self.new) {
+            (Some(old), Some(new)) => new as f64 > old as f64 * (1.0 + threshold / 100.0),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for ReportChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.old, self.new) {
+            (Some(old), Some(new)) => write!(
+                f,
+                "{}: {} -> {} ({:+})",
+                self.name,
+                old,
+                new,
+                new as i64 - old as i64
+            ),
+            (Some(old), None) => write!(f, "{}: {} -> removed", self.name, old),
+            (None, Some(new)) => write!(f, "{}: added -> {}", self.name, new),
+            (None, None) => write!(f, "{}: missing", self.name),
+        }
+    }
+}
+
+/// Returns the changes of the constraints compared to the baseline.
+pub fn diff_circuit_reports(baseline: &CircuitReport, report: &CircuitReport) -> Vec<ReportChange> {
+    fn change(name: &str, old: Option<usize>, new: Option<usize>) -> Option<ReportChange> {
+        if old == new {
+            return None;
+        }
+        Some(ReportChange {
+            name: name.to_string(),
+            old,
+            new,
+        })
+    }
+
+    let mut changes: Vec<ReportChange> = vec![
+        change(
+            "block_constraints",
+            Some(baseline.block_constraints),
+            Some(report.block_constraints),
+        ),
+        change(
+            "chunk_constraints",
+            Some(baseline.chunk_constraints),
+            Some(report.chunk_constraints),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let operations: BTreeSet<&String> = baseline
+        .operations
+        .keys()
+        .chain(report.operations.keys())
+        .collect();
+    for operation in operations {
+        let old = baseline.operations.get(operation);
+        let new = report.operations.get(operation);
+        changes.extend(change(
+            &format!("{}.chunks", operation),
+            old.map(|cost| cost.chunks),
+            new.map(|cost| cost.chunks),
+        ));
+        changes.extend(change(
+            &format!("{}.gadget_constraints", operation),
+            old.map(|cost| cost.gadget_constraints),
+            new.map(|cost| cost.gadget_constraints),
+        ));
+        changes.extend(change(
+            &format!("{}.total_constraints", operation),
+            old.map(|cost| cost.total_constraints),
+            new.map(|cost| cost.total_constraints),
+        ));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(transfer_constraints: usize) -> CircuitReport {
+        let operation = |chunks, gadget_constraints, total_constraints| OperationCost {
+            chunks,
+            gadget_constraints,
+            total_constraints,
+        };
+        CircuitReport {
+            block_constraints: 1000,
+            chunk_constraints: 200,
+            operations: vec![
+                ("noop".to_string(), operation(1, 0, 1200)),
+                (
+                    "transfer".to_string(),
+                    operation(2, transfer_constraints, 1400),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn diff_of_same_reports_is_empty() {
+        assert!(diff_circuit_reports(&report(100), &report(100)).is_empty());
+    }
+
+    #[test]
+    fn diff_circuit_reports_changes() {
+        let baseline = report(100);
+        let mut new_report = report(120);
+        new_report.chunk_constraints = 190;
+        new_report.operations.remove("noop");
+        new_report.operations.insert(
+            "swap".to_string(),
+            OperationCost {
+                chunks: 5,
+                gadget_constraints: 300,
+                total_constraints: 2000,
+            },
+        );
+
+        let lines: Vec<String> = diff_circuit_reports(&baseline, &new_report)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "chunk_constraints: 200 -> 190 (-10)",
+                "noop.chunks: 1 -> removed",
+                "noop.gadget_constraints: 0 -> removed",
+                "noop.total_constraints: 1200 -> removed",
+                "swap.chunks: added -> 5",
+                "swap.gadget_constraints: added -> 300",
+                "swap.total_constraints: added -> 2000",
+                "transfer.gadget_constraints: 100 -> 120 (+20)",
+            ]
+        );
+    }
+
+    #[test]
+    fn report_change_threshold() {
+        let change = |old, new| ReportChange {
+            name: "transfer.gadget_constraints".to_string(),
+            old,
+            new,
+        };
+
+        // Growth by exactly the threshold is allowed.
+        assert!(!change(Some(100), Some(110)).exceeds_threshold(10.0));
+        assert!(change(Some(100), Some(111)).exceeds_threshold(10.0));
+        assert!(change(Some(100), Some(101)).exceeds_threshold(0.0));
+        // Decrease never exceeds the threshold.
+        assert!(!change(Some(100), Some(50)).exceeds_threshold(0.0));
+        // Any growth from zero does.
+        assert!(change(Some(0), Some(1)).exceeds_threshold(50.0));
+        // Added and removed values are reported, but don't exceed the threshold.
+        assert!(!change(None, Some(100)).exceeds_threshold(0.0));
+        assert!(!change(Some(100), None).exceeds_threshold(0.0));
+    }
+}
@@ -10,13 +10,19 @@
 //! and `SUPPORTED_BLOCK_CHUNKS_SIZES_SETUP_POWERS` that are read from env in config files.
 //! Before generating parameters universal setup keys should be downloaded using `zksync plonk-setup` command.
 
+mod circuit_report;
 mod recursive_keys;
 mod sample_proofs;
 mod verifier_contract_generator;
 mod zksync_key;
 
+use std::path::PathBuf;
+
 use structopt::StructOpt;
 
+use crate::circuit_report::{
+    diff_circuit_reports, load_circuit_report, make_circuit_report, save_circuit_report,
+};
 use crate::recursive_keys::{
     count_gates_recursive_verification_keys, make_recursive_verification_keys,
 };
@@ -35,7 +41,18 @@ enum Command {
     /// Generate verifier contract based on verification keys
     Contract,
     /// Counts available sizes (chunks and aggregated proof size) for available setups
-    CircuitSize,
+    /// and the constraints of the main circuit by operation type
+    CircuitSize {
+        /// File to save the constraints report to, e.g. to use it as a baseline later
+        #[structopt(long, parse(from_os_str))]
+        report: Option<PathBuf>,
+        /// Previously saved constraints report to compare with
+        #[structopt(long, parse(from_os_str))]
+        baseline: Option<PathBuf>,
+        /// Fail if any value of the report grows by more than the given percent of the baseline
+        #[structopt(long, requires = "baseline")]
+        threshold: Option<f64>,
+    },
 }
 
 #[derive(StructOpt)]
@@ -61,7 +78,53 @@ fn main() {
         Command::Contract => {
             create_verifier_contract(config);
         }
-        Command::CircuitSize => {
+        Command::CircuitSize {
+            report,
+            baseline,
+            threshold,
+        } => {
+            // Only the report goes to stdout, so that it can be piped into a file.
+            let circuit_report = make_circuit_report();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&circuit_report)
+                    .expect("Failed to serialize constraints report")
+            );
+            let mut exceeded = Vec::new();
+            if let Some(baseline) = baseline {
+                let baseline =
+                    load_circuit_report(&baseline).expect("Failed to load baseline report");
+                let diff = diff_circuit_reports(&baseline, &circuit_report);
+                if diff.is_empty() {
+                    eprintln!("Constraints are the same as in the baseline");
+                } else {
+                    eprintln!("Constraints changed compared to the baseline:");
+                    for change in &diff {
+                        eprintln!("  {}", change);
+                    }
+                }
+                if let Some(threshold) = threshold {
+                    exceeded = diff
+                        .into_iter()
+                        .filter(|change| change.exceeds_threshold(threshold))
+                        .collect();
+                }
+            }
+            if let Some(report) = report {
+                save_circuit_report(&circuit_report, &report)
+                    .expect("Failed to save constraints report");
+            }
+            if !exceeded.is_empty() {
+                eprintln!(
+                    "Constraints grew by more than {}% compared to the baseline:",
+                    threshold.unwrap_or_default()
+                );
+                for change in exceeded {
+                    eprintln!("  {}", change);
+                }
+                std::process::exit(1);
+            }
+
             calculate_and_print_max_zksync_main_circuit_size();
             count_gates_recursive_verification_keys();
         }
@@ -35,6 +35,8 @@ pub fn make_recursive_verification_keys(config: ChainConfig) {
     }
 }
 
+/// Calculates max number of proofs in the recursive circuit for universal setup power up to 26
+/// and prints it to stderr.
 pub fn count_gates_recursive_verification_keys() {
     fn get_setup_size(proofs: usize) -> u32 {
         let recursive_setup =
@@ -55,7 +57,7 @@ pub fn count_gates_recursive_verification_keys() {
         if new_setup_power <= setup_power {
             proofs = new_proofs;
         } else {
-            vlog::info!("setup_size_log2: {}, proofs: {}", setup_power, proofs);
+            eprintln!("setup_size_log2: {}, proofs: {}", setup_power, proofs);
             proofs *= 2;
             let new_setup_power = get_setup_size(proofs);
             assert_eq!(setup_power + 1, new_setup_power);
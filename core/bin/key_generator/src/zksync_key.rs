@@ -234,8 +234,9 @@ fn gates_count_zksync_main_circuit(chunks: usize) -> (usize, u32) {
 }
 
 /// Calculates max zkSync circuit size for universal setup power of 21..26
+/// and prints it to stderr, so that it doesn't mix with the report in stdout.
 pub fn calculate_and_print_max_zksync_main_circuit_size() {
-    vlog::info!("Counting max zkSync circuit size for setup");
+    eprintln!("Counting max zkSync circuit size for setup");
     let mut chunks = 6;
     let mut setup_power = gates_count_zksync_main_circuit(chunks).1;
     while setup_power <= 26 {
@@ -244,11 +245,9 @@ pub fn calculate_and_print_max_zksync_main_circuit_size() {
         if power_2 <= setup_power {
             chunks = new_chunks;
         } else {
-            vlog::info!(
+            eprintln!(
                 "setup_size_log2: {}, chunks: {}, gate_count: {}",
-                setup_power,
-                chunks,
-                gate_count
+                setup_power, chunks, gate_count
             );
             setup_power += 1;
             chunks *= 2;
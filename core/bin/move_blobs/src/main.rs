@@ -0,0 +1,93 @@
+//! Tool to move the witnesses, proofs and prover job data kept in the database to the blob storage.
+//! The rows are updated in batches, so the tool can be interrupted and restarted at any time.
+
+use anyhow::format_err;
+use structopt::StructOpt;
+use zksync_config::DBConfig;
+use zksync_storage::blob_storage::blob_storage_from_config;
+use zksync_storage::StorageProcessor;
+
+#[derive(Debug, Clone, Copy)]
+enum BlobKind {
+    Witnesses,
+    Proofs,
+    AggregatedProofs,
+    JobData,
+}
+
+impl BlobKind {
+    fn table(self) -> &'static str {
+        match self {
+            Self::Witnesses => "block_witness",
+            Self::Proofs => "proofs",
+            Self::AggregatedProofs => "aggregated_proofs",
+            Self::JobData => "prover_job_queue",
+        }
+    }
+}
+
+async fn move_blobs(
+    storage: &mut StorageProcessor<'_>,
+    kind: BlobKind,
+    batch_size: i64,
+) -> anyhow::Result<usize> {
+    let mut total = 0;
+    loop {
+        let mut transaction = storage.start_transaction().await?;
+        let mut schema = transaction.prover_schema();
+        let moved = match kind {
+            BlobKind::Witnesses => schema.move_witnesses_to_blob_storage(batch_size).await?,
+            BlobKind::Proofs => schema.move_proofs_to_blob_storage(batch_size).await?,
+            BlobKind::AggregatedProofs => {
+                schema
+                    .move_aggregated_proofs_to_blob_storage(batch_size)
+                    .await?
+            }
+            BlobKind::JobData => schema.move_job_data_to_blob_storage(batch_size).await?,
+        };
+        transaction.commit().await?;
+
+        if moved == 0 {
+            return Ok(total);
+        }
+        total += moved;
+        println!("{} rows of `{}` are moved", total, kind.table());
+    }
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync blob moving tool", author = "Matter Labs")]
+#[structopt(
+    about = "Tool to move the witnesses, proofs and prover job data from the database to the blob storage"
+)]
+struct Opt {
+    /// Amount of the rows moved within a single transaction.
+    #[structopt(long, default_value = "100")]
+    batch_size: i64,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    // The blobs are moved only to the storage used by the server, so they can be loaded afterwards.
+    let blob_storage = blob_storage_from_config(&DBConfig::from_env())?.ok_or_else(|| {
+        format_err!("Blob storage is not configured, set `DATABASE_BLOB_STORAGE_BACKEND`")
+    })?;
+    let mut storage = StorageProcessor::establish_connection().await?;
+    storage.set_blob_storage(Some(blob_storage));
+
+    for &kind in &[
+        BlobKind::Witnesses,
+        BlobKind::Proofs,
+        BlobKind::AggregatedProofs,
+        BlobKind::JobData,
+    ] {
+        let moved = move_blobs(&mut storage, kind, opt.batch_size).await?;
+        println!("`{}` table is done, {} rows moved", kind.table(), moved);
+    }
+
+    // Postgres doesn't return the space of the updated rows to the OS by itself.
+    println!("Run `VACUUM FULL` on the tables to reclaim the disk space");
+    Ok(())
+}
@@ -1,11 +1,14 @@
 // Built-in deps
+use std::path::PathBuf;
 use std::time::Duration;
 // External deps
-use structopt::StructOpt;
+use structopt::{clap::AppSettings, StructOpt};
 // Workspace deps
 use zksync_config::configs::ProverConfig as EnvProverConfig;
+use zksync_types::BlockNumber;
 use zksync_utils::{get_env, parse_env};
 // Local deps
+use crate::prove_block::{load_witness_from_db, load_witness_from_file, prove_block, save_proof};
 use crate::{client, prover_work_cycle, ProverConfig, ProverImpl, ShutdownRequest};
 use zksync_config::configs::api::PrometheusConfig;
 use zksync_prometheus_exporter::run_prometheus_exporter;
@@ -21,12 +24,53 @@ fn api_client_from_env() -> client::ApiClient {
 #[structopt(
     name = "zkSync operator node",
     author = "Matter Labs",
-    rename_all = "snake_case"
+    rename_all = "snake_case",
+    setting = AppSettings::SubcommandsNegateReqs
 )]
 struct Opt {
     /// Name of the worker. Must be unique!
-    #[structopt(index = 1)]
-    worker_name: String,
+    #[structopt(index = 1, required = true)]
+    worker_name: Option<String>,
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Proves a single block locally without connecting to the prover server.
+    #[structopt(name = "prove-block")]
+    ProveBlock {
+        /// Number of the block to load the witness for from the database.
+        #[structopt(long, required_unless = "witness", conflicts_with = "witness")]
+        block: Option<u32>,
+        /// JSON file with the block witness, either exported by the witness tool or as stored in the database.
+        #[structopt(long, parse(from_os_str))]
+        witness: Option<PathBuf>,
+        /// File to write the proof to.
+        #[structopt(long, parse(from_os_str))]
+        output: PathBuf,
+        /// Don't verify the created proof, must be set for the dummy prover.
+        #[structopt(long)]
+        skip_verification: bool,
+    },
+}
+
+async fn prove_block_locally<PROVER: ProverImpl>(
+    block: Option<u32>,
+    witness: Option<PathBuf>,
+    output: PathBuf,
+    verify: bool,
+) -> anyhow::Result<()> {
+    let witness = match (block, witness) {
+        (_, Some(path)) => load_witness_from_file(&path)?,
+        (Some(block), None) => load_witness_from_db(BlockNumber(block)).await?,
+        (None, None) => anyhow::bail!("Either block number or witness file must be provided"),
+    };
+    let prover = PROVER::create_from_config(<PROVER as ProverImpl>::Config::from_env());
+    let proof = prove_block(&prover, witness, verify)?;
+    save_proof(&proof, &output)?;
+    vlog::info!("Proof is saved to {}", output.display());
+    Ok(())
 }
 
 pub async fn main_for_prover_impl<PROVER>(run_prometheus: bool)
@@ -34,7 +78,23 @@ where
     PROVER: ProverImpl + Send + Sync + 'static,
 {
     let opt = Opt::from_args();
-    let worker_name = opt.worker_name;
+    if let Some(Command::ProveBlock {
+        block,
+        witness,
+        output,
+        skip_verification,
+    }) = opt.command
+    {
+        let _vlog_guard = vlog::init();
+        if let Err(err) =
+            prove_block_locally::<PROVER>(block, witness, output, !skip_verification).await
+        {
+            vlog::error!("Failed to prove the block: {}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+    let worker_name = opt.worker_name.expect("Worker name is required");
 
     // used env
     let prover_options = EnvProverConfig::from_env();
@@ -10,7 +10,8 @@ use vlog::*;
 // Workspace deps
 use crate::auth_utils::AuthTokenGenerator;
 use zksync_prover_utils::api::{
-    ProverInputRequest, ProverInputResponse, ProverOutputRequest, ProverStopped, WorkingOn,
+    JobFailure, ProverInputRequest, ProverInputResponse, ProverOutputRequest, ProverStopped,
+    WorkingOn,
 };
 
 #[derive(Debug, Clone)]
@@ -19,6 +20,7 @@ pub struct ApiClient {
     working_on_url: Url,
     publish_url: Url,
     stopped_url: Url,
+    report_failure_url: Url,
     // Client keeps connection pool inside, so it is recommended to reuse it (see docstring for reqwest::Client).
     http_client: reqwest::Client,
     // A generator that create the authentication token upon request to any endpoint.
@@ -41,6 +43,7 @@ impl ApiClient {
             working_on_url: base_url.join("/working_on").unwrap(),
             publish_url: base_url.join("/publish").unwrap(),
             stopped_url: base_url.join("/stopped").unwrap(),
+            report_failure_url: base_url.join("/report_failure").unwrap(),
             http_client,
             auth_token_generator,
         }
@@ -142,6 +145,10 @@ impl crate::ApiClient for ApiClient {
             if response.status() == reqwest::StatusCode::UNAUTHORIZED {
                 return Err(Permanent(format_err!("authorization error")));
             }
+            // The job was given to another prover, but the proof can still be published.
+            if response.status() == reqwest::StatusCode::CONFLICT {
+                return Err(Permanent(format_err!("job lease has expired")));
+            }
 
             Ok(())
         };
@@ -165,6 +172,16 @@ impl crate::ApiClient for ApiClient {
             if response.status() == reqwest::StatusCode::UNAUTHORIZED {
                 return Err(Permanent(format_err!("authorization error")));
             }
+            // Server rejects the invalid proofs, there is no point in sending them again.
+            if response.status() == reqwest::StatusCode::BAD_REQUEST {
+                let message = response.text().await.unwrap_or_default();
+                return Err(Permanent(format_err!("proof was rejected: {}", message)));
+            }
+            // Another prover has already finished the job.
+            if response.status() == reqwest::StatusCode::CONFLICT {
+                let message = response.text().await.unwrap_or_default();
+                return Err(Permanent(format_err!("proof was discarded: {}", message)));
+            }
 
             Ok(())
         };
@@ -196,4 +213,47 @@ impl crate::ApiClient for ApiClient {
 
         self.with_retries(operation).await
     }
+
+    async fn report_failure(
+        &self,
+        job_id: i32,
+        prover_name: &str,
+        error: String,
+    ) -> anyhow::Result<()> {
+        let operation = || async {
+            let response = self
+                .http_client
+                .post(self.report_failure_url.clone())
+                .bearer_auth(&self.get_encoded_token()?)
+                .json(&JobFailure {
+                    prover_name: prover_name.to_string(),
+                    job_id,
+                    error: error.clone(),
+                })
+                .send()
+                .await
+                .map_err(|e| {
+                    Transient(format_err!("failed to send report_failure request: {}", e))
+                })?;
+
+            if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+                return Err(Permanent(format_err!("authorization error")));
+            }
+            if !response.status().is_success() {
+                let status = response.status();
+                let message = response.text().await.unwrap_or_default();
+                let error = format_err!("failure report was not accepted: {} {}", status, message);
+                // Requests rejected by the server won't succeed on retry.
+                return Err(if status.is_client_error() {
+                    Permanent(error)
+                } else {
+                    Transient(error)
+                });
+            }
+
+            Ok(())
+        };
+
+        self.with_retries(operation).await
+    }
 }
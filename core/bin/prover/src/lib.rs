@@ -3,6 +3,7 @@ pub mod cli_utils;
 pub mod client;
 pub mod dummy_prover;
 pub mod plonk_step_by_step_prover;
+pub mod prove_block;
 
 // Built-in deps
 use futures::{pin_mut, FutureExt};
@@ -92,21 +93,44 @@ pub trait ApiClient: Debug {
     async fn working_on(&self, job_id: i32, prover_name: &str) -> anyhow::Result<()>;
     async fn publish(&self, data: ProverOutputRequest) -> anyhow::Result<()>;
     async fn prover_stopped(&self, prover_name: String) -> anyhow::Result<()>;
+    /// Notifies the server that the job can't be proven, so it's given out again later.
+    async fn report_failure(
+        &self,
+        job_id: i32,
+        prover_name: &str,
+        error: String,
+    ) -> anyhow::Result<()>;
 }
 
+/// Returns the prover back along with the result of the proof computation.
+/// Fails only if the proving thread has panicked.
 async fn compute_proof_no_blocking<PROVER>(
     prover: PROVER,
     data: JobRequestData,
-) -> anyhow::Result<(PROVER, JobResultData)>
+) -> anyhow::Result<(PROVER, anyhow::Result<JobResultData>)>
 where
     PROVER: ProverImpl + Send + Sync + 'static,
 {
     let (result_sender, result_receiver) = oneshot::channel();
     std::thread::spawn(move || {
-        let prover_with_proof = prover.create_proof(data).map(|proof| (prover, proof));
-        result_sender.send(prover_with_proof).unwrap_or_default();
+        let proof = prover.create_proof(data);
+        result_sender.send((prover, proof)).unwrap_or_default();
     });
-    result_receiver.await?
+    Ok(result_receiver.await?)
+}
+
+async fn report_job_failure<CLIENT: ApiClient>(
+    client: &CLIENT,
+    prover_name: &str,
+    job_id: i32,
+    error: &anyhow::Error,
+) {
+    vlog::error!("Failed to compute proof for job {}: {}", job_id, error);
+    client
+        .report_failure(job_id, prover_name, error.to_string())
+        .await
+        .map_err(|e| vlog::warn!("Failed to report job failure: {}", e))
+        .unwrap_or_default();
 }
 
 /// Endlessly sends requests to the server, in case of not receiving a response
@@ -177,6 +201,7 @@ pub async fn prover_work_cycle<PROVER, CLIENT>(
             data: job_data,
             first_block,
             last_block,
+            lease_expires_at,
         } = prover_input_response;
         let job_data = if let Some(job_data) = job_data {
             job_data
@@ -185,10 +210,11 @@ pub async fn prover_work_cycle<PROVER, CLIENT>(
         };
 
         vlog::info!(
-            "got job id: {}, blocks: [{}, {}]",
+            "got job id: {}, blocks: [{}, {}], lease expires at: {:?}",
             job_id,
             first_block,
-            last_block
+            last_block,
+            lease_expires_at
         );
 
         let heartbeat_future_handle = heartbeat_future_handle(
@@ -208,16 +234,30 @@ pub async fn prover_work_cycle<PROVER, CLIENT>(
             last_block
         );
 
-        let (ret_prover, proof) = futures::select! {
-            comp_proof = compute_proof_future => {
-                comp_proof.expect("Failed to compute proof")
-            },
+        let computed_proof = futures::select! {
+            comp_proof = compute_proof_future => comp_proof,
             _ = heartbeat_future_handle => unreachable!(),
         };
+        let (ret_prover, proof) = match computed_proof {
+            Ok(result) => result,
+            Err(e) => {
+                // The prover is lost along with the proving thread, so there is no way to continue.
+                report_job_failure(&client, prover_name, job_id, &e).await;
+                panic!("Failed to compute proof: {}", e);
+            }
+        };
         prover = ret_prover;
+        let proof = match proof {
+            Ok(proof) => proof,
+            Err(e) => {
+                report_job_failure(&client, prover_name, job_id, &e).await;
+                continue;
+            }
+        };
 
         client
             .publish(ProverOutputRequest {
+                prover_name: prover_name.to_string(),
                 job_id,
                 first_block,
                 last_block,
@@ -0,0 +1,84 @@
+//! Proving of a single block without the prover server, used to reproduce the prover failures.
+
+// Built-in deps
+use std::fs::File;
+use std::path::Path;
+use std::time::Instant;
+// External deps
+use anyhow::format_err;
+// Workspace deps
+use zksync_circuit::serialization::{BlockWitnessExport, ProverData};
+use zksync_config::DBConfig;
+use zksync_crypto::proof::SingleProof;
+use zksync_prover_utils::api::{JobRequestData, JobResultData};
+use zksync_prover_utils::verification::ProofVerifier;
+use zksync_storage::blob_storage::blob_storage_from_config;
+use zksync_storage::StorageProcessor;
+use zksync_types::BlockNumber;
+// Local deps
+use crate::ProverImpl;
+
+/// Loads the block witness stored by the witness generator.
+pub async fn load_witness_from_db(block: BlockNumber) -> anyhow::Result<ProverData> {
+    let mut storage = StorageProcessor::establish_connection().await?;
+    storage.set_blob_storage(blob_storage_from_config(&DBConfig::from_env())?);
+    let witness = storage
+        .prover_schema()
+        .get_witness(block)
+        .await?
+        .ok_or_else(|| format_err!("There is no witness for the block {}", block))?;
+    Ok(serde_json::from_value(witness)?)
+}
+
+/// Loads the block witness from the JSON file, either made by the witness tool
+/// or containing the output of `ProverSchema::get_witness`.
+pub fn load_witness_from_file(path: &Path) -> anyhow::Result<ProverData> {
+    let content = std::fs::read(path)
+        .map_err(|e| format_err!("Failed to read witness file {}: {}", path.display(), e))?;
+    let witness: serde_json::Value = serde_json::from_slice(&content)?;
+    if witness.get("version").is_some() {
+        return Ok(BlockWitnessExport::read(content.as_slice())?.witness);
+    }
+    Ok(serde_json::from_value(witness)?)
+}
+
+/// Creates the proof for the block witness and checks it unless `verify` is unset.
+pub fn prove_block<PROVER: ProverImpl>(
+    prover: &PROVER,
+    witness: ProverData,
+    verify: bool,
+) -> anyhow::Result<SingleProof> {
+    let block_size = witness.operations.len();
+    let public_input = witness.public_data_commitment;
+
+    let start = Instant::now();
+    let proof = match prover.create_proof(JobRequestData::BlockProof(witness, block_size))? {
+        JobResultData::BlockProof(proof) => proof,
+        JobResultData::AggregatedBlockProof(_) => {
+            anyhow::bail!("Prover created an aggregated proof instead of the block one")
+        }
+    };
+    vlog::info!(
+        "Proof for the block of size {} created in {:?}",
+        block_size,
+        start.elapsed()
+    );
+
+    if verify {
+        ProofVerifier::new(&[block_size], &[])?.verify_block_proof(
+            &proof,
+            public_input,
+            block_size,
+        )?;
+        vlog::info!("Proof is valid");
+    }
+    Ok(proof)
+}
+
+/// Writes the proof as JSON.
+pub fn save_proof(proof: &SingleProof, path: &Path) -> anyhow::Result<()> {
+    let file = File::create(path)
+        .map_err(|e| format_err!("Failed to create proof file {}: {}", path.display(), e))?;
+    serde_json::to_writer_pretty(file, proof)?;
+    Ok(())
+}
@@ -1,5 +1,6 @@
 // Built-in deps
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 // External deps
@@ -15,15 +16,17 @@ use zksync_config::configs::{ChainConfig, ProverConfig};
 use zksync_crypto::{
     circuit::{account::CircuitAccount, CircuitAccountTree},
     pairing::ff::PrimeField,
+    proof::SingleProof,
     Fr,
 };
 use zksync_prover::dummy_prover::{DummyProver, DummyProverConfig};
 use zksync_prover::plonk_step_by_step_prover::{
     PlonkStepByStepProver, PlonkStepByStepProverConfig,
 };
+use zksync_prover::prove_block::{load_witness_from_file, prove_block, save_proof};
 use zksync_prover::{ProverImpl, ShutdownRequest};
 use zksync_prover_utils::api::{
-    JobRequestData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
+    JobRequestData, JobResultData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
 };
 use zksync_types::{
     block::smallest_block_size_for_chunks, operations::DepositOp, Account, AccountId, Address,
@@ -61,10 +64,14 @@ impl Default for MockProverConfigs {
             core: zksync_config::configs::prover::Core {
                 gone_timeout: 2,
                 idle_provers: 1,
+                verify_proofs: false,
+                invalid_proof_penalty: 60000,
+                failed_job_retry_backoff: 1000,
             },
             witness_generator: zksync_config::configs::prover::WitnessGenerator {
                 prepare_data_interval: 5000,
                 witness_generators: 2,
+                pipelined: false,
             },
         };
 
@@ -145,6 +152,67 @@ fn test_data_for_prover() -> JobRequestData {
     JobRequestData::BlockProof(prover_data, 10)
 }
 
+/// File in the temporary directory that is removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str) -> Self {
+        Self(std::env::temp_dir().join(format!("{}-{}", std::process::id(), name)))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Witness of the empty block the first precomputed sample proof was created for,
+/// so the proof returned by the dummy prover for it is a valid one.
+fn sample_block_witness() -> ProverData {
+    let block_size = *ChainConfig::from_env()
+        .circuit
+        .supported_block_chunks_sizes
+        .iter()
+        .min()
+        .unwrap();
+
+    let mut account_tree = CircuitAccountTree::new(zksync_crypto::params::account_tree_depth());
+    account_tree.insert(0, Account::default().into());
+    let mut witness_builder =
+        WitnessBuilder::new(&mut account_tree, AccountId(0), BlockNumber(0), 0);
+    witness_builder.extend_pubdata_with_noops(block_size);
+    witness_builder.collect_fees(&[]);
+    witness_builder.calculate_pubdata_commitment();
+    witness_builder.into()
+}
+
+#[test]
+fn test_prove_block_from_file() {
+    let witness = sample_block_witness();
+    let witness_file = TempFile::new("witness.json");
+    let proof_file = TempFile::new("proof.json");
+    std::fs::write(&witness_file.0, serde_json::to_vec(&witness).unwrap()).unwrap();
+
+    let loaded_witness = load_witness_from_file(&witness_file.0).unwrap();
+    assert_eq!(
+        loaded_witness.public_data_commitment,
+        witness.public_data_commitment
+    );
+
+    let prover = DummyProver::create_from_config(MockProverConfigs::default().dummy_config);
+    let proof = prove_block(&prover, loaded_witness, true).unwrap();
+    assert_eq!(proof.0.input_values, vec![witness.public_data_commitment]);
+
+    save_proof(&proof, &proof_file.0).unwrap();
+    let saved_proof: SingleProof =
+        serde_json::from_slice(&std::fs::read(&proof_file.0).unwrap()).unwrap();
+    assert_eq!(
+        serde_json::to_value(&saved_proof).unwrap(),
+        serde_json::to_value(&proof).unwrap()
+    );
+}
+
 #[tokio::test]
 async fn test_shutdown_request() {
     let MockProverConfigs {
@@ -256,6 +324,46 @@ async fn test_publishing_proof() {
     };
 }
 
+#[tokio::test]
+async fn test_reporting_failure() {
+    let MockProverConfigs {
+        plonk_config: _,
+        dummy_config,
+        prover_options,
+        shutdown_request,
+        prover_name,
+    } = MockProverConfigs::default();
+
+    let prover = FailingProver::create_from_config(dummy_config);
+    let client = MockApiClient::default();
+
+    let prover_work_cycle = zksync_prover::prover_work_cycle(
+        prover,
+        client.clone(),
+        shutdown_request.clone(),
+        prover_options.clone(),
+        &prover_name,
+    )
+    .fuse();
+    let timeout = tokio::time::sleep(Duration::from_secs(5)).fuse();
+
+    pin_mut!(prover_work_cycle, timeout);
+
+    futures::select! {
+        _ = prover_work_cycle => panic!("prover work ended too quickly"),
+        _ = timeout => {
+            shutdown_request.set();
+            // The prover keeps working after the failure.
+            assert_eq!(
+                client.failed_jobs.lock().await.get(&0).cloned(),
+                Some("out of memory".to_string())
+            );
+            assert!(client.failed_jobs.lock().await.contains_key(&1));
+            assert!(client.published_prof.lock().await.is_empty());
+        },
+    };
+}
+
 #[derive(Debug, Clone, Default)]
 struct MockApiClient {
     /// All published proofs are saved by `job_id`.
@@ -264,6 +372,8 @@ struct MockApiClient {
     working_on: Arc<Mutex<HashMap<i32, String>>>,
     /// `gob_id` of the last work that has not yet been submitted.
     last_job_id: Arc<Mutex<i32>>,
+    /// Errors reported via `self.report_failure()` by `job_id`.
+    failed_jobs: Arc<Mutex<HashMap<i32, String>>>,
 }
 
 #[async_trait::async_trait]
@@ -276,6 +386,7 @@ impl zksync_prover::ApiClient for MockApiClient {
             first_block: BlockNumber(1),
             last_block: BlockNumber(1),
             data: Some(test_data_for_prover()),
+            lease_expires_at: None,
         };
 
         Ok(response)
@@ -299,4 +410,26 @@ impl zksync_prover::ApiClient for MockApiClient {
     async fn prover_stopped(&self, _: String) -> anyhow::Result<()> {
         Ok(())
     }
+
+    async fn report_failure(&self, job_id: i32, _: &str, error: String) -> anyhow::Result<()> {
+        self.failed_jobs.lock().await.insert(job_id, error);
+
+        Ok(())
+    }
+}
+
+/// Prover that fails every job.
+#[derive(Debug)]
+struct FailingProver;
+
+impl ProverImpl for FailingProver {
+    type Config = DummyProverConfig;
+
+    fn create_from_config(_: Self::Config) -> Self {
+        Self
+    }
+
+    fn create_proof(&self, _: JobRequestData) -> anyhow::Result<JobResultData> {
+        anyhow::bail!("out of memory")
+    }
 }
@@ -23,6 +23,7 @@ use zksync_config::{
 use zksync_core::rejected_tx_cleaner::run_rejected_tx_cleaner;
 use zksync_mempool::run_mempool_tx_handler;
 use zksync_prometheus_exporter::{run_operation_counter, run_prometheus_exporter};
+use zksync_storage::blob_storage::blob_storage_from_config;
 use zksync_storage::ConnectionPool;
 use zksync_types::ChainId;
 
@@ -151,19 +152,43 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn create_fee_ticker(read_only_connection_pool: &ConnectionPool) -> FeeTicker {
+    let chain_config = ChainConfig::from_env();
+    let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
+    FeeTicker::new_with_default_validator(
+        ticker_info,
+        TickerConfig::from_env(),
+        chain_config.max_blocks_to_aggregate(),
+        read_only_connection_pool.clone(),
+    )
+}
+
 async fn run_server(components: &ComponentsToRun) {
-    let connection_pool = ConnectionPool::new(None);
-    let read_only_connection_pool = ConnectionPool::new_readonly_pool(None);
+    let blob_storage =
+        blob_storage_from_config(&DBConfig::from_env()).expect("Failed to create the blob storage");
+    let connection_pool = ConnectionPool::new(None).with_blob_storage(blob_storage.clone());
+    let read_only_connection_pool =
+        ConnectionPool::new_readonly_pool(None).with_blob_storage(blob_storage);
     let (stop_signal_sender, mut stop_signal_receiver) = mpsc::channel(256);
 
     let mut tasks = vec![];
 
     if components.0.contains(&Component::Web3Api) {
         // Run web3 api
+        let web3_config = Web3Config::from_env();
+        let token_config = TokenConfig::from_env();
+        let fee_ticker = create_fee_ticker(&read_only_connection_pool);
         tasks.push(zksync_api::api_server::web3::start_rpc_server(
             connection_pool.clone(),
-            &Web3Config::from_env(),
-            &TokenConfig::from_env(),
+            fee_ticker.clone(),
+            &web3_config,
+            &token_config,
+        ));
+        tasks.push(zksync_api::api_server::web3::start_ws_server(
+            connection_pool.clone(),
+            fee_ticker,
+            &web3_config,
+            &token_config,
         ));
     }
 
@@ -203,16 +228,8 @@ async fn run_server(components: &ComponentsToRun) {
         let common_config = CommonApiConfig::from_env();
         let token_config = TokenConfig::from_env();
         let chain_config = ChainConfig::from_env();
-        let fee_ticker_config = TickerConfig::from_env();
         let eth_client_config = ETHClientConfig::from_env();
-        let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
-
-        let ticker = FeeTicker::new_with_default_validator(
-            ticker_info,
-            fee_ticker_config,
-            chain_config.max_blocks_to_aggregate(),
-            read_only_connection_pool.clone(),
-        );
+        let ticker = create_fee_ticker(&read_only_connection_pool);
 
         if components.0.contains(&Component::RpcWebSocketApi) {
             let (mempool_tx_request_sender, mempool_tx_request_receiver) =
@@ -373,8 +390,9 @@ pub fn run_witness_generator(connection_pool: ConnectionPool) -> JoinHandle<()>
     vlog::info!("Starting the Prover server actors");
     let prover_api_config = ProverApiConfig::from_env();
     let prover_config = ProverConfig::from_env();
+    let chain_config = ChainConfig::from_env();
     let database = zksync_witness_generator::database::Database::new(connection_pool);
-    run_prover_server(database, prover_api_config, prover_config)
+    run_prover_server(database, prover_api_config, prover_config, chain_config)
 }
 
 pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
@@ -0,0 +1,184 @@
+//! Tool to export the block witness along with the block into a self-contained file
+//! and to import it back, e.g. into a fresh database, to reproduce the circuit issues.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use anyhow::{ensure, format_err};
+use structopt::StructOpt;
+use zksync_circuit::serialization::{BlockWitnessExport, ProverData};
+use zksync_config::DBConfig;
+use zksync_storage::blob_storage::blob_storage_from_config;
+use zksync_storage::StorageProcessor;
+use zksync_types::BlockNumber;
+
+/// Connects to the database, using the blob storage of the server for the witnesses.
+async fn establish_connection() -> anyhow::Result<StorageProcessor<'static>> {
+    let mut storage = StorageProcessor::establish_connection().await?;
+    storage.set_blob_storage(blob_storage_from_config(&DBConfig::from_env())?);
+    Ok(storage)
+}
+
+async fn export_witness(
+    storage: &mut StorageProcessor<'_>,
+    block_number: BlockNumber,
+    output: &Path,
+) -> anyhow::Result<()> {
+    let block = storage
+        .chain()
+        .block_schema()
+        .get_block(block_number)
+        .await?
+        .ok_or_else(|| format_err!("There is no block {} in storage", block_number))?;
+    let witness = storage
+        .prover_schema()
+        .get_witness(block_number)
+        .await?
+        .ok_or_else(|| format_err!("There is no witness for the block {}", block_number))?;
+    let witness: ProverData = serde_json::from_value(witness)?;
+
+    let export = BlockWitnessExport::new(block, witness);
+    export.write(BufWriter::new(File::create(output)?))?;
+    println!(
+        "Witness for the block {} is exported to {}",
+        block_number,
+        output.display()
+    );
+    Ok(())
+}
+
+async fn import_witness(
+    storage: &mut StorageProcessor<'_>,
+    export: BlockWitnessExport,
+) -> anyhow::Result<()> {
+    let block_number = export.block.block_number;
+    let mut transaction = storage.start_transaction().await?;
+
+    match transaction
+        .chain()
+        .block_schema()
+        .get_block(block_number)
+        .await?
+    {
+        Some(stored_block) => {
+            ensure!(
+                stored_block.new_root_hash == export.block.new_root_hash,
+                "Block {} in storage differs from the exported one",
+                block_number
+            );
+            println!("Block {} already exists in storage", block_number);
+        }
+        None => {
+            transaction
+                .chain()
+                .block_schema()
+                .save_full_block(export.block)
+                .await?;
+            println!("Block {} is saved", block_number);
+        }
+    }
+
+    let witness_exists = transaction
+        .prover_schema()
+        .get_witness(block_number)
+        .await?
+        .is_some();
+    ensure!(
+        !witness_exists,
+        "Witness for the block {} already exists in storage",
+        block_number
+    );
+    transaction
+        .prover_schema()
+        .store_witness(block_number, serde_json::to_value(export.witness)?)
+        .await?;
+    transaction.commit().await?;
+
+    println!("Witness for the block {} is imported", block_number);
+    Ok(())
+}
+
+fn check_witness(export: &BlockWitnessExport) -> anyhow::Result<()> {
+    ensure!(
+        export.witness.operations.len() == export.block.block_chunks_size,
+        "Witness has {} operations, while the block size is {}",
+        export.witness.operations.len(),
+        export.block.block_chunks_size
+    );
+    export.check_constraints().map_err(|constraint| {
+        format_err!(
+            "Witness for the block {} doesn't satisfy the constraint: {}",
+            export.block.block_number,
+            constraint
+        )
+    })?;
+
+    println!(
+        "Witness for the block {} satisfies the circuit constraints",
+        export.block.block_number
+    );
+    Ok(())
+}
+
+fn read_export(input: &Path) -> anyhow::Result<BlockWitnessExport> {
+    let file =
+        File::open(input).map_err(|e| format_err!("Failed to open {}: {}", input.display(), e))?;
+    BlockWitnessExport::read(BufReader::new(file))
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Exports the block and its witness from the database into a file.
+    Export {
+        /// Number of the block to export.
+        #[structopt(long)]
+        block: u32,
+        /// File to write the export to.
+        #[structopt(long, parse(from_os_str))]
+        output: PathBuf,
+    },
+    /// Imports the block and its witness into the database, the block is saved only if it doesn't exist.
+    Import {
+        /// File with the export.
+        #[structopt(long, parse(from_os_str))]
+        input: PathBuf,
+    },
+    /// Synthesizes the block circuit from the exported witness and reports the unsatisfied constraints.
+    Check {
+        /// File with the export.
+        #[structopt(long, parse(from_os_str))]
+        input: PathBuf,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkSync witness tool", author = "Matter Labs")]
+#[structopt(about = "Tool to export and import block witnesses for offline debugging")]
+struct Opt {
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    match opt.command {
+        Command::Export { block, output } => {
+            let mut storage = establish_connection().await?;
+            export_witness(&mut storage, BlockNumber(block), &output).await?;
+        }
+        Command::Import { input } => {
+            let export = read_export(&input)?;
+            let mut storage = establish_connection().await?;
+            import_witness(&mut storage, export).await?;
+        }
+        Command::Check { input } => {
+            let export = read_export(&input)?;
+            check_witness(&export)?;
+        }
+    }
+
+    Ok(())
+}
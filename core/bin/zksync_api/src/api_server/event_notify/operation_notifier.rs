@@ -1,5 +1,5 @@
 use crate::api_server::rpc_server::types::{
-    BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+    AccountActivityResp, BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
 };
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use std::time::{Duration, Instant};
@@ -10,8 +10,9 @@ use zksync_types::BlockNumber;
 use zksync_types::{block::ExecutedOperations, AccountId, ActionType, Address, PriorityOpId};
 
 use super::{
-    state::NotifierState, sub_store::SubStorage, EventNotifierRequest, EventSubscribeRequest,
-    ExecutedOps,
+    state::NotifierState,
+    sub_store::{ActivityAddress, SubStorage},
+    EventNotifierRequest, EventSubscribeRequest, ExecutedOps,
 };
 
 pub struct OperationNotifier {
@@ -20,6 +21,7 @@ pub struct OperationNotifier {
     tx_subs: SubStorage<TxHash, TransactionInfoResp>,
     prior_op_subs: SubStorage<PriorityOpId, ETHOpInfoResp>,
     account_subs: SubStorage<AccountId, ResponseAccountState>,
+    activity_subs: SubStorage<ActivityAddress, AccountActivityResp>,
 }
 
 impl OperationNotifier {
@@ -33,6 +35,7 @@ impl OperationNotifier {
             tx_subs: SubStorage::new(),
             prior_op_subs: SubStorage::new(),
             account_subs: SubStorage::new(),
+            activity_subs: SubStorage::new(),
         }
     }
 
@@ -64,6 +67,11 @@ impl OperationNotifier {
                     self.add_account_update_sub(address, action, subscriber)
                         .await
                 }
+                EventSubscribeRequest::AccountActivity {
+                    address,
+                    action,
+                    subscriber,
+                } => self.add_account_activity_sub(address, action, subscriber),
             }
             .map_err(|e| anyhow::format_err!("Failed to add sub: {}", e)),
             EventNotifierRequest::Unsub(sub_id) => self
@@ -86,6 +94,19 @@ impl OperationNotifier {
         };
 
         for block in blocks {
+            // Activity notifications are less important than the rest of them,
+            // so they must not prevent the operation and account subscribers from being notified.
+            if let Err(err) = self
+                .handle_account_activity(&block.block_transactions, action, block.block_number)
+                .await
+            {
+                vlog::error!(
+                    "Failed to notify account activity subscribers, block: {}, error: {}",
+                    *block.block_number,
+                    err
+                );
+            }
+
             self.handle_executed_operations(
                 block.block_transactions.clone(),
                 action,
@@ -162,6 +183,66 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.handle_executed_operations", start.elapsed());
     }
 
+    /// Notifies account activity subscribers about every operation from the block
+    /// that touches the subscribed address.
+    ///
+    /// Unlike transaction subscriptions, activity subscriptions are only notified about
+    /// operations included into blocks, so every operation is reported once per action type.
+    async fn handle_account_activity(
+        &mut self,
+        ops: &[ExecutedOperations],
+        action: ActionType,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<()> {
+        if self.activity_subs.is_empty() {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        let block = BlockInfo {
+            block_number: i64::from(*block_number),
+            committed: true,
+            verified: action == ActionType::VERIFY,
+        };
+        for op in ops {
+            let (addresses, resp) = match op {
+                ExecutedOperations::Tx(tx) => {
+                    let addresses = self
+                        .state
+                        .get_tx_affected_addresses(&tx.signed_tx.tx)
+                        .await?;
+                    let resp = AccountActivityResp {
+                        tx_hash: tx.signed_tx.hash(),
+                        eth_hash: None,
+                        op: serde_json::to_value(&tx.signed_tx.tx)?,
+                        success: tx.success,
+                        fail_reason: tx.fail_reason.clone(),
+                        block: block.clone(),
+                    };
+                    (addresses, resp)
+                }
+                ExecutedOperations::PriorityOp(prior_op) => {
+                    let resp = AccountActivityResp {
+                        tx_hash: prior_op.priority_op.tx_hash(),
+                        eth_hash: Some(prior_op.priority_op.eth_hash),
+                        op: serde_json::to_value(&prior_op.priority_op.data)?,
+                        success: true,
+                        fail_reason: None,
+                        block: block.clone(),
+                    };
+                    (prior_op.priority_op.data.affected_accounts(), resp)
+                }
+            };
+
+            for address in addresses {
+                self.activity_subs
+                    .broadcast(ActivityAddress(address), action, resp.clone());
+            }
+        }
+        metrics::histogram!("api.notifier.handle_account_activity", start.elapsed());
+        Ok(())
+    }
+
     /// More convenient alias for `handle_executed_operations`.
     pub fn handle_new_executed_batch(
         &mut self,
@@ -179,7 +260,8 @@ impl OperationNotifier {
     fn handle_unsub(&mut self, sub_id: SubscriptionId) -> Result<(), anyhow::Error> {
         self.prior_op_subs.remove(sub_id.clone())?;
         self.tx_subs.remove(sub_id.clone())?;
-        self.account_subs.remove(sub_id)?;
+        self.account_subs.remove(sub_id.clone())?;
+        self.activity_subs.remove(sub_id)?;
         Ok(())
     }
 
@@ -294,4 +376,79 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.add_account_update_sub", start.elapsed());
         Ok(())
     }
+
+    /// Add account activity subscription.
+    fn add_account_activity_sub(
+        &mut self,
+        address: Address,
+        action: ActionType,
+        sub: Subscriber<AccountActivityResp>,
+    ) -> Result<(), anyhow::Error> {
+        let address = ActivityAddress(address);
+        let sub_id = self.activity_subs.generate_sub_id(address, action);
+
+        self.activity_subs
+            .insert_new(sub_id, sub, address, action)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::test_utils::dummy_deposit_op;
+    use zksync_storage::test_data::gen_unique_aggregated_operation_with_txs;
+    use zksync_types::aggregated_operations::AggregatedActionType;
+
+    #[tokio::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn account_activity_notification() -> anyhow::Result<()> {
+        let address = Address::random();
+        let mut notifier =
+            OperationNotifier::new(10, ConnectionPool::new(Some(1)), Duration::from_secs(60));
+
+        let (subscriber, sub_id, mut notifications) = Subscriber::new_test("account_activity");
+        notifier
+            .handle_notify_req(EventNotifierRequest::Sub(
+                EventSubscribeRequest::AccountActivity {
+                    address,
+                    action: ActionType::COMMIT,
+                    subscriber,
+                },
+            ))
+            .await?;
+        let sub_id = sub_id.await.unwrap().unwrap();
+
+        let deposit = dummy_deposit_op(address, AccountId(1), 0, 0);
+        let tx_hash = deposit.priority_op.tx_hash();
+        let operation = gen_unique_aggregated_operation_with_txs(
+            BlockNumber(1),
+            AggregatedActionType::CommitBlocks,
+            10,
+            vec![ExecutedOperations::PriorityOp(Box::new(deposit))],
+        );
+        notifier.handle_new_block(operation).await?;
+
+        let message = notifications
+            .try_next()
+            .expect("Account activity notification was not sent")
+            .unwrap();
+        let message: serde_json::Value = serde_json::from_str(&message)?;
+        assert_eq!(
+            message["params"]["subscription"],
+            serde_json::to_value(&sub_id)?
+        );
+        let activity: AccountActivityResp =
+            serde_json::from_value(message["params"]["result"].clone())?;
+        assert_eq!(activity.tx_hash, tx_hash);
+        assert_eq!(activity.block.block_number, 1);
+        assert!(activity.success);
+        // Deposit to the own address is reported once.
+        assert!(notifications.try_next().is_err());
+
+        Ok(())
+    }
 }
@@ -8,7 +8,7 @@ use zksync_token_db_cache::TokenDBCache;
 use zksync_types::aggregated_operations::AggregatedActionType;
 use zksync_types::tx::TxHash;
 use zksync_types::BlockNumber;
-use zksync_types::{AccountId, ActionType, Address};
+use zksync_types::{AccountId, ActionType, Address, ZkSyncTx};
 
 pub struct NotifierState {
     pub(super) cache_of_executed_priority_operations:
@@ -228,4 +228,47 @@ impl NotifierState {
         metrics::histogram!("api.notifier.get_account_state", start.elapsed());
         Ok(account)
     }
+
+    /// Returns the addresses of all the accounts touched by the transaction,
+    /// including order signers of the swap and the initiator of the forced exit.
+    pub async fn get_tx_affected_addresses(&self, tx: &ZkSyncTx) -> anyhow::Result<Vec<Address>> {
+        // Accounts referenced only by their ids.
+        let mut account_ids = Vec::new();
+        let mut addresses = match tx {
+            ZkSyncTx::Transfer(tx) => vec![tx.from, tx.to],
+            ZkSyncTx::Withdraw(tx) => vec![tx.from, tx.to],
+            ZkSyncTx::Close(tx) => vec![tx.account],
+            ZkSyncTx::ChangePubKey(tx) => vec![tx.account],
+            ZkSyncTx::ForcedExit(tx) => {
+                account_ids.push(tx.initiator_account_id);
+                vec![tx.target]
+            }
+            ZkSyncTx::MintNFT(tx) => vec![tx.creator_address, tx.recipient],
+            ZkSyncTx::WithdrawNFT(tx) => vec![tx.from, tx.to],
+            ZkSyncTx::Swap(tx) => {
+                account_ids.extend_from_slice(&[tx.orders.0.account_id, tx.orders.1.account_id]);
+                vec![
+                    tx.submitter_address,
+                    tx.orders.0.recipient_address,
+                    tx.orders.1.recipient_address,
+                ]
+            }
+        };
+        if !account_ids.is_empty() {
+            let mut storage = self.db_pool.access_storage().await?;
+            for account_id in account_ids {
+                if let Some(address) = storage
+                    .chain()
+                    .account_schema()
+                    .account_address_by_id(account_id)
+                    .await?
+                {
+                    addresses.push(address);
+                }
+            }
+        }
+        addresses.sort();
+        addresses.dedup();
+        Ok(addresses)
+    }
 }
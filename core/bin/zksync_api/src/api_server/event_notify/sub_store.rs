@@ -1,7 +1,7 @@
 //! Storage for subscription objects.
 use super::SubscriptionSender;
-use std::{cmp::Ord, collections::BTreeMap, str::FromStr};
-use zksync_types::{tx::TxHash, AccountId, ActionType, PriorityOpId};
+use std::{cmp::Ord, collections::BTreeMap, fmt, str::FromStr};
+use zksync_types::{tx::TxHash, AccountId, ActionType, Address, PriorityOpId};
 
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
@@ -12,6 +12,7 @@ const MAX_LISTENERS_PER_ENTITY: usize = 2048;
 const TX_SUB_PREFIX: &str = "txsub";
 const ETHOP_SUB_PREFIX: &str = "eosub";
 const ACCOUNT_SUB_PREFIX: &str = "acsub";
+const ACCOUNT_ACTIVITY_SUB_PREFIX: &str = "aasub";
 
 pub trait ActionId {
     fn sub_type() -> &'static str;
@@ -35,6 +36,33 @@ impl ActionId for AccountId {
     }
 }
 
+impl ActionId for ActivityAddress {
+    fn sub_type() -> &'static str {
+        ACCOUNT_ACTIVITY_SUB_PREFIX
+    }
+}
+
+/// Address of the account which activity is being tracked.
+///
+/// `Display` implementation of `Address` shortens the value, so it can't be used
+/// as a part of the subscription id directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ActivityAddress(pub Address);
+
+impl fmt::Display for ActivityAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl FromStr for ActivityAddress {
+    type Err = <Address as FromStr>::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
 #[derive(Debug)]
 pub struct SubStorage<ID, RESP> {
     storage: BTreeMap<(ID, ActionType), Vec<SubscriptionSender<RESP>>>,
@@ -136,6 +164,20 @@ where
         self.storage.contains_key(&(action_id, action_type))
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    /// Sends the event to the subscribers without removing them,
+    /// unlike `notify` which treats every subscription as a one-time one.
+    pub fn broadcast(&self, action_id: ID, action_type: ActionType, event: RESP) {
+        if let Some(subs) = self.storage.get(&(action_id, action_type)) {
+            for sub in subs {
+                self.send_once(&sub.sink, event.clone());
+            }
+        }
+    }
+
     pub fn notify(&mut self, action_id: ID, action_type: ActionType, event: RESP) {
         if let Some(subs) = self.storage.remove(&(action_id, action_type)) {
             for sub in subs {
@@ -158,3 +200,26 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rpc_server::types::AccountActivityResp;
+
+    #[test]
+    fn activity_sub_id_roundtrip() {
+        let address = ActivityAddress(Address::random());
+        let mut subs = SubStorage::<ActivityAddress, AccountActivityResp>::new();
+
+        let sub_id = subs.generate_sub_id(address, ActionType::VERIFY);
+        let str_sub_id = match sub_id {
+            SubscriptionId::String(id) => id,
+            SubscriptionId::Number(_) => panic!("Subscription id should be a string"),
+        };
+
+        assert_eq!(
+            subs.parse_sub_id(&str_sub_id).unwrap(),
+            Some((address, ActionType::VERIFY))
+        );
+    }
+}
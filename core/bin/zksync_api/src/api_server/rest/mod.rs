@@ -22,6 +22,7 @@ use zksync_mempool::MempoolTransactionRequest;
 mod forced_exit_requests;
 mod helpers;
 pub mod network_status;
+mod stats_updater;
 mod v01;
 pub mod v02;
 
@@ -125,6 +126,12 @@ pub fn start_server_thread_detached(
                     .await
                     .unwrap();
 
+                // Statistics are aggregated into the main database.
+                stats_updater::start_stats_updater_detached(
+                    panic_sender.clone(),
+                    main_database_connection_pool.clone(),
+                );
+
                 let api_v01 = ApiV01::new(
                     read_only_connection_pool,
                     main_database_connection_pool,
@@ -0,0 +1,54 @@
+//! Background task aggregating the network statistics served by `api/v0.2/stats`.
+
+use futures::channel::mpsc;
+use std::time::Duration;
+use tokio::{runtime::Runtime, time};
+use zksync_storage::ConnectionPool;
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+/// Maximum number of blocks aggregated in a single database transaction.
+const STATS_UPDATE_BATCH_SIZE: u32 = 100;
+const STATS_UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+
+async fn update_stats(connection_pool: &ConnectionPool) -> anyhow::Result<()> {
+    let mut storage = connection_pool.access_storage().await?;
+    loop {
+        let processed = storage
+            .chain()
+            .stats_schema()
+            .update_stats_summary(STATS_UPDATE_BATCH_SIZE)
+            .await?;
+        if processed < STATS_UPDATE_BATCH_SIZE {
+            return Ok(());
+        }
+    }
+}
+
+/// Periodically aggregates the statistics of the newly finalized blocks.
+///
+/// Progress is stored in the database, so it's safe to run this task on several
+/// API instances simultaneously.
+pub fn start_stats_updater_detached(
+    panic_notify: mpsc::Sender<bool>,
+    connection_pool: ConnectionPool,
+) {
+    std::thread::Builder::new()
+        .name("rest-stats-updater".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+
+            let runtime = Runtime::new().expect("tokio runtime creation");
+
+            let stats_update_task = async move {
+                let mut timer = time::interval(STATS_UPDATE_INTERVAL);
+                loop {
+                    timer.tick().await;
+                    if let Err(err) = update_stats(&connection_pool).await {
+                        vlog::error!("Can't update network statistics: {}", err);
+                    }
+                }
+            };
+            runtime.block_on(stats_update_task);
+        })
+        .expect("Stats update thread");
+}
@@ -1,16 +1,22 @@
 //! Account part of API implementation.
 
 // Built-in uses
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::time::Instant;
 
 // External uses
-use actix_web::{web, Scope};
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
 
 // Workspace uses
 use zksync_api_types::v02::{
-    account::{Account, AccountAddressOrId, AccountState, IncomingAccountTxsQuery},
+    account::{
+        Account, AccountAddressOrId, AccountBatchItem, AccountState, AccountsBatchRequest,
+        EthAccountType, IncomingAccountTxsQuery, MAX_ACCOUNTS_BATCH_SIZE,
+    },
     pagination::{
         parse_query, AccountTxsRequest, ApiEither, Paginated, PaginationQuery, PendingOpsRequest,
     },
@@ -29,16 +35,41 @@ use super::{
 };
 use crate::{api_server::helpers::get_depositing, api_try, fee_ticker::PriceError};
 
+/// Parses the account id or the address (with an optional `0x` prefix).
+pub(super) fn parse_account_id_or_address(
+    account_address_or_id: &str,
+) -> Result<AccountAddressOrId, Error> {
+    if let Ok(account_id) = u32::from_str(account_address_or_id) {
+        Ok(AccountAddressOrId::Id(AccountId(account_id)))
+    } else {
+        let address_str = if let Some(address_str) = account_address_or_id.strip_prefix("0x") {
+            address_str
+        } else {
+            account_address_or_id
+        };
+
+        if let Ok(address) = Address::from_str(address_str) {
+            Ok(AccountAddressOrId::Address(address))
+        } else {
+            Err(Error::from(InvalidDataError::InvalidAccountIdOrAddress))
+        }
+    }
+}
+
 /// Shared data between `api/v02/accounts` endpoints.
 #[derive(Clone)]
-struct ApiAccountData {
+pub(super) struct ApiAccountData {
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
 }
 
 impl ApiAccountData {
-    fn new(pool: ConnectionPool, tokens: TokenDBCache, confirmations_for_eth_event: u64) -> Self {
+    pub(super) fn new(
+        pool: ConnectionPool,
+        tokens: TokenDBCache,
+        confirmations_for_eth_event: u64,
+    ) -> Self {
         Self {
             pool,
             tokens,
@@ -46,7 +77,7 @@ impl ApiAccountData {
         }
     }
 
-    async fn get_id_by_address_or_id(
+    pub(super) async fn get_id_by_address_or_id(
         &self,
         account_address_or_id: AccountAddressOrId,
     ) -> Result<Option<AccountId>, Error> {
@@ -65,7 +96,7 @@ impl ApiAccountData {
         }
     }
 
-    async fn get_address_by_address_or_id(
+    pub(super) async fn get_address_by_address_or_id(
         &self,
         account_address_or_id: AccountAddressOrId,
     ) -> Result<Address, Error> {
@@ -84,25 +115,11 @@ impl ApiAccountData {
         }
     }
 
-    fn parse_account_id_or_address(
+    pub(super) fn parse_account_id_or_address(
         &self,
         account_address_or_id: &str,
     ) -> Result<AccountAddressOrId, Error> {
-        if let Ok(account_id) = u32::from_str(account_address_or_id) {
-            Ok(AccountAddressOrId::Id(AccountId(account_id)))
-        } else {
-            let address_str = if let Some(address_str) = account_address_or_id.strip_prefix("0x") {
-                address_str
-            } else {
-                account_address_or_id
-            };
-
-            if let Ok(address) = Address::from_str(address_str) {
-                Ok(AccountAddressOrId::Address(address))
-            } else {
-                Err(Error::from(InvalidDataError::InvalidAccountIdOrAddress))
-            }
-        }
+        parse_account_id_or_address(account_address_or_id)
     }
 
     async fn api_account(
@@ -111,6 +128,31 @@ impl ApiAccountData {
         account_id: AccountId,
         last_update_in_block: BlockNumber,
         storage: &mut StorageProcessor<'_>,
+    ) -> Result<Account, Error> {
+        let account_type = storage
+            .chain()
+            .account_schema()
+            .account_type_by_id(account_id)
+            .await
+            .map_err(Error::storage)?
+            .map(|t| t.into());
+        self.api_account_with_type(
+            account,
+            account_id,
+            last_update_in_block,
+            account_type,
+            storage,
+        )
+        .await
+    }
+
+    async fn api_account_with_type(
+        &self,
+        account: zksync_types::Account,
+        account_id: AccountId,
+        last_update_in_block: BlockNumber,
+        account_type: Option<EthAccountType>,
+        storage: &mut StorageProcessor<'_>,
     ) -> Result<Account, Error> {
         let mut balances = BTreeMap::new();
         let mut nfts = BTreeMap::new();
@@ -149,13 +191,6 @@ impl ApiAccountData {
             .map(|(id, nft)| (*id, nft.clone().into()))
             .collect();
 
-        let account_type = storage
-            .chain()
-            .account_schema()
-            .account_type_by_id(account_id)
-            .await
-            .map_err(Error::storage)?
-            .map(|t| t.into());
         Ok(Account {
             account_id,
             address: account.address,
@@ -169,7 +204,7 @@ impl ApiAccountData {
         })
     }
 
-    async fn account_committed_info(
+    pub(super) async fn account_committed_info(
         &self,
         account_id: AccountId,
     ) -> Result<Option<Account>, Error> {
@@ -202,7 +237,7 @@ impl ApiAccountData {
         result
     }
 
-    async fn account_finalized_info(
+    pub(super) async fn account_finalized_info(
         &self,
         account_id: AccountId,
     ) -> Result<Option<Account>, Error> {
@@ -290,7 +325,101 @@ impl ApiAccountData {
         })
     }
 
-    async fn account_txs(
+    /// Loads committed and finalized states of the requested accounts.
+    /// All the states are loaded by a fixed number of queries regardless of the batch size.
+    async fn accounts_batch(
+        &self,
+        accounts: Vec<(String, AccountAddressOrId)>,
+    ) -> Result<Vec<AccountBatchItem>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+
+        let addresses: Vec<Address> = accounts
+            .iter()
+            .filter_map(|(_, address_or_id)| match address_or_id {
+                AccountAddressOrId::Address(address) => Some(*address),
+                AccountAddressOrId::Id(_) => None,
+            })
+            .collect();
+        let ids_by_address = transaction
+            .chain()
+            .account_schema()
+            .account_ids_by_addresses(&addresses)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut account_ids: Vec<AccountId> = accounts
+            .iter()
+            .filter_map(|(_, address_or_id)| match address_or_id {
+                AccountAddressOrId::Address(address) => ids_by_address.get(address).copied(),
+                AccountAddressOrId::Id(account_id) => Some(*account_id),
+            })
+            .collect();
+        account_ids.sort_unstable();
+        account_ids.dedup();
+        let snapshots: HashMap<_, _> = transaction
+            .chain()
+            .account_schema()
+            .account_snapshots_by_ids(&account_ids)
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .map(|snapshot| (snapshot.account_id, snapshot))
+            .collect();
+
+        let mut items = Vec::with_capacity(accounts.len());
+        for (requested, address_or_id) in accounts {
+            let account_id = match address_or_id {
+                AccountAddressOrId::Address(address) => ids_by_address.get(&address).copied(),
+                AccountAddressOrId::Id(account_id) => Some(account_id),
+            };
+            let snapshot = account_id.and_then(|account_id| snapshots.get(&account_id));
+
+            let (committed, finalized) = if let Some(snapshot) = snapshot {
+                let account_type: Option<EthAccountType> = snapshot.account_type.map(|t| t.into());
+                let committed = if let Some((last_block, account)) = &snapshot.committed {
+                    Some(
+                        self.api_account_with_type(
+                            account.clone(),
+                            snapshot.account_id,
+                            *last_block,
+                            account_type.clone(),
+                            &mut transaction,
+                        )
+                        .await?,
+                    )
+                } else {
+                    None
+                };
+                let finalized = if let Some((last_block, account)) = &snapshot.verified {
+                    Some(
+                        self.api_account_with_type(
+                            account.clone(),
+                            snapshot.account_id,
+                            *last_block,
+                            account_type,
+                            &mut transaction,
+                        )
+                        .await?,
+                    )
+                } else {
+                    None
+                };
+                (committed, finalized)
+            } else {
+                (None, None)
+            };
+            items.push(AccountBatchItem {
+                requested,
+                committed,
+                finalized,
+            });
+        }
+        transaction.commit().await.map_err(Error::storage)?;
+        Ok(items)
+    }
+
+    pub(super) async fn account_txs(
         &self,
         query: PaginationQuery<ApiEither<TxHash>>,
         address: Address,
@@ -451,6 +580,26 @@ async fn account_pending_txs(
     res
 }
 
+async fn accounts_batch(
+    data: web::Data<ApiAccountData>,
+    Json(request): Json<AccountsBatchRequest>,
+) -> ApiResult<Vec<AccountBatchItem>> {
+    let start = Instant::now();
+    if request.accounts.len() > MAX_ACCOUNTS_BATCH_SIZE {
+        return Error::from(InvalidDataError::AccountsBatchTooBig).into();
+    }
+
+    let mut accounts = Vec::with_capacity(request.accounts.len());
+    for requested in request.accounts {
+        let address_or_id = api_try!(data.parse_account_id_or_address(&requested));
+        accounts.push((requested, address_or_id));
+    }
+
+    let res = data.accounts_batch(accounts).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "accounts_batch");
+    res
+}
+
 pub fn api_scope(
     pool: ConnectionPool,
     tokens: TokenDBCache,
@@ -460,6 +609,7 @@ pub fn api_scope(
 
     web::scope("accounts")
         .app_data(web::Data::new(data))
+        .route("batch", web::post().to(accounts_batch))
         .route(
             "{account_id_or_address}/committed",
             web::get().to(account_committed_info),
@@ -682,6 +832,30 @@ mod tests {
         assert_eq!(account_full_info.finalized, account_finalized_info);
         assert_eq!(account_full_info.depositing, expected_depositing);
 
+        // Get the same account by id and by address, along with the unknown one, in one batch.
+        let requested = vec![
+            account_id.to_string(),
+            format!("{:?}", address),
+            format!("{:?}", Address::repeat_byte(0xfe)),
+        ];
+        let response = client.accounts_batch(requested.clone()).await?;
+        let batch: Vec<AccountBatchItem> = deserialize_response_result(response)?;
+        assert_eq!(batch.len(), requested.len());
+        for (item, requested) in batch.iter().zip(&requested) {
+            assert_eq!(&item.requested, requested);
+        }
+        for item in &batch[..2] {
+            assert_eq!(item.committed, account_full_info.committed);
+            assert_eq!(item.finalized, account_full_info.finalized);
+        }
+        assert_eq!(batch[2].committed, None);
+        assert_eq!(batch[2].finalized, None);
+
+        let response = client
+            .accounts_batch(vec![account_id.to_string(); MAX_ACCOUNTS_BATCH_SIZE + 1])
+            .await?;
+        assert!(deserialize_response_result::<Vec<AccountBatchItem>>(response).is_err());
+
         let query = PaginationQuery {
             from: ApiEither::from(tx_hash),
             limit: 1,
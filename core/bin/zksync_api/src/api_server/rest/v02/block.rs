@@ -50,13 +50,13 @@ pub fn block_info_from_details(details: StorageBlockDetails) -> BlockInfo {
 
 /// Shared data between `api/v0.2/blocks` endpoints.
 #[derive(Debug, Clone)]
-struct ApiBlockData {
+pub(super) struct ApiBlockData {
     pool: ConnectionPool,
     verified_blocks_cache: BlockDetailsCache,
 }
 
 impl ApiBlockData {
-    fn new(pool: ConnectionPool, verified_blocks_cache: BlockDetailsCache) -> Self {
+    pub(super) fn new(pool: ConnectionPool, verified_blocks_cache: BlockDetailsCache) -> Self {
         Self {
             pool,
             verified_blocks_cache,
@@ -66,7 +66,10 @@ impl ApiBlockData {
     /// Returns information about block with the specified number.
     ///
     /// This method caches some of the verified blocks.
-    async fn block_info(&self, block_number: BlockNumber) -> Result<Option<BlockInfo>, Error> {
+    pub(super) async fn block_info(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockInfo>, Error> {
         let details = self
             .verified_blocks_cache
             .get(&self.pool, block_number)
@@ -100,7 +103,7 @@ impl ApiBlockData {
         }
     }
 
-    async fn block_page(
+    pub(super) async fn block_page(
         &self,
         query: PaginationQuery<ApiEither<BlockNumber>>,
     ) -> Result<Paginated<BlockInfo, BlockNumber>, Error> {
@@ -108,7 +111,7 @@ impl ApiBlockData {
         storage.paginate_checked(&query).await
     }
 
-    async fn transaction_page(
+    pub(super) async fn transaction_page(
         &self,
         block_number: BlockNumber,
         query: PaginationQuery<ApiEither<TxHash>>,
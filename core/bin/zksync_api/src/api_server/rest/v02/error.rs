@@ -7,7 +7,11 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 use thiserror::Error;
 
 // Workspace uses
-use zksync_api_types::v02::pagination::{UnknownFromParameter, MAX_LIMIT};
+use zksync_api_types::v02::{
+    account::MAX_ACCOUNTS_BATCH_SIZE,
+    pagination::{UnknownFromParameter, MAX_LIMIT},
+    stats::MAX_STATS_POINTS,
+};
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 
 // Local uses
@@ -27,6 +31,10 @@ pub enum ErrorCode {
     PaginationLimitTooBig = 206,
     QueryDeserializationError = 207,
     InvalidNFTTokenId = 208,
+    AccountsBatchTooBig = 209,
+    EmptyTxsSearchFilters = 210,
+    InvalidTxType = 211,
+    InvalidStatsRange = 212,
     StorageError = 300,
     TokenNotFound = 500,
     ExternalApiError = 501,
@@ -104,6 +112,20 @@ pub enum InvalidDataError {
     PaginationLimitTooBig,
     #[error("NFT token ID should be greater than or equal to {}", MIN_NFT_TOKEN_ID)]
     InvalidNFTTokenId,
+    #[error(
+        "Number of accounts in the batch should be less than or equal to {}",
+        MAX_ACCOUNTS_BATCH_SIZE
+    )]
+    AccountsBatchTooBig,
+    #[error("At least one of `from`, `to` or `token` filters should be specified")]
+    EmptyTxsSearchFilters,
+    #[error("Unknown transaction type")]
+    InvalidTxType,
+    #[error(
+        "Statistics range should be non-empty and contain at most {} periods",
+        MAX_STATS_POINTS
+    )]
+    InvalidStatsRange,
 }
 
 impl ApiError for InvalidDataError {
@@ -121,6 +143,10 @@ impl ApiError for InvalidDataError {
             Self::TransactionNotFound => ErrorCode::TransactionNotFound,
             Self::PaginationLimitTooBig => ErrorCode::PaginationLimitTooBig,
             Self::InvalidNFTTokenId => ErrorCode::InvalidNFTTokenId,
+            Self::AccountsBatchTooBig => ErrorCode::AccountsBatchTooBig,
+            Self::EmptyTxsSearchFilters => ErrorCode::EmptyTxsSearchFilters,
+            Self::InvalidTxType => ErrorCode::InvalidTxType,
+            Self::InvalidStatsRange => ErrorCode::InvalidStatsRange,
         }
     }
 }
@@ -0,0 +1,66 @@
+//! Batch loading of the objects referenced by many nodes of the same query.
+
+// Built-in uses
+use std::collections::HashMap;
+
+// External uses
+use async_graphql::dataloader::Loader;
+use async_trait::async_trait;
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{Token, TokenLike};
+
+// Local uses
+use crate::api_server::rest::v02::error::Error;
+
+/// Loads the tokens of the account balances by their symbols,
+/// so the tokens missing in the cache are requested from the database with a single query.
+pub struct TokenLoader {
+    pub(super) pool: ConnectionPool,
+    pub(super) tokens: TokenDBCache,
+}
+
+#[async_trait]
+impl Loader<String> for TokenLoader {
+    type Value = Token;
+    type Error = Error;
+
+    async fn load(&self, symbols: &[String]) -> Result<HashMap<String, Token>, Error> {
+        let mut tokens = HashMap::with_capacity(symbols.len());
+        let mut missing = Vec::new();
+        for symbol in symbols {
+            let cached = self
+                .tokens
+                .try_get_token_from_cache(TokenLike::Symbol(symbol.clone()))
+                .await;
+            match cached {
+                Some(token) => {
+                    tokens.insert(symbol.clone(), token);
+                }
+                None => missing.push(symbol.clone()),
+            }
+        }
+        if missing.is_empty() {
+            return Ok(tokens);
+        }
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let loaded = storage
+            .tokens_schema()
+            .get_tokens_by_symbols(&missing)
+            .await
+            .map_err(Error::storage)?;
+        for symbol in missing {
+            // Symbols are compared case-insensitively, the same way as in `get_token`.
+            let token = loaded
+                .iter()
+                .find(|token| token.symbol.eq_ignore_ascii_case(&symbol));
+            if let Some(token) = token {
+                tokens.insert(symbol, token.clone());
+            }
+        }
+        Ok(tokens)
+    }
+}
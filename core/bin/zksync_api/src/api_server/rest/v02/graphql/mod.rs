@@ -0,0 +1,304 @@
+//! GraphQL part of API implementation.
+//!
+//! Exposes the same data as the REST endpoints (blocks, transactions, accounts and tokens),
+//! but allows to resolve nested objects within a single request.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, HttpResponse, Scope};
+use async_graphql::{
+    connection::{Connection, Edge},
+    dataloader::DataLoader,
+    http::{playground_source, GraphQLPlaygroundConfig},
+    EmptyMutation, EmptySubscription, ErrorExtensions, Schema,
+};
+use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
+
+// Workspace uses
+use zksync_api_types::v02::pagination::MAX_LIMIT;
+use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::TokenDBCache;
+
+// Local uses
+use self::{loader::TokenLoader, query::QueryRoot};
+use super::{account::ApiAccountData, block::ApiBlockData, error::Error};
+use crate::utils::block_details_cache::BlockDetailsCache;
+
+mod loader;
+mod query;
+mod types;
+
+/// Page size used when the `first` argument is omitted.
+const DEFAULT_PAGE_SIZE: u32 = 20;
+/// REST pagination includes the item passed in `from` while GraphQL cursors are exclusive,
+/// so two slots of the REST page are reserved for the cursor item and for the next page check.
+const MAX_PAGE_SIZE: u32 = MAX_LIMIT - 2;
+/// Maximum depth of the nested objects in a single query.
+const MAX_QUERY_DEPTH: usize = 8;
+/// Maximum complexity of a single query, roughly the number of resolved fields.
+/// Fields of the connection items are counted once per requested item.
+const MAX_QUERY_COMPLEXITY: usize = 5_000;
+
+pub type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Shared data between GraphQL resolvers.
+#[derive(Clone)]
+pub(super) struct ApiGraphQlData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    blocks: ApiBlockData,
+    accounts: ApiAccountData,
+}
+
+impl ApiGraphQlData {
+    fn new(
+        pool: ConnectionPool,
+        tokens: TokenDBCache,
+        verified_blocks_cache: BlockDetailsCache,
+        confirmations_for_eth_event: u64,
+    ) -> Self {
+        Self {
+            blocks: ApiBlockData::new(pool.clone(), verified_blocks_cache),
+            accounts: ApiAccountData::new(
+                pool.clone(),
+                tokens.clone(),
+                confirmations_for_eth_event,
+            ),
+            pool,
+            tokens,
+        }
+    }
+}
+
+impl From<Error> for async_graphql::Error {
+    fn from(err: Error) -> Self {
+        async_graphql::Error::new(err.message).extend_with(|_, extensions| {
+            extensions.set("type", err.error_type);
+            extensions.set("code", err.code as u16);
+        })
+    }
+}
+
+/// Checks the requested page size and returns the default one if it's not specified.
+fn page_size(first: Option<i32>) -> async_graphql::Result<u32> {
+    match first {
+        None => Ok(DEFAULT_PAGE_SIZE),
+        Some(first) if first > 0 && first as u32 <= MAX_PAGE_SIZE => Ok(first as u32),
+        Some(_) => Err(async_graphql::Error::new(format!(
+            "`first` argument must be between 1 and {}",
+            MAX_PAGE_SIZE
+        ))),
+    }
+}
+
+/// Complexity of the paginated field, which resolves its fields for every requested item.
+/// Invalid page sizes are rejected by `page_size` later, so the maximum one is assumed for them.
+fn connection_complexity(first: Option<i32>, child_complexity: usize) -> usize {
+    let page_size = match first {
+        None => DEFAULT_PAGE_SIZE,
+        Some(first) => (first.max(1) as u32).min(MAX_PAGE_SIZE),
+    };
+    page_size as usize * child_complexity
+}
+
+/// Builds a forward connection from the page loaded with an inclusive REST pagination.
+///
+/// The page is expected to be requested with `page_size + 1` items (`page_size + 2` if cursor
+/// is set), so it's possible to skip the cursor item and to find out whether there is a next page.
+fn into_connection<T, F>(
+    mut items: Vec<T>,
+    after: Option<String>,
+    page_size: u32,
+    cursor_of: F,
+) -> Connection<String, T>
+where
+    T: async_graphql::OutputType,
+    F: Fn(&T) -> String,
+{
+    if let Some(after) = &after {
+        if items.first().map(&cursor_of).as_ref() == Some(after) {
+            items.remove(0);
+        }
+    }
+
+    let has_next_page = items.len() > page_size as usize;
+    items.truncate(page_size as usize);
+
+    let mut connection = Connection::new(after.is_some(), has_next_page);
+    connection.edges.extend(
+        items
+            .into_iter()
+            .map(|item| Edge::new(cursor_of(&item), item)),
+    );
+    connection
+}
+
+pub fn build_schema(
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    verified_blocks_cache: BlockDetailsCache,
+    confirmations_for_eth_event: u64,
+) -> ApiSchema {
+    let token_loader = TokenLoader {
+        pool: pool.clone(),
+        tokens: tokens.clone(),
+    };
+    let data = ApiGraphQlData::new(
+        pool,
+        tokens,
+        verified_blocks_cache,
+        confirmations_for_eth_event,
+    );
+
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(data)
+        .data(DataLoader::new(token_loader, tokio::spawn))
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+// Server implementation
+
+async fn graphql(schema: web::Data<ApiSchema>, request: GraphQLRequest) -> GraphQLResponse {
+    let start = Instant::now();
+    let res = schema.execute(request.into_inner()).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "graphql");
+    res
+}
+
+async fn graphql_playground() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(playground_source(GraphQLPlaygroundConfig::new("graphql")))
+}
+
+pub fn api_scope(
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    verified_blocks_cache: BlockDetailsCache,
+    confirmations_for_eth_event: u64,
+) -> Scope {
+    let schema = build_schema(
+        pool,
+        tokens,
+        verified_blocks_cache,
+        confirmations_for_eth_event,
+    );
+
+    web::scope("graphql")
+        .app_data(web::Data::new(schema))
+        .route("", web::post().to(graphql))
+        .route("", web::get().to(graphql_playground))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::test_utils::TestServerConfig;
+    use serde_json::json;
+    use std::time::Duration;
+    use zksync_types::BlockNumber;
+
+    async fn execute(schema: &ApiSchema, query: &str) -> anyhow::Result<serde_json::Value> {
+        let response = schema.execute(query).await;
+        anyhow::ensure!(
+            response.errors.is_empty(),
+            "Query failed: {:?}",
+            response.errors
+        );
+        Ok(response.data.into_json()?)
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn graphql_queries() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let schema = build_schema(
+            cfg.pool.clone(),
+            TokenDBCache::new(Duration::from_secs(5)),
+            BlockDetailsCache::new(10),
+            cfg.config.eth_watch.confirmations_for_eth_event,
+        );
+
+        let last_block = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .get_last_committed_confirmed_block()
+                .await?
+        };
+
+        // Blocks are returned starting from the latest one, and the cursor is exclusive.
+        let data = execute(
+            &schema,
+            "{ blocks(first: 2) { edges { cursor node { number } } pageInfo { hasNextPage } } }",
+        )
+        .await?;
+        let edges = data["blocks"]["edges"].as_array().unwrap();
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges[0]["node"]["number"], json!(*last_block));
+        assert_eq!(edges[1]["node"]["number"], json!(*last_block - 1));
+        assert_eq!(data["blocks"]["pageInfo"]["hasNextPage"], json!(true));
+
+        let cursor = edges[1]["cursor"].as_str().unwrap();
+        let data = execute(
+            &schema,
+            &format!(
+                r#"{{ blocks(first: 1, after: "{}") {{ edges {{ node {{ number }} }} }} }}"#,
+                cursor
+            ),
+        )
+        .await?;
+        assert_eq!(
+            data["blocks"]["edges"][0]["node"]["number"],
+            json!(*last_block - 2)
+        );
+
+        // Transactions of the block are resolved along with the block itself.
+        let block_number = BlockNumber(1);
+        let expected_txs = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(block_number)
+                .await?
+        };
+        let data = execute(
+            &schema,
+            "{ block(number: 1) { number transactions(first: 50) { edges { node { hash block { number } } } } } }",
+        )
+        .await?;
+        let txs = data["block"]["transactions"]["edges"].as_array().unwrap();
+        assert_eq!(txs.len(), expected_txs.len().min(50));
+        for tx in txs {
+            assert_eq!(tx["node"]["block"]["number"], json!(*block_number));
+        }
+
+        let data = execute(&schema, r#"{ token(idOrSymbol: "ETH") { id symbol } }"#).await?;
+        assert_eq!(data["token"], json!({ "id": 0, "symbol": "ETH" }));
+
+        // Unknown account ids are resolved to `null` rather than to an error.
+        let data = execute(&schema, r#"{ account(idOrAddress: "1000000") { id } }"#).await?;
+        assert_eq!(data["account"], json!(null));
+
+        // Complexity of the nested connections grows with the requested page sizes.
+        let response = schema
+            .execute(
+                "{ blocks(first: 50) { edges { node { transactions(first: 50) { edges { node { hash } } } } } } }",
+            )
+            .await;
+        assert!(!response.errors.is_empty());
+
+        Ok(())
+    }
+}
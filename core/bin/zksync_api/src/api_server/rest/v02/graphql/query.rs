@@ -0,0 +1,172 @@
+// Built-in uses
+
+// External uses
+use async_graphql::{connection::Connection, Context, Object, Result};
+
+// Workspace uses
+use zksync_api_types::v02::pagination::{ApiEither, PaginationDirection, PaginationQuery};
+use zksync_types::{tx::TxHash, BlockNumber, TokenId, TokenLike};
+
+// Local uses
+use super::{
+    connection_complexity, into_connection, page_size,
+    types::{AccountObject, BlockObject, NftObject, TokenObject, TransactionObject},
+    ApiGraphQlData,
+};
+use crate::api_server::rest::v02::{
+    error::{Error, ErrorCode},
+    paginate_trait::Paginate,
+};
+
+/// Root of all the GraphQL queries.
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Returns the block with the specified number.
+    async fn block(&self, ctx: &Context<'_>, number: u32) -> Result<Option<BlockObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        let block = data.blocks.block_info(BlockNumber(number)).await?;
+        Ok(block.map(BlockObject))
+    }
+
+    /// Returns the blocks starting from the latest one.
+    #[graphql(complexity = "connection_complexity(first, child_complexity)")]
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<String, BlockObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        let page_size = page_size(first)?;
+
+        let from = match &after {
+            Some(cursor) => {
+                let block_number: u32 = cursor.parse()?;
+                if block_number <= 1 {
+                    return Ok(Connection::new(true, false));
+                }
+                ApiEither::from(BlockNumber(block_number - 1))
+            }
+            None => "latest".parse()?,
+        };
+        let query = PaginationQuery {
+            from,
+            limit: page_size + 1,
+            direction: PaginationDirection::Older,
+        };
+
+        let blocks = data.blocks.block_page(query).await?.list;
+        Ok(into_connection(
+            blocks.into_iter().map(BlockObject).collect(),
+            after,
+            page_size,
+            |block| block.0.block_number.to_string(),
+        ))
+    }
+
+    /// Returns the transaction (either L1 or L2) with the specified hash.
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+        hash: String,
+    ) -> Result<Option<TransactionObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        let tx_hash: TxHash = hash.parse()?;
+
+        let mut storage = data.pool.access_storage().await.map_err(Error::storage)?;
+        let tx_data = storage
+            .chain()
+            .operations_ext_schema()
+            .tx_data_api_v02(tx_hash.as_ref())
+            .await
+            .map_err(Error::storage)?;
+        Ok(tx_data.map(|tx_data| TransactionObject(tx_data.tx)))
+    }
+
+    /// Returns the account with the specified id or address.
+    ///
+    /// Accounts that don't exist in the network yet are still returned
+    /// if requested by address, but they don't have any state.
+    async fn account(
+        &self,
+        ctx: &Context<'_>,
+        id_or_address: String,
+    ) -> Result<Option<AccountObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        let address_or_id = data.accounts.parse_account_id_or_address(&id_or_address)?;
+
+        let id = data
+            .accounts
+            .get_id_by_address_or_id(address_or_id.clone())
+            .await?;
+        match data
+            .accounts
+            .get_address_by_address_or_id(address_or_id)
+            .await
+        {
+            Ok(address) => Ok(Some(AccountObject { address, id })),
+            // Requested account id doesn't exist.
+            Err(err) if err.code == ErrorCode::AccountNotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Returns the token with the specified id, symbol or address.
+    async fn token(&self, ctx: &Context<'_>, id_or_symbol: String) -> Result<Option<TokenObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+
+        let mut storage = data.pool.access_storage().await.map_err(Error::storage)?;
+        let token = data
+            .tokens
+            .get_token(&mut storage, TokenLike::parse(&id_or_symbol))
+            .await
+            .map_err(Error::storage)?;
+        Ok(token.map(TokenObject))
+    }
+
+    /// Returns the ERC20 tokens in the ascending order of their ids.
+    #[graphql(complexity = "connection_complexity(first, child_complexity)")]
+    async fn tokens(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<String, TokenObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        let page_size = page_size(first)?;
+
+        let from = match &after {
+            Some(cursor) => TokenId(cursor.parse()?),
+            None => TokenId(0),
+        };
+        let query = PaginationQuery {
+            from: ApiEither::from(from),
+            limit: page_size + 2,
+            direction: PaginationDirection::Newer,
+        };
+
+        let mut storage = data.pool.access_storage().await.map_err(Error::storage)?;
+        let tokens = storage.paginate_checked(&query).await?.list;
+        Ok(into_connection(
+            tokens.into_iter().map(TokenObject).collect(),
+            after,
+            page_size,
+            |token| token.0.id.to_string(),
+        ))
+    }
+
+    /// Returns the NFT with the specified id.
+    async fn nft(&self, ctx: &Context<'_>, id: u32) -> Result<Option<NftObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+
+        let mut storage = data.pool.access_storage().await.map_err(Error::storage)?;
+        let nft = data
+            .tokens
+            .get_nft_by_id(&mut storage, TokenId(id))
+            .await
+            .map_err(Error::storage)?;
+        Ok(nft.map(|nft| NftObject(nft.into())))
+    }
+}
@@ -0,0 +1,389 @@
+//! GraphQL representations of the API v0.2 types.
+//!
+//! All the hashes and addresses are represented as `0x`-prefixed hex strings,
+//! and all the amounts are represented as decimal strings.
+
+// Built-in uses
+
+// External uses
+use async_graphql::{
+    connection::Connection, dataloader::DataLoader, Context, Enum, Json, Object, Result,
+};
+use chrono::{DateTime, Utc};
+
+// Workspace uses
+use zksync_api_types::v02::{
+    account::{Account, EthAccountType},
+    block::{BlockInfo, BlockStatus},
+    pagination::{ApiEither, PaginationDirection, PaginationQuery},
+    token::NFT,
+    transaction::{Transaction, TxInBlockStatus},
+};
+use zksync_crypto::convert::FeConvert;
+use zksync_types::{tx::TxHash, AccountId, Address, Token, TokenLike, H256};
+
+// Local uses
+use super::{
+    connection_complexity, into_connection, loader::TokenLoader, page_size, ApiGraphQlData,
+};
+use crate::api_server::rest::v02::error::Error;
+
+fn hex_hash(hash: H256) -> String {
+    format!("{:?}", hash)
+}
+
+fn hex_tx_hash(hash: &TxHash) -> String {
+    format!("0x{}", hash.to_string_without_prefix())
+}
+
+fn hex_address(address: Address) -> String {
+    format!("{:?}", address)
+}
+
+/// Loads the page of transactions with an exclusive `after` cursor.
+async fn transactions_connection<F, Fut>(
+    after: Option<String>,
+    first: Option<i32>,
+    load: F,
+) -> Result<Connection<String, TransactionObject>>
+where
+    F: FnOnce(PaginationQuery<ApiEither<TxHash>>) -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<Vec<Transaction>, Error>>,
+{
+    let page_size = page_size(first)?;
+    let (from, limit) = match &after {
+        Some(cursor) => (ApiEither::from(cursor.parse::<TxHash>()?), page_size + 2),
+        None => ("latest".parse()?, page_size + 1),
+    };
+    let query = PaginationQuery {
+        from,
+        limit,
+        direction: PaginationDirection::Older,
+    };
+
+    let txs = load(query).await?;
+    Ok(into_connection(
+        txs.into_iter().map(TransactionObject).collect(),
+        after,
+        page_size,
+        |tx| hex_tx_hash(&tx.0.tx_hash),
+    ))
+}
+
+/// Status of the block or transaction.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Queued,
+    Committed,
+    Finalized,
+    Rejected,
+}
+
+impl From<BlockStatus> for Status {
+    fn from(status: BlockStatus) -> Self {
+        TxInBlockStatus::from(status).into()
+    }
+}
+
+impl From<TxInBlockStatus> for Status {
+    fn from(status: TxInBlockStatus) -> Self {
+        match status {
+            TxInBlockStatus::Queued => Self::Queued,
+            TxInBlockStatus::Committed => Self::Committed,
+            TxInBlockStatus::Finalized => Self::Finalized,
+            TxInBlockStatus::Rejected => Self::Rejected,
+        }
+    }
+}
+
+pub struct BlockObject(pub BlockInfo);
+
+#[Object(name = "Block")]
+impl BlockObject {
+    async fn number(&self) -> u32 {
+        *self.0.block_number
+    }
+
+    async fn new_state_root(&self) -> String {
+        format!("0x{}", self.0.new_state_root.to_hex())
+    }
+
+    async fn block_size(&self) -> u64 {
+        self.0.block_size
+    }
+
+    async fn commit_tx_hash(&self) -> Option<String> {
+        self.0.commit_tx_hash.map(hex_hash)
+    }
+
+    async fn verify_tx_hash(&self) -> Option<String> {
+        self.0.verify_tx_hash.map(hex_hash)
+    }
+
+    async fn committed_at(&self) -> DateTime<Utc> {
+        self.0.committed_at
+    }
+
+    async fn finalized_at(&self) -> Option<DateTime<Utc>> {
+        self.0.finalized_at
+    }
+
+    async fn status(&self) -> Status {
+        self.0.status.into()
+    }
+
+    /// Transactions of the block starting from the last one.
+    #[graphql(complexity = "connection_complexity(first, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<String, TransactionObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        let block_number = self.0.block_number;
+        transactions_connection(after, first, |query| async move {
+            data.blocks
+                .transaction_page(block_number, query)
+                .await
+                .map(|page| page.list)
+        })
+        .await
+    }
+}
+
+pub struct TransactionObject(pub Transaction);
+
+#[Object(name = "Transaction")]
+impl TransactionObject {
+    async fn hash(&self) -> String {
+        hex_tx_hash(&self.0.tx_hash)
+    }
+
+    async fn block_number(&self) -> Option<u32> {
+        self.0.block_number.map(|block_number| *block_number)
+    }
+
+    async fn block_index(&self) -> Option<u32> {
+        self.0.block_index
+    }
+
+    async fn status(&self) -> Status {
+        self.0.status.into()
+    }
+
+    async fn fail_reason(&self) -> Option<&str> {
+        self.0.fail_reason.as_deref()
+    }
+
+    async fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.0.created_at
+    }
+
+    async fn batch_id(&self) -> Option<u32> {
+        self.0.batch_id
+    }
+
+    /// Operation data in the same format as in the REST API.
+    async fn op(&self) -> Result<Json<serde_json::Value>> {
+        Ok(Json(serde_json::to_value(&self.0.op)?))
+    }
+
+    /// Block that includes the transaction, if any.
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<BlockObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        match self.0.block_number {
+            Some(block_number) => {
+                let block = data.blocks.block_info(block_number).await?;
+                Ok(block.map(BlockObject))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+pub struct AccountObject {
+    pub address: Address,
+    pub id: Option<AccountId>,
+}
+
+#[Object(name = "Account")]
+impl AccountObject {
+    async fn address(&self) -> String {
+        hex_address(self.address)
+    }
+
+    async fn id(&self) -> Option<u32> {
+        self.id.map(|id| *id)
+    }
+
+    /// State of the account in the last committed block.
+    async fn committed(&self, ctx: &Context<'_>) -> Result<Option<AccountStateObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        match self.id {
+            Some(id) => {
+                let account = data.accounts.account_committed_info(id).await?;
+                Ok(account.map(AccountStateObject))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// State of the account in the last finalized block.
+    async fn finalized(&self, ctx: &Context<'_>) -> Result<Option<AccountStateObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        match self.id {
+            Some(id) => {
+                let account = data.accounts.account_finalized_info(id).await?;
+                Ok(account.map(AccountStateObject))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Transactions of the account starting from the last one,
+    /// optionally filtered by token (id, symbol or address).
+    #[graphql(complexity = "connection_complexity(first, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        first: Option<i32>,
+        token: Option<String>,
+    ) -> Result<Connection<String, TransactionObject>> {
+        let data = ctx.data_unchecked::<ApiGraphQlData>();
+        let address = self.address;
+        let token_like = token.map(|token| TokenLike::parse(&token));
+        transactions_connection(after, first, |query| async move {
+            data.accounts
+                .account_txs(query, address, token_like, None)
+                .await
+                .map(|page| page.list)
+        })
+        .await
+    }
+}
+
+pub struct AccountStateObject(pub Account);
+
+#[Object(name = "AccountState")]
+impl AccountStateObject {
+    async fn nonce(&self) -> u32 {
+        *self.0.nonce
+    }
+
+    async fn pub_key_hash(&self) -> String {
+        self.0.pub_key_hash.as_hex()
+    }
+
+    async fn last_update_in_block(&self) -> u32 {
+        *self.0.last_update_in_block
+    }
+
+    async fn account_type(&self) -> Option<&str> {
+        self.0
+            .account_type
+            .as_ref()
+            .map(|account_type| match account_type {
+                EthAccountType::Owned => "Owned",
+                EthAccountType::CREATE2 => "CREATE2",
+                EthAccountType::No2FA(_) => "No2FA",
+            })
+    }
+
+    async fn balances(&self) -> Vec<BalanceObject> {
+        self.0
+            .balances
+            .iter()
+            .map(|(symbol, amount)| BalanceObject {
+                symbol: symbol.clone(),
+                amount: amount.0.to_string(),
+            })
+            .collect()
+    }
+
+    async fn nfts(&self) -> Vec<NftObject> {
+        self.0.nfts.values().cloned().map(NftObject).collect()
+    }
+
+    async fn minted_nfts(&self) -> Vec<NftObject> {
+        self.0
+            .minted_nfts
+            .values()
+            .cloned()
+            .map(NftObject)
+            .collect()
+    }
+}
+
+pub struct BalanceObject {
+    symbol: String,
+    amount: String,
+}
+
+#[Object(name = "Balance")]
+impl BalanceObject {
+    async fn amount(&self) -> &str {
+        &self.amount
+    }
+
+    /// Tokens of all the requested balances are loaded at once.
+    async fn token(&self, ctx: &Context<'_>) -> Result<Option<TokenObject>> {
+        let loader = ctx.data_unchecked::<DataLoader<TokenLoader>>();
+        let token = loader.load_one(self.symbol.clone()).await?;
+        Ok(token.map(TokenObject))
+    }
+}
+
+pub struct TokenObject(pub Token);
+
+#[Object(name = "Token")]
+impl TokenObject {
+    async fn id(&self) -> u32 {
+        *self.0.id
+    }
+
+    async fn address(&self) -> String {
+        hex_address(self.0.address)
+    }
+
+    async fn symbol(&self) -> &str {
+        &self.0.symbol
+    }
+
+    async fn decimals(&self) -> u8 {
+        self.0.decimals
+    }
+}
+
+pub struct NftObject(pub NFT);
+
+#[Object(name = "NFT")]
+impl NftObject {
+    async fn id(&self) -> u32 {
+        *self.0.id
+    }
+
+    async fn content_hash(&self) -> String {
+        hex_hash(self.0.content_hash)
+    }
+
+    async fn serial_id(&self) -> u32 {
+        self.0.serial_id
+    }
+
+    async fn address(&self) -> String {
+        hex_address(self.0.address)
+    }
+
+    async fn symbol(&self) -> &str {
+        &self.0.symbol
+    }
+
+    async fn creator(&self) -> AccountObject {
+        AccountObject {
+            address: self.0.creator_address,
+            id: Some(self.0.creator_id),
+        }
+    }
+}
@@ -17,9 +17,11 @@ mod block;
 mod config;
 pub mod error;
 mod fee;
+mod graphql;
 mod paginate_impl;
 mod paginate_trait;
 mod response;
+mod stats;
 mod status;
 #[cfg(test)]
 pub mod test_utils;
@@ -54,6 +56,16 @@ pub(crate) fn api_scope(
         ))
         .service(config::api_scope(zk_config))
         .service(fee::api_scope(tx_sender.clone()))
+        .service(graphql::api_scope(
+            tx_sender.pool.clone(),
+            tx_sender.tokens.clone(),
+            tx_sender.blocks.clone(),
+            zk_config.eth_watch.confirmations_for_eth_event,
+        ))
+        .service(stats::api_scope(
+            tx_sender.pool.clone(),
+            tx_sender.tokens.clone(),
+        ))
         .service(status::api_scope(network_status))
         .service(token::api_scope(
             zk_config,
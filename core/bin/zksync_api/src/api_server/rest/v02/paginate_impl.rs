@@ -7,8 +7,8 @@ use zksync_api_types::{
     v02::{
         block::BlockInfo,
         pagination::{
-            AccountTxsRequest, ApiEither, BlockAndTxHash, Paginated, PaginationQuery,
-            PendingOpsRequest,
+            AccountTxsRequest, ApiEither, BlockAndTxHash, Paginated, PaginationDirection,
+            PaginationQuery, PendingOpsRequest, TxsSearchRequest,
         },
         transaction::{Transaction, TxHashSerializeWrapper},
     },
@@ -267,6 +267,52 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
     }
 }
 
+#[async_trait::async_trait]
+impl Paginate<TxsSearchRequest> for StorageProcessor<'_> {
+    type OutputObj = Transaction;
+    type OutputId = TxHashSerializeWrapper;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<TxsSearchRequest>,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let txs = transaction
+            .chain()
+            .operations_ext_schema()
+            .search_transactions(query)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::TransactionNotFound))?;
+        let count = transaction
+            .chain()
+            .operations_ext_schema()
+            .search_transactions_count(&query.from)
+            .await
+            .map_err(Error::storage)?;
+
+        transaction.commit().await.map_err(Error::storage)?;
+
+        // The latest matching transaction is used as a starting one if the `latest` was requested.
+        let tx_hash = match query.from.tx_hash.inner {
+            Either::Left(tx_hash) => tx_hash,
+            Either::Right(_) => match (query.direction, txs.first()) {
+                (PaginationDirection::Older, Some(tx)) => tx.tx_hash,
+                _ => Default::default(),
+            },
+        };
+
+        Ok(Paginated::new(
+            txs,
+            TxHashSerializeWrapper(tx_hash),
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
 #[async_trait::async_trait]
 impl Paginate<PendingOpsRequest> for StorageProcessor<'_> {
     type OutputObj = Transaction;
@@ -0,0 +1,358 @@
+//! Network statistics part of API implementation.
+
+// Built-in uses
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+use chrono::{DateTime, TimeZone, Utc};
+
+// Workspace uses
+use zksync_api_types::v02::stats::{
+    AccountsPoint, BlocksPoint, FeesPoint, StatsInterval, StatsQuery, TxCountsPoint,
+    DEFAULT_STATS_POINTS, MAX_STATS_POINTS,
+};
+use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::TokenId;
+
+// Local uses
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::api_try;
+
+/// Shared data between `api/v0.2/stats` endpoints.
+#[derive(Clone)]
+struct ApiStatsData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+}
+
+impl ApiStatsData {
+    fn new(pool: ConnectionPool, tokens: TokenDBCache) -> Self {
+        Self { pool, tokens }
+    }
+
+    async fn tx_counts(&self, range: StatsRange) -> Result<Vec<TxCountsPoint>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let stats = storage
+            .chain()
+            .stats_schema()
+            .tx_counts_stats(range.interval, range.from, range.to)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut points: HashMap<_, _> = range
+            .periods()
+            .map(|period| {
+                let point = TxCountsPoint {
+                    period,
+                    total: 0,
+                    by_type: BTreeMap::new(),
+                };
+                (period, point)
+            })
+            .collect();
+        for record in stats {
+            if let Some(point) = points.get_mut(&record.period) {
+                point.total += record.count as u64;
+                *point.by_type.entry(record.tx_type).or_default() += record.count as u64;
+            }
+        }
+        Ok(range.sorted(points))
+    }
+
+    async fn accounts(&self, range: StatsRange) -> Result<Vec<AccountsPoint>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut schema = storage.chain().stats_schema();
+        let active = schema
+            .active_accounts_stats(range.interval, range.from, range.to)
+            .await
+            .map_err(Error::storage)?;
+        let new = schema
+            .new_accounts_stats(range.interval, range.from, range.to)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut points: HashMap<_, _> = range
+            .periods()
+            .map(|period| {
+                let point = AccountsPoint {
+                    period,
+                    active: 0,
+                    new: 0,
+                };
+                (period, point)
+            })
+            .collect();
+        for record in active {
+            if let Some(point) = points.get_mut(&record.period) {
+                point.active = record.count as u64;
+            }
+        }
+        for record in new {
+            if let Some(point) = points.get_mut(&record.period) {
+                point.new = record.count as u64;
+            }
+        }
+        Ok(range.sorted(points))
+    }
+
+    async fn fees(&self, range: StatsRange) -> Result<Vec<FeesPoint>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let stats = storage
+            .chain()
+            .stats_schema()
+            .fees_stats(range.interval, range.from, range.to)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut points: HashMap<_, _> = range
+            .periods()
+            .map(|period| {
+                let point = FeesPoint {
+                    period,
+                    fees: BTreeMap::new(),
+                };
+                (period, point)
+            })
+            .collect();
+        for record in stats {
+            let token_id = TokenId(record.token_id as u32);
+            let symbol = self
+                .tokens
+                .token_symbol(&mut storage, token_id)
+                .await
+                .map_err(Error::storage)?
+                .unwrap_or_else(|| token_id.to_string());
+            if let Some(point) = points.get_mut(&record.period) {
+                point.fees.insert(symbol, record.amount().into());
+            }
+        }
+        Ok(range.sorted(points))
+    }
+
+    async fn blocks(&self, range: StatsRange) -> Result<Vec<BlocksPoint>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let stats = storage
+            .chain()
+            .stats_schema()
+            .blocks_stats(range.interval, range.from, range.to)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut points: HashMap<_, _> = range
+            .periods()
+            .map(|period| {
+                let point = BlocksPoint {
+                    period,
+                    blocks: 0,
+                    average_fill: 0.0,
+                };
+                (period, point)
+            })
+            .collect();
+        for record in stats {
+            if let Some(point) = points.get_mut(&record.period) {
+                point.blocks = record.blocks_count as u64;
+                if record.chunks_total > 0 {
+                    point.average_fill = record.chunks_used as f64 / record.chunks_total as f64;
+                }
+            }
+        }
+        Ok(range.sorted(points))
+    }
+}
+
+/// Validated range of the statistics series.
+#[derive(Debug, Clone, Copy)]
+struct StatsRange {
+    interval: StatsInterval,
+    /// Start of the first period of the series.
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+}
+
+impl StatsRange {
+    fn from_query(query: StatsQuery) -> Result<Self, Error> {
+        let interval = query.interval;
+        let to = query.to.unwrap_or_else(Utc::now);
+        let from = match query.from {
+            Some(from) => Self::period_start(from, interval),
+            None => {
+                Self::period_start(to, interval)
+                    - interval.duration() * (DEFAULT_STATS_POINTS - 1) as i32
+            }
+        };
+
+        let interval_secs = interval.duration().num_seconds();
+        let periods = ((to - from).num_seconds() + interval_secs - 1) / interval_secs;
+        if from >= to || periods > MAX_STATS_POINTS {
+            return Err(Error::from(InvalidDataError::InvalidStatsRange));
+        }
+        Ok(Self { interval, from, to })
+    }
+
+    /// Returns the start of the period containing the given time.
+    fn period_start(time: DateTime<Utc>, interval: StatsInterval) -> DateTime<Utc> {
+        // Both hours and days in UTC are aligned with the unix epoch.
+        let interval_secs = interval.duration().num_seconds();
+        let timestamp = time.timestamp();
+        Utc.timestamp_opt(timestamp - timestamp.rem_euclid(interval_secs), 0)
+            .unwrap()
+    }
+
+    /// Returns the starts of all periods in the range.
+    fn periods(&self) -> impl Iterator<Item = DateTime<Utc>> {
+        let (interval, to) = (self.interval.duration(), self.to);
+        std::iter::successors(Some(self.from), move |period| Some(*period + interval))
+            .take_while(move |period| *period < to)
+    }
+
+    fn sorted<T>(&self, mut points: HashMap<DateTime<Utc>, T>) -> Vec<T> {
+        self.periods()
+            .filter_map(|period| points.remove(&period))
+            .collect()
+    }
+}
+
+// Server implementation
+
+async fn tx_counts(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<StatsQuery>,
+) -> ApiResult<Vec<TxCountsPoint>> {
+    let start = Instant::now();
+    let range = api_try!(StatsRange::from_query(query));
+    let res = data.tx_counts(range).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "stats_tx_counts");
+    res
+}
+
+async fn accounts(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<StatsQuery>,
+) -> ApiResult<Vec<AccountsPoint>> {
+    let start = Instant::now();
+    let range = api_try!(StatsRange::from_query(query));
+    let res = data.accounts(range).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "stats_accounts");
+    res
+}
+
+async fn fees(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<StatsQuery>,
+) -> ApiResult<Vec<FeesPoint>> {
+    let start = Instant::now();
+    let range = api_try!(StatsRange::from_query(query));
+    let res = data.fees(range).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "stats_fees");
+    res
+}
+
+async fn blocks(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<StatsQuery>,
+) -> ApiResult<Vec<BlocksPoint>> {
+    let start = Instant::now();
+    let range = api_try!(StatsRange::from_query(query));
+    let res = data.blocks(range).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "stats_blocks");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool, tokens: TokenDBCache) -> Scope {
+    let data = ApiStatsData::new(pool, tokens);
+
+    web::scope("stats")
+        .app_data(web::Data::new(data))
+        .route("transactions", web::get().to(tx_counts))
+        .route("accounts", web::get().to(accounts))
+        .route("fees", web::get().to(fees))
+        .route("blocks", web::get().to(blocks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::ApiVersion;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn stats_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .stats_schema()
+                .update_stats_summary(u32::MAX)
+                .await?;
+        }
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            move |cfg| {
+                api_scope(
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let query = StatsQuery {
+            interval: StatsInterval::Day,
+            from: None,
+            to: None,
+        };
+        let response = client.tx_counts_stats(&query).await?;
+        let tx_counts: Vec<TxCountsPoint> = deserialize_response_result(response)?;
+        assert_eq!(tx_counts.len(), DEFAULT_STATS_POINTS as usize);
+        assert!(tx_counts
+            .iter()
+            .all(|point| point.total == point.by_type.values().sum::<u64>()));
+
+        let response = client.accounts_stats(&query).await?;
+        let accounts: Vec<AccountsPoint> = deserialize_response_result(response)?;
+        assert_eq!(accounts.len(), DEFAULT_STATS_POINTS as usize);
+
+        let response = client.fees_stats(&query).await?;
+        let fees: Vec<FeesPoint> = deserialize_response_result(response)?;
+        assert_eq!(fees.len(), DEFAULT_STATS_POINTS as usize);
+
+        let response = client.blocks_stats(&query).await?;
+        let blocks: Vec<BlocksPoint> = deserialize_response_result(response)?;
+        assert_eq!(blocks.len(), DEFAULT_STATS_POINTS as usize);
+        assert!(blocks
+            .iter()
+            .all(|point| point.average_fill >= 0.0 && point.average_fill <= 1.0));
+
+        // Too long range.
+        let query = StatsQuery {
+            interval: StatsInterval::Hour,
+            from: Some(Utc::now() - chrono::Duration::hours(MAX_STATS_POINTS + 1)),
+            to: None,
+        };
+        let response = client.tx_counts_stats(&query).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
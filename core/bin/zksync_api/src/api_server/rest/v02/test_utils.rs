@@ -3,6 +3,7 @@
 // Built-in uses
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::time::Duration;
 
 // External uses
 use actix_web::{web, App, Scope};
@@ -536,12 +537,12 @@ impl TestServerConfig {
 
                 // Get job id.
                 let stored_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue("test_prover", Duration::from_secs(60))
                     .await?
                     .unwrap()
                     .job_id;
                 let stored_aggregated_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue("test_prover", Duration::from_secs(60))
                     .await?
                     .unwrap()
                     .job_id;
@@ -550,7 +551,7 @@ impl TestServerConfig {
                 let proof = get_sample_single_proof();
                 let aggregated_proof = get_sample_aggregated_proof();
                 ProverSchema(&mut storage)
-                    .store_proof(stored_job_id, block_number, &proof)
+                    .store_proof(stored_job_id, block_number, &proof, "test_prover")
                     .await?;
                 ProverSchema(&mut storage)
                     .store_aggregated_proof(
@@ -558,6 +559,7 @@ impl TestServerConfig {
                         block_number,
                         block_number,
                         &aggregated_proof,
+                        "test_prover",
                     )
                     .await?;
 
@@ -10,18 +10,43 @@ use actix_web::{
 
 // Workspace uses
 use zksync_api_types::{
-    v02::transaction::{
-        ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, Receipt, SubmitBatchResponse,
-        Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData, TxHashSerializeWrapper,
-        TxInBlockStatus,
+    v02::{
+        account::AccountAddressOrId,
+        pagination::{ApiEither, Paginated, PaginationQuery, TxsSearchRequest},
+        transaction::{
+            ApiTxBatch, IncomingTxBatch, IncomingTxsSearchQuery, L1Receipt, L1Transaction, Receipt,
+            SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, Transaction, TransactionData,
+            TxData, TxHashSerializeWrapper, TxInBlockStatus,
+        },
     },
     TxWithSignature,
 };
-use zksync_types::{tx::TxHash, EthBlockId};
+use zksync_types::{tx::TxHash, Address, EthBlockId, TokenLike};
 
 // Local uses
-use super::{error::Error, response::ApiResult};
-use crate::api_server::tx_sender::{SubmitError, TxSender};
+use super::{
+    account::parse_account_id_or_address,
+    error::{Error, InvalidDataError},
+    paginate_trait::Paginate,
+    response::ApiResult,
+};
+use crate::{
+    api_server::tx_sender::{SubmitError, TxSender},
+    fee_ticker::PriceError,
+};
+
+/// Types of the operations that can be used as a `type` filter of the transactions search.
+const SEARCHABLE_TX_TYPES: &[&str] = &[
+    "Transfer",
+    "Withdraw",
+    "ChangePubKey",
+    "ForcedExit",
+    "MintNFT",
+    "WithdrawNFT",
+    "Swap",
+    "Deposit",
+    "FullExit",
+];
 
 /// Shared data between `api/v0.2/transactions` endpoints.
 #[derive(Clone)]
@@ -122,6 +147,95 @@ impl ApiTransactionData {
         }
     }
 
+    async fn address_by_id_or_address(
+        &self,
+        account_id_or_address: &str,
+    ) -> Result<Address, Error> {
+        match parse_account_id_or_address(account_id_or_address)? {
+            AccountAddressOrId::Address(address) => Ok(address),
+            AccountAddressOrId::Id(account_id) => {
+                let mut storage = self
+                    .tx_sender
+                    .pool
+                    .access_storage()
+                    .await
+                    .map_err(Error::storage)?;
+                storage
+                    .chain()
+                    .account_schema()
+                    .account_address_by_id(account_id)
+                    .await
+                    .map_err(Error::storage)?
+                    .ok_or_else(|| Error::from(InvalidDataError::AccountNotFound))
+            }
+        }
+    }
+
+    async fn search_txs(
+        &self,
+        query: IncomingTxsSearchQuery,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
+        if query.from.is_none() && query.to.is_none() && query.token.is_none() {
+            return Err(Error::from(InvalidDataError::EmptyTxsSearchFilters));
+        }
+        if let Some(tx_type) = &query.tx_type {
+            if !SEARCHABLE_TX_TYPES.contains(&tx_type.as_str()) {
+                return Err(Error::from(InvalidDataError::InvalidTxType));
+            }
+        }
+
+        let tx_hash: ApiEither<TxHash> = query
+            .cursor
+            .as_deref()
+            .unwrap_or("latest")
+            .parse()
+            .map_err(Error::from)?;
+        let from = match &query.from {
+            Some(from) => Some(self.address_by_id_or_address(from).await?),
+            None => None,
+        };
+        let to = match &query.to {
+            Some(to) => Some(self.address_by_id_or_address(to).await?),
+            None => None,
+        };
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let token = if let Some(token) = &query.token {
+            let token_like = TokenLike::parse(token);
+            Some(
+                self.tx_sender
+                    .tokens
+                    .get_token(&mut storage, token_like.clone())
+                    .await
+                    .map_err(Error::storage)?
+                    .ok_or_else(|| Error::from(PriceError::token_not_found(token_like)))?
+                    .id,
+            )
+        } else {
+            None
+        };
+
+        let search_query = PaginationQuery {
+            from: TxsSearchRequest {
+                tx_hash,
+                from,
+                to,
+                token,
+                tx_type: query.tx_type,
+                from_block: query.from_block,
+                to_block: query.to_block,
+            },
+            limit: query.limit,
+            direction: query.direction,
+        };
+        storage.paginate_checked(&search_query).await
+    }
+
     async fn get_batch(&self, batch_hash: TxHash) -> Result<Option<ApiTxBatch>, Error> {
         let mut storage = self
             .tx_sender
@@ -235,12 +349,23 @@ async fn get_batch(
     res
 }
 
+async fn search_txs(
+    data: web::Data<ApiTransactionData>,
+    web::Query(query): web::Query<IncomingTxsSearchQuery>,
+) -> ApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
+    let start = Instant::now();
+    let res = data.search_txs(query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "search_txs");
+    res
+}
+
 pub fn api_scope(tx_sender: TxSender) -> Scope {
     let data = ApiTransactionData::new(tx_sender);
 
     web::scope("transactions")
         .app_data(web::Data::new(data))
         .route("", web::post().to(submit_tx))
+        .route("", web::get().to(search_txs))
         .route("{tx_hash}", web::get().to(tx_status))
         .route("{tx_hash}/data", web::get().to(tx_data))
         .route("/batches", web::post().to(submit_batch))
@@ -266,6 +391,7 @@ mod tests {
     use std::str::FromStr;
     use tokio::task::JoinHandle;
     use zksync_api_types::v02::{
+        pagination::PaginationDirection,
         transaction::{L2Receipt, TxHashSerializeWrapper},
         ApiVersion,
     };
@@ -463,6 +589,43 @@ mod tests {
         let tx_data: Option<TxData> = deserialize_response_result(response)?;
         assert_eq!(tx_data.unwrap().tx.tx_hash, tx_hash);
 
+        // Search transactions of the first block by token.
+        let mut search_query = IncomingTxsSearchQuery {
+            from: None,
+            to: None,
+            token: Some("ETH".to_string()),
+            tx_type: None,
+            from_block: Some(BlockNumber(1)),
+            to_block: Some(BlockNumber(1)),
+            cursor: None,
+            limit: 2,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.search_txs(&search_query).await?;
+        let page: Paginated<Transaction, TxHashSerializeWrapper> =
+            deserialize_response_result(response)?;
+        assert!(!page.list.is_empty());
+        assert!(page.pagination.count as usize >= page.list.len());
+        for tx in &page.list {
+            assert_eq!(tx.block_number, Some(BlockNumber(1)));
+        }
+
+        // The cursor is inclusive, so the next page starts with the last transaction.
+        let last_tx_hash = page.list.last().unwrap().tx_hash;
+        search_query.cursor = Some(last_tx_hash.to_string());
+        let response = client.search_txs(&search_query).await?;
+        let next_page: Paginated<Transaction, TxHashSerializeWrapper> =
+            deserialize_response_result(response)?;
+        assert_eq!(next_page.list[0].tx_hash, last_tx_hash);
+
+        // At least one of the indexed filters is required.
+        search_query.token = None;
+        let response = client.search_txs(&search_query).await?;
+        assert!(
+            deserialize_response_result::<Paginated<Transaction, TxHashSerializeWrapper>>(response)
+                .is_err()
+        );
+
         let pending_tx_hash = {
             let mut storage = cfg.pool.access_storage().await?;
 
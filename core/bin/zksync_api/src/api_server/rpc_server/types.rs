@@ -12,7 +12,7 @@ use zksync_api_types::v02::{
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
 use zksync_storage::StorageProcessor;
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{Account, AccountId, Address, Nonce, PubKeyHash, TokenId};
+use zksync_types::{tx::TxHash, Account, AccountId, Address, Nonce, PubKeyHash, TokenId, H256};
 use zksync_utils::BigUintSerdeWrapper;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -119,6 +119,19 @@ pub struct ETHOpInfoResp {
     pub block: Option<BlockInfo>,
 }
 
+/// Notification about an operation that touched the subscribed address.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountActivityResp {
+    pub tx_hash: TxHash,
+    /// Hash of the Ethereum transaction, set for priority operations only.
+    pub eth_hash: Option<H256>,
+    pub op: serde_json::Value,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+    pub block: BlockInfo,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractAddressResp {
@@ -20,7 +20,9 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 use crate::fee_ticker::FeeTicker;
 use crate::{
     api_server::event_notify::{start_sub_notifier, EventNotifierRequest, EventSubscribeRequest},
-    api_server::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
+    api_server::rpc_server::types::{
+        AccountActivityResp, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+    },
     signature_checker::VerifySignatureRequest,
 };
 
@@ -82,6 +84,30 @@ pub trait RpcPubSub {
         meta: Option<Self::Metadata>,
         subscription: SubscriptionId,
     ) -> Result<bool>;
+
+    #[pubsub(
+        subscription = "account_activity",
+        subscribe,
+        name = "account_activity_subscribe",
+        alias("account_activity_sub")
+    )]
+    fn subscribe_account_activity(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<AccountActivityResp>,
+        addr: Address,
+        action_type: ActionType,
+    );
+    #[pubsub(
+        subscription = "account_activity",
+        unsubscribe,
+        name = "account_activity_unsubscribe"
+    )]
+    fn unsubscribe_account_activity(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
 }
 
 impl RpcPubSub for RpcSubApp {
@@ -170,6 +196,37 @@ impl RpcPubSub for RpcSubApp {
             .unwrap_or_default();
         Ok(true)
     }
+
+    fn subscribe_account_activity(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<AccountActivityResp>,
+        address: Address,
+        action: ActionType,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(
+                EventSubscribeRequest::AccountActivity {
+                    address,
+                    action,
+                    subscriber,
+                },
+            ))
+            .unwrap_or_default();
+    }
+
+    fn unsubscribe_account_activity(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
 }
 
 struct RpcSubApp {
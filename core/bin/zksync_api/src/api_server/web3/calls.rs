@@ -93,6 +93,24 @@ impl CallsHelper {
         }
     }
 
+    /// Checks whether the address belongs to one of the contracts emulated by the web3 API,
+    /// i.e. the zkSync proxy, the NFT factory or a token.
+    pub async fn is_contract(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        address: H160,
+    ) -> Result<bool> {
+        if address == self.zksync_proxy_address || address == self.nft_factory_address {
+            return Ok(true);
+        }
+        let token = self
+            .tokens
+            .get_token(storage, address)
+            .await
+            .map_err(|_| Error::internal_error())?;
+        Ok(token.is_some())
+    }
+
     pub async fn execute(
         &self,
         storage: &mut StorageProcessor<'_>,
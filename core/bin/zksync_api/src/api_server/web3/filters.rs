@@ -0,0 +1,139 @@
+// Built-in uses
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+// External uses
+// Workspace uses
+use zksync_types::BlockNumber;
+// Local uses
+use super::types::{Filter, Log, H160, H256, U256};
+
+/// Filters that were not polled during this period are removed.
+pub const FILTER_EXPIRY: Duration = Duration::from_secs(5 * 60);
+
+/// Log filter installed with the `eth_newFilter` method.
+#[derive(Debug, Clone)]
+pub struct InstalledFilter {
+    pub filter: Filter,
+    /// The first block which logs were not returned by `eth_getFilterChanges` yet.
+    pub next_block: BlockNumber,
+    last_polled_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct FiltersState {
+    last_id: u64,
+    filters: HashMap<U256, InstalledFilter>,
+}
+
+/// In-memory storage of the installed log filters.
+///
+/// Filters are not shared between the server instances, so clients
+/// are expected to poll the same instance that installed the filter.
+#[derive(Debug, Clone)]
+pub struct FiltersHelper {
+    state: Arc<Mutex<FiltersState>>,
+    expiry: Duration,
+}
+
+impl FiltersHelper {
+    pub fn new(expiry: Duration) -> Self {
+        Self {
+            state: Arc::default(),
+            expiry,
+        }
+    }
+
+    /// Installs the filter and returns its identifier.
+    pub fn install(&self, filter: Filter, next_block: BlockNumber) -> U256 {
+        let mut state = self.state.lock().unwrap();
+        self.remove_expired(&mut state);
+
+        state.last_id += 1;
+        let id = U256::from(state.last_id);
+        state.filters.insert(
+            id,
+            InstalledFilter {
+                filter,
+                next_block,
+                last_polled_at: Instant::now(),
+            },
+        );
+        id
+    }
+
+    /// Returns the filter with the given identifier and prolongs its lifetime.
+    pub fn poll(&self, id: U256) -> Option<InstalledFilter> {
+        let mut state = self.state.lock().unwrap();
+        self.remove_expired(&mut state);
+
+        let installed = state.filters.get_mut(&id)?;
+        installed.last_polled_at = Instant::now();
+        Some(installed.clone())
+    }
+
+    /// Marks the logs up to `next_block` (exclusively) as returned to the client.
+    pub fn advance(&self, id: U256, next_block: BlockNumber) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(installed) = state.filters.get_mut(&id) {
+            installed.next_block = installed.next_block.max(next_block);
+        }
+    }
+
+    /// Removes the filter, returns `false` if there is no such filter.
+    pub fn uninstall(&self, id: U256) -> bool {
+        let mut state = self.state.lock().unwrap();
+        self.remove_expired(&mut state);
+
+        state.filters.remove(&id).is_some()
+    }
+
+    fn remove_expired(&self, state: &mut FiltersState) {
+        let expiry = self.expiry;
+        state
+            .filters
+            .retain(|_, installed| installed.last_polled_at.elapsed() < expiry);
+    }
+}
+
+/// Address and topics criteria of the log filter.
+#[derive(Debug, Clone)]
+pub struct LogsMatcher {
+    addresses: Vec<H160>,
+    topics: Vec<H256>,
+}
+
+impl LogsMatcher {
+    /// Returns `None` if no log can match the filter.
+    pub fn new(filter: &Filter) -> Option<Self> {
+        let topics = if let Some(mut topics) = filter.topics.clone() {
+            // If there is non-null topic at the non-first position then nothing matches,
+            // since all our logs contain exactly one topic.
+            let has_not_first = topics
+                .iter()
+                .enumerate()
+                .any(|(i, topic)| i > 0 && topic.is_some());
+            if has_not_first {
+                return None;
+            } else if topics.is_empty() {
+                Vec::new()
+            } else {
+                topics.remove(0).unwrap_or_default().0
+            }
+        } else {
+            Vec::new()
+        };
+        let addresses = filter.address.clone().map(|a| a.0).unwrap_or_default();
+        Some(Self { addresses, topics })
+    }
+
+    pub fn matches(&self, log: &Log) -> bool {
+        if !self.topics.is_empty() && !self.topics.contains(&log.topics[0]) {
+            return false;
+        }
+        if !self.addresses.is_empty() && !self.addresses.contains(&log.address) {
+            return false;
+        }
+        true
+    }
+}
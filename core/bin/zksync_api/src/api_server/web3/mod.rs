@@ -1,14 +1,25 @@
 // Built-in uses
+use std::sync::Arc;
 // External uses
 
 use jsonrpc_core::{Error, IoHandler, MetaIoHandler, Metadata, Middleware, Result};
 use jsonrpc_http_server::ServerBuilder;
+use jsonrpc_pubsub::{PubSubHandler, Session};
+use jsonrpc_ws_server::RequestContext;
+use tokio::runtime::Runtime;
 // Workspace uses
 
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 // Local uses
-use self::{calls::CallsHelper, logs::LogsHelper, rpc_trait::Web3Rpc};
+use self::{
+    calls::CallsHelper,
+    filters::{FiltersHelper, FILTER_EXPIRY},
+    logs::LogsHelper,
+    rpc_trait::Web3Rpc,
+    subscriptions::{SubscribersHelper, SubscriptionsNotifier, Web3PubSub, Web3PubSubApp},
+};
+use crate::fee_ticker::FeeTicker;
 
 use tokio::task::JoinHandle;
 use zksync_config::configs::api::{TokenConfig, Web3Config};
@@ -16,21 +27,28 @@ use zksync_types::ChainId;
 
 mod calls;
 mod converter;
+mod filters;
 mod logs;
 mod rpc_impl;
 mod rpc_trait;
+mod subscriptions;
 #[cfg(test)]
 mod tests;
 mod types;
 
 pub const ZKSYNC_PROXY_ADDRESS: &str = "1000000000000000000000000000000000000000";
 pub const NFT_FACTORY_ADDRESS: &str = "2000000000000000000000000000000000000000";
+/// Code returned by `eth_getCode` for the emulated contracts. It consists of the single
+/// `INVALID` opcode, which is enough to distinguish contracts from the regular accounts.
+pub const EMULATED_CONTRACT_CODE: &[u8] = &[0xfe];
 
 #[derive(Clone)]
 pub struct Web3RpcApp {
     connection_pool: ConnectionPool,
     logs_helper: LogsHelper,
     calls_helper: CallsHelper,
+    filters_helper: FiltersHelper,
+    fee_ticker: FeeTicker,
     max_block_range: u32,
     chain_id: ChainId,
 }
@@ -38,6 +56,7 @@ pub struct Web3RpcApp {
 impl Web3RpcApp {
     pub fn new(
         connection_pool: ConnectionPool,
+        fee_ticker: FeeTicker,
         config: &Web3Config,
         token_config: &TokenConfig,
     ) -> Self {
@@ -45,6 +64,8 @@ impl Web3RpcApp {
             connection_pool,
             logs_helper: LogsHelper::new(token_config.invalidate_token_cache_period()),
             calls_helper: CallsHelper::new(token_config.invalidate_token_cache_period()),
+            filters_helper: FiltersHelper::new(FILTER_EXPIRY),
+            fee_ticker,
             max_block_range: config.max_block_range,
             chain_id: ChainId(config.chain_id),
         }
@@ -64,12 +85,13 @@ impl Web3RpcApp {
 
 pub fn start_rpc_server(
     connection_pool: ConnectionPool,
+    fee_ticker: FeeTicker,
     web3_config: &Web3Config,
     token_config: &TokenConfig,
 ) -> JoinHandle<()> {
     let addr = web3_config.bind_addr();
 
-    let rpc_app = Web3RpcApp::new(connection_pool, web3_config, token_config);
+    let rpc_app = Web3RpcApp::new(connection_pool, fee_ticker, web3_config, token_config);
     let (handler, panic_sender) = spawn_panic_handler();
 
     std::thread::spawn(move || {
@@ -86,3 +108,41 @@ pub fn start_rpc_server(
     });
     handler
 }
+
+/// Starts the websocket transport of the web3 API, which additionally supports
+/// `eth_subscribe` for the `newHeads` and `logs` subscriptions.
+pub fn start_ws_server(
+    connection_pool: ConnectionPool,
+    fee_ticker: FeeTicker,
+    web3_config: &Web3Config,
+    token_config: &TokenConfig,
+) -> JoinHandle<()> {
+    let addr = web3_config.ws_bind_addr();
+
+    let rpc_app = Web3RpcApp::new(connection_pool, fee_ticker, web3_config, token_config);
+    let subscribers = SubscribersHelper::default();
+    let pubsub_app = Web3PubSubApp::new(subscribers.clone());
+    let notifier = SubscriptionsNotifier::new(rpc_app.clone(), subscribers);
+    let (handler, panic_sender) = spawn_panic_handler();
+
+    std::thread::spawn(move || {
+        let _panic_sentinel = ThreadPanicNotify(panic_sender);
+
+        let mut io = PubSubHandler::new(MetaIoHandler::default());
+        rpc_app.extend(&mut io);
+        io.extend_with(pubsub_app.to_delegate());
+
+        let _server = jsonrpc_ws_server::ServerBuilder::with_meta_extractor(
+            io,
+            |context: &RequestContext| Arc::new(Session::new(context.sender())),
+        )
+        .max_connections(1000)
+        .start(&addr)
+        .expect("Unable to start web3 ws server");
+
+        // The server runs on its own threads, so this one is used to notify the subscribers.
+        let runtime = Runtime::new().expect("tokio runtime creation");
+        runtime.block_on(notifier.run());
+    });
+    handler
+}
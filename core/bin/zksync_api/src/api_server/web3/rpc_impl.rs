@@ -1,5 +1,6 @@
 // Built-in uses
 use ethabi::Address;
+use std::convert::TryFrom;
 use std::time::Instant;
 // External uses
 use jsonrpc_core::{Error, Result};
@@ -10,15 +11,16 @@ use zksync_storage::{
     StorageProcessor,
 };
 use zksync_types::withdrawals::WithdrawalPendingEvent;
-use zksync_types::{ExecutedOperations, TokenId, ZkSyncOp};
+use zksync_types::{ExecutedOperations, TokenId, TokenLike, TxFeeTypes, ZkSyncOp};
 // Local uses
 use super::{
     converter::{resolve_block_number, transaction_from_tx_data, u256_from_biguint},
+    filters::LogsMatcher,
     types::{
         BlockInfo, BlockNumber, Bytes, CallRequest, CommonLogData, Filter, Log, Transaction,
         TransactionReceipt, TxData, H160, H2048, H256, U256, U64,
     },
-    Web3RpcApp,
+    Web3RpcApp, EMULATED_CONTRACT_CODE,
 };
 
 impl Web3RpcApp {
@@ -61,6 +63,61 @@ impl Web3RpcApp {
         Ok(result)
     }
 
+    pub async fn _impl_get_transaction_count(
+        self,
+        address: H160,
+        _block: Option<BlockNumber>,
+    ) -> Result<U256> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| Error::internal_error())?;
+
+        // Nonces history is not stored, so the nonce expected by the next
+        // transaction of the account is returned for any block.
+        let account_id = transaction
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(|_| Error::internal_error())?;
+        let nonce = match account_id {
+            Some(account_id) => transaction
+                .chain()
+                .account_schema()
+                .estimate_nonce(account_id)
+                .await
+                .map_err(|_| Error::internal_error())?
+                .map(|nonce| *nonce)
+                .unwrap_or_default(),
+            None => 0,
+        };
+        transaction
+            .commit()
+            .await
+            .map_err(|_| Error::internal_error())?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "get_transaction_count");
+        Ok(U256::from(nonce))
+    }
+
+    pub async fn _impl_get_code(self, address: H160, _block: Option<BlockNumber>) -> Result<Bytes> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+
+        let is_contract = self.calls_helper.is_contract(&mut storage, address).await?;
+        let code = if is_contract {
+            EMULATED_CONTRACT_CODE.to_vec()
+        } else {
+            Vec::new()
+        };
+
+        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "get_code");
+        Ok(Bytes(code))
+    }
+
     pub async fn _impl_get_block_transaction_count_by_hash(
         self,
         hash: H256,
@@ -136,6 +193,38 @@ impl Web3RpcApp {
         Ok(result)
     }
 
+    pub async fn _impl_get_transaction_by_block_number_and_index(
+        self,
+        block: Option<BlockNumber>,
+        index: U64,
+    ) -> Result<Option<Transaction>> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| Error::internal_error())?;
+
+        let block_number = resolve_block_number(&mut transaction, block).await?;
+        let result = match (block_number, u32::try_from(index.as_u64())) {
+            (Some(block_number), Ok(index)) => transaction
+                .chain()
+                .operations_ext_schema()
+                .web3_tx_data_by_block_and_index(block_number, index)
+                .await
+                .map_err(|_| Error::internal_error())?
+                .map(|tx| transaction_from_tx_data(tx.into())),
+            _ => None,
+        };
+        transaction
+            .commit()
+            .await
+            .map_err(|_| Error::internal_error())?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "get_transaction_by_block_number_and_index");
+        Ok(result)
+    }
+
     pub async fn _impl_get_block_by_number(
         self,
         block_number: Option<BlockNumber>,
@@ -253,52 +342,98 @@ impl Web3RpcApp {
             )));
         }
 
-        let topics = if let Some(mut topics) = filter.topics {
-            // If there is non-null topic at the non-first position then return empty vec,
-            // since all our logs contain exactly one topic.
-            let has_not_first = topics
-                .iter()
-                .enumerate()
-                .any(|(i, topic)| i > 0 && topic.is_some());
-            if has_not_first {
-                return Ok(Vec::new());
-            } else if topics.is_empty() {
-                Vec::new()
-            } else {
-                topics.remove(0).unwrap_or_default().0
+        let result = self
+            .logs_in_range(&mut transaction, from_block, to_block, &filter)
+            .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|_| Error::internal_error())?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "get_logs");
+        Ok(result)
+    }
+
+    pub async fn _impl_new_filter(self, filter: Filter) -> Result<U256> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| Error::internal_error())?;
+
+        let next_block = match filter.from_block {
+            // Filters may be installed for the blocks that are not created yet.
+            Some(BlockNumber::Number(number)) => u32::try_from(number.as_u64())
+                .map(zksync_types::BlockNumber)
+                .map_err(|_| Error::invalid_params("Invalid `fromBlock`"))?,
+            Some(from_block) => resolve_block_number(&mut transaction, Some(from_block))
+                .await?
+                .ok_or_else(Error::internal_error)?,
+            // By default, only logs of the blocks finalized after the filter installation are returned.
+            None => {
+                resolve_block_number(&mut transaction, None)
+                    .await?
+                    .ok_or_else(Error::internal_error)?
+                    + 1
             }
-        } else {
-            Vec::new()
         };
-        let addresses = filter.address.map(|a| a.0).unwrap_or_default();
-        let mut result = Vec::new();
+        transaction
+            .commit()
+            .await
+            .map_err(|_| Error::internal_error())?;
 
-        let receipts = transaction
-            .chain()
-            .operations_ext_schema()
-            .web3_receipts(from_block, to_block)
+        let filter_id = self.filters_helper.install(filter, next_block);
+
+        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "new_filter");
+        Ok(filter_id)
+    }
+
+    pub async fn _impl_get_filter_changes(self, filter_id: U256) -> Result<Vec<Log>> {
+        let start = Instant::now();
+        let installed = self
+            .filters_helper
+            .poll(filter_id)
+            .ok_or_else(|| Error::invalid_params("Filter not found"))?;
+
+        let mut storage = self.access_storage().await?;
+        let mut transaction = storage
+            .start_transaction()
             .await
             .map_err(|_| Error::internal_error())?;
-        for receipt in receipts {
-            let logs = self.logs_from_receipt(&mut transaction, receipt).await?;
-            let filtered = logs.into_iter().filter(|log| {
-                if !topics.is_empty() && !topics.contains(&log.topics[0]) {
-                    return false;
-                }
-                if !addresses.is_empty() && !addresses.contains(&log.address) {
-                    return false;
-                }
-                true
-            });
-            result.extend(filtered);
-        }
 
+        // Only finalized blocks are taken into account, so the returned logs are never reverted.
+        let mut to_block = resolve_block_number(&mut transaction, None)
+            .await?
+            .ok_or_else(Error::internal_error)?;
+        if let Some(filter_to_block) = installed.filter.to_block {
+            if let Some(filter_to_block) =
+                resolve_block_number(&mut transaction, Some(filter_to_block)).await?
+            {
+                to_block = to_block.min(filter_to_block);
+            }
+        }
+        let from_block = installed.next_block;
+        // Clients which are far behind receive the logs over several polls,
+        // each covering at most `max_block_range` blocks.
+        let to_block = to_block.min(from_block + self.max_block_range.saturating_sub(1));
+
+        let result = if from_block <= to_block {
+            let logs = self
+                .logs_in_range(&mut transaction, from_block, to_block, &installed.filter)
+                .await?;
+            self.filters_helper.advance(filter_id, to_block + 1);
+            logs
+        } else {
+            Vec::new()
+        };
         transaction
             .commit()
             .await
             .map_err(|_| Error::internal_error())?;
 
-        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "get_logs");
+        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "get_filter_changes");
         Ok(result)
     }
 
@@ -315,6 +450,26 @@ impl Web3RpcApp {
         result.map(Bytes)
     }
 
+    pub async fn _impl_estimate_gas(
+        self,
+        req: CallRequest,
+        _block: Option<BlockNumber>,
+    ) -> Result<U256> {
+        let start = Instant::now();
+
+        // zkSync transactions can't be sent via web3 API, so the estimation is the gas amount
+        // the fee ticker charges for the zkSync `Transfer` of ETH to the `to` address
+        // (i.e. the L1 gas spent on it), rather than the cost of executing the call on L1.
+        let fee = self
+            .fee_ticker
+            .get_fee_from_ticker_in_wei(TxFeeTypes::Transfer, TokenLike::Id(TokenId(0)), req.to)
+            .await
+            .map_err(|_| Error::internal_error())?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "estimate_gas");
+        Ok(u256_from_biguint(fee.normal_fee.gas_tx_amount))
+    }
+
     pub async fn _impl_check_withdrawal(
         self,
         tx_hash: H256,
@@ -332,6 +487,34 @@ impl Web3RpcApp {
         Ok(withdrawals)
     }
 
+    /// Returns the logs matching the address and topics of the filter
+    /// from the blocks in the `[from_block, to_block]` range.
+    pub(crate) async fn logs_in_range(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        from_block: zksync_types::BlockNumber,
+        to_block: zksync_types::BlockNumber,
+        filter: &Filter,
+    ) -> Result<Vec<Log>> {
+        let matcher = match LogsMatcher::new(filter) {
+            Some(matcher) => matcher,
+            None => return Ok(Vec::new()),
+        };
+        let mut result = Vec::new();
+
+        let receipts = storage
+            .chain()
+            .operations_ext_schema()
+            .web3_receipts(from_block, to_block)
+            .await
+            .map_err(|_| Error::internal_error())?;
+        for receipt in receipts {
+            let logs = self.logs_from_receipt(storage, receipt).await?;
+            result.extend(logs.into_iter().filter(|log| matcher.matches(log)));
+        }
+        Ok(result)
+    }
+
     pub(crate) async fn logs_from_receipt(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -5,8 +5,8 @@ use zksync_types::withdrawals::WithdrawalPendingEvent;
 // Local uses
 use super::{
     types::{
-        BlockInfo, BlockNumber, Bytes, CallRequest, Filter, Log, Transaction, TransactionReceipt,
-        H160, H256, U256, U64,
+        BlockInfo, BlockNumber, Bytes, CallRequest, Filter, Log, SyncState, Transaction,
+        TransactionReceipt, H160, H256, U256, U64,
     },
     Web3RpcApp,
 };
@@ -31,6 +31,12 @@ pub trait Web3Rpc {
     #[rpc(name = "eth_protocolVersion", returns = "String")]
     fn protocol_version(&self) -> Result<String>;
 
+    #[rpc(name = "eth_chainId", returns = "U64")]
+    fn chain_id(&self) -> Result<U64>;
+
+    #[rpc(name = "eth_syncing", returns = "SyncState")]
+    fn syncing(&self) -> Result<SyncState>;
+
     #[rpc(name = "eth_mining", returns = "bool")]
     fn mining(&self) -> Result<bool>;
 
@@ -55,6 +61,16 @@ pub trait Web3Rpc {
     #[rpc(name = "eth_getBalance", returns = "U256")]
     fn get_balance(&self, address: H160, block: Option<BlockNumber>) -> BoxFutureResult<U256>;
 
+    #[rpc(name = "eth_getTransactionCount", returns = "U256")]
+    fn get_transaction_count(
+        &self,
+        address: H160,
+        block: Option<BlockNumber>,
+    ) -> BoxFutureResult<U256>;
+
+    #[rpc(name = "eth_getCode", returns = "Bytes")]
+    fn get_code(&self, address: H160, block: Option<BlockNumber>) -> BoxFutureResult<Bytes>;
+
     #[rpc(name = "eth_getBlockTransactionCountByHash", returns = "Option<U256>")]
     fn get_block_transaction_count_by_hash(&self, hash: H256) -> BoxFutureResult<Option<U256>>;
 
@@ -70,6 +86,16 @@ pub trait Web3Rpc {
     #[rpc(name = "eth_getTransactionByHash", returns = "Option<Transaction>")]
     fn get_transaction_by_hash(&self, hash: H256) -> BoxFutureResult<Option<Transaction>>;
 
+    #[rpc(
+        name = "eth_getTransactionByBlockNumberAndIndex",
+        returns = "Option<Transaction>"
+    )]
+    fn get_transaction_by_block_number_and_index(
+        &self,
+        block: Option<BlockNumber>,
+        index: U64,
+    ) -> BoxFutureResult<Option<Transaction>>;
+
     #[rpc(name = "eth_getBlockByNumber", returns = "Option<BlockInfo>")]
     fn get_block_by_number(
         &self,
@@ -93,9 +119,21 @@ pub trait Web3Rpc {
     #[rpc(name = "eth_getLogs", returns = "Vec<Log>")]
     fn get_logs(&self, filter: Filter) -> BoxFutureResult<Vec<Log>>;
 
+    #[rpc(name = "eth_newFilter", returns = "U256")]
+    fn new_filter(&self, filter: Filter) -> BoxFutureResult<U256>;
+
+    #[rpc(name = "eth_getFilterChanges", returns = "Vec<Log>")]
+    fn get_filter_changes(&self, filter_id: U256) -> BoxFutureResult<Vec<Log>>;
+
+    #[rpc(name = "eth_uninstallFilter", returns = "bool")]
+    fn uninstall_filter(&self, filter_id: U256) -> Result<bool>;
+
     #[rpc(name = "eth_call", returns = "Bytes")]
     fn call(&self, req: CallRequest, _block: Option<BlockNumber>) -> BoxFutureResult<Bytes>;
 
+    #[rpc(name = "eth_estimateGas", returns = "U256")]
+    fn estimate_gas(&self, req: CallRequest, _block: Option<BlockNumber>) -> BoxFutureResult<U256>;
+
     #[rpc(name = "zksync_checkWithdrawal", returns = "Vec<String>")]
     fn check_withdrawal(&self, tx_hash: H256) -> BoxFutureResult<Vec<WithdrawalPendingEvent>>;
 }
@@ -113,6 +151,15 @@ impl Web3Rpc for Web3RpcApp {
         Ok(String::from("0"))
     }
 
+    fn chain_id(&self) -> Result<U64> {
+        Ok(U64::from(self.chain_id.0))
+    }
+
+    fn syncing(&self) -> Result<SyncState> {
+        // The API serves the data directly from the database, so there is nothing to sync.
+        Ok(SyncState::NotSyncing)
+    }
+
     fn mining(&self) -> Result<bool> {
         Ok(false)
     }
@@ -145,6 +192,18 @@ impl Web3Rpc for Web3RpcApp {
         spawn!(self._impl_get_balance(address, block))
     }
 
+    fn get_transaction_count(
+        &self,
+        address: H160,
+        block: Option<BlockNumber>,
+    ) -> BoxFutureResult<U256> {
+        spawn!(self._impl_get_transaction_count(address, block))
+    }
+
+    fn get_code(&self, address: H160, block: Option<BlockNumber>) -> BoxFutureResult<Bytes> {
+        spawn!(self._impl_get_code(address, block))
+    }
+
     fn get_block_transaction_count_by_hash(&self, hash: H256) -> BoxFutureResult<Option<U256>> {
         spawn!(self._impl_get_block_transaction_count_by_hash(hash))
     }
@@ -160,6 +219,14 @@ impl Web3Rpc for Web3RpcApp {
         spawn!(self._impl_get_transaction_by_hash(hash))
     }
 
+    fn get_transaction_by_block_number_and_index(
+        &self,
+        block: Option<BlockNumber>,
+        index: U64,
+    ) -> BoxFutureResult<Option<Transaction>> {
+        spawn!(self._impl_get_transaction_by_block_number_and_index(block, index))
+    }
+
     fn get_block_by_number(
         &self,
         block_number: Option<BlockNumber>,
@@ -184,10 +251,26 @@ impl Web3Rpc for Web3RpcApp {
         spawn!(self._impl_get_logs(filter))
     }
 
+    fn new_filter(&self, filter: Filter) -> BoxFutureResult<U256> {
+        spawn!(self._impl_new_filter(filter))
+    }
+
+    fn get_filter_changes(&self, filter_id: U256) -> BoxFutureResult<Vec<Log>> {
+        spawn!(self._impl_get_filter_changes(filter_id))
+    }
+
+    fn uninstall_filter(&self, filter_id: U256) -> Result<bool> {
+        Ok(self.filters_helper.uninstall(filter_id))
+    }
+
     fn call(&self, req: CallRequest, block: Option<BlockNumber>) -> BoxFutureResult<Bytes> {
         spawn! { self._impl_call(req, block) }
     }
 
+    fn estimate_gas(&self, req: CallRequest, block: Option<BlockNumber>) -> BoxFutureResult<U256> {
+        spawn! { self._impl_estimate_gas(req, block) }
+    }
+
     fn check_withdrawal(&self, tx_hash: H256) -> BoxFutureResult<Vec<WithdrawalPendingEvent>> {
         spawn! { self._impl_check_withdrawal(tx_hash) }
     }
@@ -0,0 +1,230 @@
+//! Web3 `eth_subscribe` subscriptions served over the websocket transport.
+//!
+//! Notifications are driven by the finalized blocks only, so that `newHeads` are
+//! consistent with `eth_blockNumber` and the sent logs are never reverted.
+
+// Built-in uses
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+// External uses
+use jsonrpc_core::{Error, Result};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{
+    typed::{Sink, Subscriber},
+    Session, SubscriptionId,
+};
+use serde::{Deserialize, Serialize};
+use tokio::time;
+// Workspace uses
+use zksync_types::BlockNumber;
+// Local uses
+use super::{
+    filters::LogsMatcher,
+    types::{BlockInfo, Filter, Log},
+    Web3RpcApp,
+};
+
+/// Interval between the checks for the newly finalized blocks.
+pub const SUBSCRIPTIONS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+    NewHeads,
+    Logs,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum SubscriptionResult {
+    Header(BlockInfo),
+    Log(Log),
+}
+
+#[rpc]
+pub trait Web3PubSub {
+    type Metadata;
+
+    #[pubsub(subscription = "eth_subscription", subscribe, name = "eth_subscribe")]
+    fn subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<SubscriptionResult>,
+        kind: SubscriptionKind,
+        filter: Option<Filter>,
+    );
+
+    #[pubsub(
+        subscription = "eth_subscription",
+        unsubscribe,
+        name = "eth_unsubscribe"
+    )]
+    fn unsubscribe(&self, meta: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
+}
+
+#[derive(Debug, Clone)]
+enum SubscriptionTarget {
+    NewHeads,
+    Logs(LogsMatcher),
+}
+
+struct Web3Subscriber {
+    target: SubscriptionTarget,
+    sink: Sink<SubscriptionResult>,
+}
+
+/// Active subscriptions shared between the websocket handlers and the notifier.
+#[derive(Clone, Default)]
+pub struct SubscribersHelper {
+    subscribers: Arc<Mutex<HashMap<SubscriptionId, Web3Subscriber>>>,
+}
+
+impl SubscribersHelper {
+    fn insert(&self, subscriber: Subscriber<SubscriptionResult>, target: SubscriptionTarget) {
+        let id = SubscriptionId::String(format!("0x{:016x}", zksync_crypto::rand::random::<u64>()));
+        if let Ok(sink) = subscriber.assign_id(id.clone()) {
+            self.subscribers
+                .lock()
+                .unwrap()
+                .insert(id, Web3Subscriber { target, sink });
+        }
+    }
+
+    fn remove(&self, id: &SubscriptionId) -> bool {
+        self.subscribers.lock().unwrap().remove(id).is_some()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.subscribers.lock().unwrap().is_empty()
+    }
+
+    /// Sends the block header and its logs to the matching subscribers.
+    /// Subscribers with closed connections are removed.
+    fn notify_block(&self, header: BlockInfo, logs: &[Log]) {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|_, subscriber| match &subscriber.target {
+                SubscriptionTarget::NewHeads => subscriber
+                    .sink
+                    .notify(Ok(SubscriptionResult::Header(header.clone())))
+                    .is_ok(),
+                SubscriptionTarget::Logs(matcher) => {
+                    logs.iter().filter(|log| matcher.matches(log)).all(|log| {
+                        subscriber
+                            .sink
+                            .notify(Ok(SubscriptionResult::Log(log.clone())))
+                            .is_ok()
+                    })
+                }
+            });
+    }
+}
+
+pub struct Web3PubSubApp {
+    subscribers: SubscribersHelper,
+}
+
+impl Web3PubSubApp {
+    pub fn new(subscribers: SubscribersHelper) -> Self {
+        Self { subscribers }
+    }
+}
+
+impl Web3PubSub for Web3PubSubApp {
+    type Metadata = Arc<Session>;
+
+    fn subscribe(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<SubscriptionResult>,
+        kind: SubscriptionKind,
+        filter: Option<Filter>,
+    ) {
+        let target = match kind {
+            SubscriptionKind::NewHeads => SubscriptionTarget::NewHeads,
+            SubscriptionKind::Logs => {
+                // Block range of the filter is ignored, only the new logs are sent.
+                match LogsMatcher::new(&filter.unwrap_or_default()) {
+                    Some(matcher) => SubscriptionTarget::Logs(matcher),
+                    None => {
+                        subscriber
+                            .reject(Error::invalid_params(
+                                "Only the first topic can be specified",
+                            ))
+                            .unwrap_or_default();
+                        return;
+                    }
+                }
+            }
+        };
+        self.subscribers.insert(subscriber, target);
+    }
+
+    fn unsubscribe(&self, _meta: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+        Ok(self.subscribers.remove(&id))
+    }
+}
+
+/// Polls the database for the newly finalized blocks and notifies the subscribers.
+pub struct SubscriptionsNotifier {
+    rpc_app: Web3RpcApp,
+    subscribers: SubscribersHelper,
+    /// The first block which was not sent to the subscribers yet.
+    pub(super) next_block: Option<BlockNumber>,
+}
+
+impl SubscriptionsNotifier {
+    pub fn new(rpc_app: Web3RpcApp, subscribers: SubscribersHelper) -> Self {
+        Self {
+            rpc_app,
+            subscribers,
+            next_block: None,
+        }
+    }
+
+    pub async fn run(mut self) {
+        let mut timer = time::interval(SUBSCRIPTIONS_POLL_INTERVAL);
+        loop {
+            timer.tick().await;
+            if let Err(err) = self.notify_new_blocks().await {
+                vlog::warn!("Failed to notify web3 subscribers: {}", err);
+            }
+        }
+    }
+
+    pub(super) async fn notify_new_blocks(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.rpc_app.connection_pool.access_storage().await?;
+        let last_finalized = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        let from_block = match self.next_block {
+            // Blocks finalized before there were any subscribers are skipped.
+            Some(next_block) if !self.subscribers.is_empty() => next_block,
+            _ => {
+                self.next_block = Some(last_finalized + 1);
+                return Ok(());
+            }
+        };
+        // Notifications are sent in batches of at most `max_block_range` blocks
+        // if the notifier is far behind.
+        let to_block =
+            last_finalized.min(from_block + self.rpc_app.max_block_range.saturating_sub(1));
+
+        for block_number in from_block.0..=to_block.0 {
+            let block_number = BlockNumber(block_number);
+            let header = Web3RpcApp::block_by_number(&mut storage, block_number, false).await?;
+            let logs = self
+                .rpc_app
+                .logs_in_range(&mut storage, block_number, block_number, &Filter::default())
+                .await?;
+            self.subscribers.notify_block(header, &logs);
+            self.next_block = Some(block_number + 1);
+        }
+        Ok(())
+    }
+}
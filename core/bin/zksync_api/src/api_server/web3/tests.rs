@@ -1,10 +1,12 @@
 // Built-in uses
 use std::str::FromStr;
+use std::sync::Arc;
 // External uses
 use ethabi::{ParamType, Token};
 use futures::future::{join, join5, Future};
 use jsonrpc_core::{Error, ErrorCode, IoHandler, Params};
 use jsonrpc_core_client::{RawClient, RpcError, RpcResult};
+use jsonrpc_pubsub::{typed::Subscriber, Session};
 use num::BigUint;
 use serde_json::{Map, Value};
 // Workspace uses
@@ -13,24 +15,35 @@ use zksync_storage::{chain::operations_ext::records::Web3TxReceipt, ConnectionPo
 use zksync_test_account::ZkSyncAccount;
 use zksync_types::{
     tx::ChangePubKeyType, AccountId, AccountUpdate, BlockNumber, ChangePubKeyOp, CloseOp, Deposit,
-    DepositOp, ForcedExitOp, FullExit, FullExitOp, MintNFTOp, Nonce, SwapOp, TokenId, TransferOp,
-    WithdrawNFTOp, WithdrawOp, ZkSyncOp, NFT,
+    DepositOp, ForcedExitOp, FullExit, FullExitOp, MintNFTOp, Nonce, SwapOp, TokenId, TokenLike,
+    TransferOp, TxFeeTypes, WithdrawNFTOp, WithdrawOp, ZkSyncOp, NFT,
 };
 // Local uses
 use super::{
     calls::CallsHelper,
     converter::{transaction_from_tx_data, u256_from_biguint},
-    types::{BlockInfo, Event, Log, Transaction, TransactionReceipt, H160, H256, U256, U64},
+    subscriptions::{
+        SubscribersHelper, SubscriptionKind, SubscriptionsNotifier, Web3PubSub, Web3PubSubApp,
+    },
+    types::{
+        BlockInfo, Event, Filter, Log, Transaction, TransactionReceipt, ValueOrArray, H160, H256,
+        U256, U64,
+    },
     Web3RpcApp, NFT_FACTORY_ADDRESS, ZKSYNC_PROXY_ADDRESS,
 };
-use crate::api_server::rest::v02::test_utils::TestServerConfig;
+use crate::api_server::rest::v02::test_utils::{dummy_fee_ticker, TestServerConfig};
 use zksync_config::configs::api::{TokenConfig, Web3Config};
 
 async fn local_client() -> anyhow::Result<(RawClient, impl Future<Output = RpcResult<()>>)> {
     let cfg = TestServerConfig::default();
     cfg.fill_database().await?;
 
-    let rpc_app = Web3RpcApp::new(cfg.pool, &cfg.config.api.web3, &cfg.config.api.token_config);
+    let rpc_app = Web3RpcApp::new(
+        cfg.pool,
+        dummy_fee_ticker(&[], None),
+        &cfg.config.api.web3,
+        &cfg.config.api.token_config,
+    );
     let mut io = IoHandler::new();
     rpc_app.extend(&mut io);
 
@@ -377,6 +390,192 @@ async fn get_transaction_by_hash() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Tests `eth_getTransactionByBlockNumberAndIndex` method
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(
+    not(feature = "api_test"),
+    ignore = "Use `zk test rust-api` command to perform this test"
+)]
+async fn get_transaction_by_block_number_and_index() -> anyhow::Result<()> {
+    let pool = ConnectionPool::new(Some(1));
+    // Checks that `null` is returned if there is no transaction with such index.
+    let fut = {
+        let (client, server) = local_client().await?;
+        join(
+            client.call_method(
+                "eth_getTransactionByBlockNumberAndIndex",
+                Params::Array(vec![
+                    Value::String("0x1".to_string()),
+                    Value::String("0xffff".to_string()),
+                ]),
+            ),
+            server,
+        )
+    };
+    let transaction = fut.await.0.unwrap();
+    assert!(transaction.is_null());
+
+    // Checks that the transaction is found by its position.
+    let fut = {
+        let (client, server) = local_client().await?;
+        join(
+            client.call_method(
+                "eth_getTransactionByBlockNumberAndIndex",
+                Params::Array(vec![
+                    Value::String("0x1".to_string()),
+                    Value::String("0x0".to_string()),
+                ]),
+            ),
+            server,
+        )
+    };
+    let transaction = fut.await.0.unwrap();
+    let expected = {
+        let mut storage = pool.access_storage().await?;
+        let tx_data = storage
+            .chain()
+            .operations_ext_schema()
+            .web3_tx_data_by_block_and_index(BlockNumber(1), 0)
+            .await?
+            .unwrap();
+        transaction_from_tx_data(tx_data.into())
+    };
+    let transaction = serde_json::from_value::<Transaction>(transaction).unwrap();
+    assert_eq!(transaction, expected);
+    assert_eq!(transaction.transaction_index, Some(U64::zero()));
+
+    Ok(())
+}
+
+/// Tests `eth_chainId`, `eth_syncing`, `eth_getTransactionCount` and `eth_getCode` methods
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(
+    not(feature = "api_test"),
+    ignore = "Use `zk test rust-api` command to perform this test"
+)]
+async fn account_methods() -> anyhow::Result<()> {
+    let pool = ConnectionPool::new(Some(1));
+    let (address, expected_nonce) = {
+        let mut storage = pool.access_storage().await?;
+        let tx_data = storage
+            .chain()
+            .operations_ext_schema()
+            .web3_tx_data_by_block_and_index(BlockNumber(1), 0)
+            .await?
+            .unwrap();
+        let address = H160::from_slice(&tx_data.from_account);
+        let account_id = storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await?
+            .unwrap();
+        let nonce = storage
+            .chain()
+            .account_schema()
+            .estimate_nonce(account_id)
+            .await?
+            .unwrap();
+        (address, U256::from(*nonce))
+    };
+
+    let (client, server) = local_client().await?;
+    let calls = async move {
+        let chain_id = client.call_method("eth_chainId", Params::None).await?;
+        let syncing = client.call_method("eth_syncing", Params::None).await?;
+        let nonce = client
+            .call_method(
+                "eth_getTransactionCount",
+                Params::Array(vec![
+                    serde_json::to_value(address).unwrap(),
+                    Value::String("latest".to_string()),
+                ]),
+            )
+            .await?;
+        let unknown_nonce = client
+            .call_method(
+                "eth_getTransactionCount",
+                Params::Array(vec![serde_json::to_value(H160::random()).unwrap()]),
+            )
+            .await?;
+        let contract_code = client
+            .call_method(
+                "eth_getCode",
+                Params::Array(vec![Value::String(format!("0x{}", ZKSYNC_PROXY_ADDRESS))]),
+            )
+            .await?;
+        let account_code = client
+            .call_method(
+                "eth_getCode",
+                Params::Array(vec![serde_json::to_value(address).unwrap()]),
+            )
+            .await?;
+        Ok::<_, RpcError>((
+            chain_id,
+            syncing,
+            nonce,
+            unknown_nonce,
+            contract_code,
+            account_code,
+        ))
+    };
+    let (chain_id, syncing, nonce, unknown_nonce, contract_code, account_code) =
+        join(calls, server).await.0.unwrap();
+
+    assert_eq!(chain_id.as_str().unwrap(), "0xf0");
+    assert!(!syncing.as_bool().unwrap());
+    assert_eq!(serde_json::from_value::<U256>(nonce)?, expected_nonce);
+    assert_eq!(unknown_nonce.as_str().unwrap(), "0x0");
+    assert_eq!(contract_code.as_str().unwrap(), "0xfe");
+    assert_eq!(account_code.as_str().unwrap(), "0x");
+
+    Ok(())
+}
+
+/// Tests `eth_estimateGas` method
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(
+    not(feature = "api_test"),
+    ignore = "Use `zk test rust-api` command to perform this test"
+)]
+async fn estimate_gas() -> anyhow::Result<()> {
+    let cfg = TestServerConfig::default();
+    cfg.fill_database().await?;
+    let prices = [(TokenLike::Id(TokenId(0)), 1_u64.into())];
+    let rpc_app = Web3RpcApp::new(
+        cfg.pool.clone(),
+        dummy_fee_ticker(&prices, None),
+        &cfg.config.api.web3,
+        &cfg.config.api.token_config,
+    );
+    let mut io = IoHandler::new();
+    rpc_app.extend(&mut io);
+    let (client, server) = jsonrpc_core_client::transports::local::connect::<RawClient, _, _>(io);
+
+    let to = H160::random();
+    let mut req = Map::new();
+    req.insert("to".to_string(), serde_json::to_value(to).unwrap());
+    let gas = join(
+        client.call_method("eth_estimateGas", Params::Array(vec![Value::Object(req)])),
+        server,
+    )
+    .await
+    .0
+    .unwrap();
+
+    let expected_gas = dummy_fee_ticker(&prices, None)
+        .get_fee_from_ticker_in_wei(TxFeeTypes::Transfer, TokenLike::Id(TokenId(0)), to)
+        .await?
+        .normal_fee
+        .gas_tx_amount;
+    assert_eq!(
+        serde_json::from_value::<U256>(gas).unwrap(),
+        u256_from_biguint(expected_gas)
+    );
+
+    Ok(())
+}
+
 /// Tests `eth_getBlockByNumber` and `eth_getBlockByHash` methods
 #[tokio::test(flavor = "multi_thread")]
 #[cfg_attr(
@@ -465,7 +664,12 @@ async fn get_block() -> anyhow::Result<()> {
 async fn create_logs() -> anyhow::Result<()> {
     let cfg = TestServerConfig::default();
     cfg.fill_database().await?;
-    let rpc_app = Web3RpcApp::new(cfg.pool, &cfg.config.api.web3, &cfg.config.api.token_config);
+    let rpc_app = Web3RpcApp::new(
+        cfg.pool,
+        dummy_fee_ticker(&[], None),
+        &cfg.config.api.web3,
+        &cfg.config.api.token_config,
+    );
 
     let from_account_id = AccountId(3);
     let from_account = ZkSyncAccount::rand_with_seed([1, 2, 3, 4]);
@@ -855,6 +1059,7 @@ async fn get_transaction_receipt() -> anyhow::Result<()> {
             .unwrap();
         let rpc_app = Web3RpcApp::new(
             pool.clone(),
+            dummy_fee_ticker(&[], None),
             &Web3Config::from_env(),
             &TokenConfig::from_env(),
         );
@@ -878,6 +1083,7 @@ async fn get_logs() -> anyhow::Result<()> {
     let pool = ConnectionPool::new(Some(1));
     let rpc_app = Web3RpcApp::new(
         pool.clone(),
+        dummy_fee_ticker(&[], None),
         &Web3Config::from_env(),
         &TokenConfig::from_env(),
     );
@@ -908,10 +1114,17 @@ async fn get_logs() -> anyhow::Result<()> {
             let config = Web3Config {
                 port: 0,
                 url: "".to_string(),
+                ws_port: 0,
+                ws_url: "".to_string(),
                 max_block_range: 3,
                 chain_id: 9,
             };
-            let rpc_app = Web3RpcApp::new(pool.clone(), &config, &TokenConfig::from_env());
+            let rpc_app = Web3RpcApp::new(
+                pool.clone(),
+                dummy_fee_ticker(&[], None),
+                &config,
+                &TokenConfig::from_env(),
+            );
             let mut io = IoHandler::new();
             rpc_app.extend(&mut io);
 
@@ -1042,6 +1255,179 @@ async fn get_logs() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Tests `eth_newFilter`, `eth_getFilterChanges` and `eth_uninstallFilter` methods
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(
+    not(feature = "api_test"),
+    ignore = "Use `zk test rust-api` command to perform this test"
+)]
+async fn log_filters() -> anyhow::Result<()> {
+    let mut req = Map::new();
+    req.insert("fromBlock".to_string(), Value::String("0x1".to_string()));
+    req.insert("toBlock".to_string(), Value::String("0x1".to_string()));
+    let filter = Value::Object(req);
+
+    let (client, server) = local_client().await?;
+    let calls = async move {
+        let expected_logs = client
+            .call_method("eth_getLogs", Params::Array(vec![filter.clone()]))
+            .await?;
+        let filter_id = client
+            .call_method("eth_newFilter", Params::Array(vec![filter]))
+            .await?;
+        let first_changes = client
+            .call_method(
+                "eth_getFilterChanges",
+                Params::Array(vec![filter_id.clone()]),
+            )
+            .await?;
+        let second_changes = client
+            .call_method(
+                "eth_getFilterChanges",
+                Params::Array(vec![filter_id.clone()]),
+            )
+            .await?;
+        let uninstalled = client
+            .call_method(
+                "eth_uninstallFilter",
+                Params::Array(vec![filter_id.clone()]),
+            )
+            .await?;
+        let removed_changes = client
+            .call_method("eth_getFilterChanges", Params::Array(vec![filter_id]))
+            .await;
+        Ok::<_, RpcError>((
+            expected_logs,
+            first_changes,
+            second_changes,
+            uninstalled,
+            removed_changes,
+        ))
+    };
+    let (expected_logs, first_changes, second_changes, uninstalled, removed_changes) =
+        join(calls, server).await.0.unwrap();
+
+    // All logs of the block are returned by the first poll and only once.
+    let expected_logs = serde_json::from_value::<Vec<Log>>(expected_logs).unwrap();
+    assert!(!expected_logs.is_empty());
+    assert_eq!(
+        serde_json::from_value::<Vec<Log>>(first_changes).unwrap(),
+        expected_logs
+    );
+    assert!(serde_json::from_value::<Vec<Log>>(second_changes)
+        .unwrap()
+        .is_empty());
+
+    assert!(uninstalled.as_bool().unwrap());
+    assert!(matches!(
+        removed_changes.unwrap_err(),
+        RpcError::JsonRpcError(Error {
+            code: ErrorCode::InvalidParams,
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+/// Checks that `eth_subscribe` subscribers are notified about the finalized blocks and their logs
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(
+    not(feature = "api_test"),
+    ignore = "Use `zk test rust-api` command to perform this test"
+)]
+async fn subscriptions() -> anyhow::Result<()> {
+    let cfg = TestServerConfig::default();
+    cfg.fill_database().await?;
+    let rpc_app = Web3RpcApp::new(
+        cfg.pool.clone(),
+        dummy_fee_ticker(&[], None),
+        &cfg.config.api.web3,
+        &cfg.config.api.token_config,
+    );
+    let filter = Filter {
+        address: Some(ValueOrArray(vec![H160::from_str(ZKSYNC_PROXY_ADDRESS)?])),
+        ..Default::default()
+    };
+
+    let subscribers = SubscribersHelper::default();
+    let pubsub_app = Web3PubSubApp::new(subscribers.clone());
+    let session = Arc::new(Session::new(futures::channel::mpsc::unbounded().0));
+    let (subscriber, heads_id, mut heads) = Subscriber::new_test("eth_subscription");
+    pubsub_app.subscribe(
+        session.clone(),
+        subscriber,
+        SubscriptionKind::NewHeads,
+        None,
+    );
+    let (subscriber, logs_id, mut logs) = Subscriber::new_test("eth_subscription");
+    pubsub_app.subscribe(
+        session,
+        subscriber,
+        SubscriptionKind::Logs,
+        Some(filter.clone()),
+    );
+    let heads_id = heads_id.await.unwrap().unwrap();
+    let logs_id = logs_id.await.unwrap().unwrap();
+
+    let mut notifier = SubscriptionsNotifier::new(rpc_app.clone(), subscribers);
+    notifier.next_block = Some(BlockNumber(1));
+    notifier.notify_new_blocks().await?;
+    let last_block = notifier.next_block.unwrap() - 1;
+    assert!(last_block >= BlockNumber(1));
+
+    let mut received_heads = Vec::new();
+    while let Ok(Some(message)) = heads.try_next() {
+        let message: Value = serde_json::from_str(&message)?;
+        assert_eq!(
+            message["params"]["subscription"],
+            serde_json::to_value(&heads_id)?
+        );
+        let header: BlockInfo = serde_json::from_value(message["params"]["result"].clone())?;
+        received_heads.push(header);
+    }
+    let expected_heads = {
+        let mut storage = cfg.pool.access_storage().await?;
+        let mut heads = Vec::new();
+        for block_number in 1..=last_block.0 {
+            heads.push(
+                Web3RpcApp::block_by_number(&mut storage, BlockNumber(block_number), false)
+                    .await
+                    .unwrap(),
+            );
+        }
+        heads
+    };
+    assert_eq!(received_heads, expected_heads);
+
+    let mut received_logs = Vec::new();
+    while let Ok(Some(message)) = logs.try_next() {
+        let message: Value = serde_json::from_str(&message)?;
+        assert_eq!(
+            message["params"]["subscription"],
+            serde_json::to_value(&logs_id)?
+        );
+        let log: Log = serde_json::from_value(message["params"]["result"].clone())?;
+        received_logs.push(log);
+    }
+    let expected_logs = {
+        let mut storage = cfg.pool.access_storage().await?;
+        rpc_app
+            .logs_in_range(&mut storage, BlockNumber(1), last_block, &filter)
+            .await
+            .unwrap()
+    };
+    assert!(!expected_logs.is_empty());
+    assert_eq!(received_logs, expected_logs);
+
+    // Blocks are sent only once.
+    notifier.notify_new_blocks().await?;
+    assert!(heads.try_next().is_err());
+    assert!(logs.try_next().is_err());
+
+    Ok(())
+}
+
 /// Tests `eth_call` method for erc20 contracts
 #[tokio::test(flavor = "multi_thread")]
 #[cfg_attr(
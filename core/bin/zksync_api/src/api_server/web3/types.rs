@@ -12,7 +12,8 @@ use std::marker::PhantomData;
 use itertools::unfold;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 pub use web3::types::{
-    Block, Bytes, Log, Transaction, TransactionReceipt, H160, H2048, H256, H64, U256, U64,
+    Block, Bytes, Log, SyncState, Transaction, TransactionReceipt, H160, H2048, H256, H64, U256,
+    U64,
 };
 // Workspace uses
 use zksync_storage::chain::operations_ext::records::Web3TxData;
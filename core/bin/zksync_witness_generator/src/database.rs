@@ -5,8 +5,14 @@
 
 // Built-in
 use std::clone::Clone;
+use std::time::Duration;
+// External uses
+use chrono::{DateTime, Utc};
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
+use zksync_storage::prover::records::{
+    StorageActiveProverJob, StorageProverJobQueueDepth, StorageProverStats, StorageProverThroughput,
+};
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
@@ -154,10 +160,12 @@ impl DatabaseInterface for Database {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease_duration: Duration,
     ) -> anyhow::Result<Option<ProverJob>> {
         let proof = connection
             .prover_schema()
-            .get_idle_prover_job_from_job_queue()
+            .get_idle_prover_job_from_job_queue(prover_name, lease_duration)
             .await?;
 
         Ok(proof)
@@ -168,13 +176,14 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()> {
-        connection
+        lease_duration: Duration,
+    ) -> anyhow::Result<Option<DateTime<Utc>>> {
+        let lease = connection
             .prover_schema()
-            .record_prover_is_working(job_id, prover_name)
+            .record_prover_is_working(job_id, prover_name, lease_duration)
             .await?;
 
-        Ok(())
+        Ok(lease)
     }
 
     async fn store_proof(
@@ -183,10 +192,11 @@ impl DatabaseInterface for Database {
         job_id: i32,
         block_number: BlockNumber,
         proof: &SingleProof,
+        prover_name: &str,
     ) -> anyhow::Result<()> {
         connection
             .prover_schema()
-            .store_proof(job_id, block_number, proof)
+            .store_proof(job_id, block_number, proof, prover_name)
             .await?;
 
         Ok(())
@@ -199,10 +209,11 @@ impl DatabaseInterface for Database {
         first_block: BlockNumber,
         last_block: BlockNumber,
         proof: &AggregatedProof,
+        prover_name: &str,
     ) -> anyhow::Result<()> {
         connection
             .prover_schema()
-            .store_aggregated_proof(job_id, first_block, last_block, proof)
+            .store_aggregated_proof(job_id, first_block, last_block, proof, prover_name)
             .await?;
 
         Ok(())
@@ -221,6 +232,22 @@ impl DatabaseInterface for Database {
         Ok(())
     }
 
+    async fn record_job_failure(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        prover_name: &str,
+        error: &str,
+        retry_backoff: Duration,
+    ) -> anyhow::Result<()> {
+        connection
+            .prover_schema()
+            .record_job_failure(job_id, prover_name, error, retry_backoff)
+            .await?;
+
+        Ok(())
+    }
+
     async fn load_committed_state(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -287,12 +314,78 @@ impl DatabaseInterface for Database {
         Ok(())
     }
 
-    async fn pending_jobs_count(
+    async fn load_active_prover_jobs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageActiveProverJob>> {
+        let jobs = connection.prover_schema().load_active_jobs().await?;
+
+        Ok(jobs)
+    }
+
+    async fn load_prover_job_queue_depth(
         &self,
         connection: &mut StorageProcessor<'_>,
-    ) -> anyhow::Result<u32> {
-        let count = connection.prover_schema().pending_jobs_count().await?;
+    ) -> anyhow::Result<Vec<StorageProverJobQueueDepth>> {
+        let depth = connection.prover_schema().load_job_queue_depth().await?;
+
+        Ok(depth)
+    }
+
+    async fn load_prover_throughput(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageProverThroughput>> {
+        let throughput = connection.prover_schema().load_throughput(since).await?;
+
+        Ok(throughput)
+    }
+
+    async fn load_all_prover_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverStats>> {
+        let stats = connection.prover_schema().load_all_prover_stats().await?;
+
+        Ok(stats)
+    }
+
+    async fn load_pending_jobs_by_size(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverJobsBySize>> {
+        let jobs = connection
+            .prover_schema()
+            .load_pending_jobs_by_size()
+            .await?;
+
+        Ok(jobs)
+    }
+
+    async fn load_proving_times_by_size(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageProverJobsBySize>> {
+        let jobs = connection
+            .prover_schema()
+            .load_proving_times_by_size(since)
+            .await?;
+
+        Ok(jobs)
+    }
+
+    async fn load_created_blocks_by_size(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageBlocksBySize>> {
+        let blocks = connection
+            .prover_schema()
+            .load_created_blocks_by_size(since)
+            .await?;
 
-        Ok(count)
+        Ok(blocks)
     }
 }
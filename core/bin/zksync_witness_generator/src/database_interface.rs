@@ -6,8 +6,14 @@
 // Built-in
 use std::clone::Clone;
 use std::marker::{Send, Sync};
+use std::time::Duration;
+// External uses
+use chrono::{DateTime, Utc};
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
+use zksync_storage::prover::records::{
+    StorageActiveProverJob, StorageProverJobQueueDepth, StorageProverStats, StorageProverThroughput,
+};
 use zksync_storage::StorageProcessor;
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::{
@@ -80,17 +86,22 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<Option<(BlockNumber, Vec<u8>)>>;
 
+    /// Takes the next job for the prover, including the ones with expired leases.
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease_duration: Duration,
     ) -> anyhow::Result<Option<ProverJob>>;
 
+    /// Extends the lease of the job, returns `None` if the job is not leased to the prover.
     async fn record_prover_is_working(
         &self,
         connection: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()>;
+        lease_duration: Duration,
+    ) -> anyhow::Result<Option<DateTime<Utc>>>;
 
     async fn store_proof(
         &self,
@@ -98,6 +109,7 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         job_id: i32,
         block_number: BlockNumber,
         proof: &SingleProof,
+        prover_name: &str,
     ) -> anyhow::Result<()>;
 
     async fn store_aggregated_proof(
@@ -107,6 +119,7 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         first_block: BlockNumber,
         last_block: BlockNumber,
         proof: &AggregatedProof,
+        prover_name: &str,
     ) -> anyhow::Result<()>;
 
     async fn record_prover_stop(
@@ -115,6 +128,17 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         prover_name: &str,
     ) -> anyhow::Result<()>;
 
+    /// Returns the failed job to the queue, so it's retried after the backoff.
+    /// Only the prover the job is assigned to can fail it.
+    async fn record_job_failure(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        prover_name: &str,
+        error: &str,
+        retry_backoff: Duration,
+    ) -> anyhow::Result<()>;
+
     async fn load_committed_state(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -142,8 +166,47 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         witness: serde_json::Value,
     ) -> anyhow::Result<()>;
 
-    async fn pending_jobs_count(
+    /// Returns the jobs that are currently being proven.
+    async fn load_active_prover_jobs(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageActiveProverJob>>;
+
+    /// Returns the amount of the unfinished jobs by job type and status.
+    async fn load_prover_job_queue_depth(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverJobQueueDepth>>;
+
+    /// Returns the hourly amount of the finished jobs since the given time.
+    async fn load_prover_throughput(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageProverThroughput>>;
+
+    async fn load_all_prover_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverStats>>;
+
+    /// Returns the amount of the unfinished jobs by job type and size.
+    async fn load_pending_jobs_by_size(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverJobsBySize>>;
+
+    /// Returns the average proving time of the jobs finished since the given time by job type and size.
+    async fn load_proving_times_by_size(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageProverJobsBySize>>;
+
+    /// Returns the amount of blocks created since the given time by block size.
+    async fn load_created_blocks_by_size(
         &self,
         connection: &mut StorageProcessor<'_>,
-    ) -> anyhow::Result<u32>;
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageBlocksBySize>>;
 }
@@ -20,14 +20,18 @@ use tokio::sync::RwLock;
 use zksync_config::ProverConfig;
 // Local deps
 use self::database_interface::DatabaseInterface;
+use self::proof_checker::ProofChecker;
 use self::scaler::ScalerOracle;
 use tokio::task::JoinHandle;
 use zksync_circuit::serialization::ProverData;
 use zksync_config::configs::api::ProverApiConfig;
+use zksync_config::ChainConfig;
 use zksync_prover_utils::api::{
-    JobRequestData, JobResultData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
-    WorkingOn,
+    JobFailure, JobRequestData, JobResultData, ProverInputRequest, ProverInputResponse,
+    ProverOutputRequest, WorkingOn,
 };
+use zksync_prover_utils::verification::ProofVerifier;
+use zksync_storage::prover::JobAlreadyFinished;
 use zksync_types::aggregated_operations::{
     AggregatedActionType, AggregatedOperation, BlocksCreateProofOperation,
 };
@@ -42,7 +46,9 @@ mod tests;
 
 pub mod database;
 mod database_interface;
+mod proof_checker;
 mod scaler;
+mod status;
 mod witness_generator;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -58,19 +64,35 @@ struct AppState<DB: DatabaseInterface> {
     secret_auth: String,
     database: DB,
     scaler_oracle: Arc<RwLock<ScalerOracle<DB>>>,
+    proof_checker: ProofChecker,
+    failed_job_retry_backoff: Duration,
+    /// Time for which the job is leased to the prover, extended by every heartbeat.
+    job_lease_duration: Duration,
 }
 
 impl<DB: DatabaseInterface> AppState<DB> {
-    pub fn new(secret_auth: String, database: DB, idle_provers: u32) -> Self {
+    pub fn new(
+        secret_auth: String,
+        database: DB,
+        idle_provers: u32,
+        proof_checker: ProofChecker,
+        failed_job_retry_backoff: Duration,
+        job_lease_duration: Duration,
+        prove_deadline: Duration,
+    ) -> Self {
         let scaler_oracle = Arc::new(RwLock::new(ScalerOracle::new(
             database.clone(),
             idle_provers,
+            prove_deadline,
         )));
 
         Self {
             secret_auth,
             database,
             scaler_oracle,
+            proof_checker,
+            failed_job_retry_backoff,
+            job_lease_duration,
         }
     }
 
@@ -127,10 +149,20 @@ async fn get_job<DB: DatabaseInterface>(
     if r.prover_name.is_empty() {
         return Err(actix_web::error::ErrorBadRequest("empty name"));
     }
+    if data.proof_checker.is_penalized(&r.prover_name).await {
+        vlog::debug!("prover {} is penalized for an invalid proof", r.prover_name);
+        return Ok(HttpResponse::Ok().json(ProverInputResponse {
+            job_id: 0,
+            first_block: BlockNumber(0),
+            last_block: BlockNumber(0),
+            data: None,
+            lease_expires_at: None,
+        }));
+    }
     let mut storage = data.access_storage().await?;
     let ret = data
         .database
-        .load_idle_prover_job_from_job_queue(&mut storage)
+        .load_idle_prover_job_from_job_queue(&mut storage, &r.prover_name, data.job_lease_duration)
         .await
         .map_err(|e| {
             vlog::warn!("could not get next unverified commit operation: {}", e);
@@ -146,6 +178,7 @@ async fn get_job<DB: DatabaseInterface>(
                 serde_json::from_value(prover_job.job_data)
                     .expect("Failed to parse prover job from db"),
             ),
+            lease_expires_at: Some(prover_job.lease_expires_at),
         }))
     } else {
         Ok(HttpResponse::Ok().json(ProverInputResponse {
@@ -153,6 +186,7 @@ async fn get_job<DB: DatabaseInterface>(
             first_block: BlockNumber(0),
             last_block: BlockNumber(0),
             data: None,
+            lease_expires_at: None,
         }))
     }
 }
@@ -168,13 +202,28 @@ async fn working_on<DB: DatabaseInterface>(
         .access_storage()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
-    data.database
-        .record_prover_is_working(&mut storage, r.job_id, &r.prover_name)
+    let lease = data
+        .database
+        .record_prover_is_working(
+            &mut storage,
+            r.job_id,
+            &r.prover_name,
+            data.job_lease_duration,
+        )
         .await
         .map_err(|e| {
             vlog::warn!("failed to record prover work in progress request: {}", e);
             actix_web::error::ErrorInternalServerError("storage layer error")
         })?;
+    if lease.is_none() {
+        // The prover may still publish the proof, it's accepted unless the job is finished.
+        vlog::debug!(
+            "Job {} is not leased to the prover '{}' anymore",
+            r.job_id,
+            r.prover_name
+        );
+        return Err(actix_web::error::ErrorConflict("job lease has expired"));
+    }
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -187,6 +236,50 @@ async fn publish<DB: DatabaseInterface>(
         .access_storage()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
+    let check_result = data
+        .proof_checker
+        .check(
+            &data.database,
+            &mut storage,
+            r.first_block,
+            r.last_block,
+            r.data.clone(),
+        )
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to check received proof: {}", e);
+            actix_web::error::ErrorInternalServerError("proof check error")
+        })?;
+    if let Err(e) = check_result {
+        vlog::error!(
+            "Rejected proof for job: {}, blocks: [{},{}] from prover '{}': {}",
+            r.job_id,
+            r.first_block,
+            r.last_block,
+            r.prover_name,
+            e
+        );
+        metrics::increment_counter!("witness_generator.invalid_proofs", "prover" => r.prover_name.clone());
+        data.proof_checker.penalize(&r.prover_name).await;
+        data.database
+            .record_job_failure(
+                &mut storage,
+                r.job_id,
+                &r.prover_name,
+                &format!("invalid proof: {}", e),
+                data.failed_job_retry_backoff,
+            )
+            .await
+            .map_err(|e| {
+                vlog::warn!("failed to return job to the queue: {}", e);
+                actix_web::error::ErrorInternalServerError("storage layer error")
+            })?;
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "invalid proof: {}",
+            e
+        )));
+    }
+
     let storage_result = match &r.data {
         JobResultData::BlockProof(single_proof) => {
             vlog::info!(
@@ -195,7 +288,13 @@ async fn publish<DB: DatabaseInterface>(
                 r.first_block
             );
             data.database
-                .store_proof(&mut storage, r.job_id, r.first_block, single_proof)
+                .store_proof(
+                    &mut storage,
+                    r.job_id,
+                    r.first_block,
+                    single_proof,
+                    &r.prover_name,
+                )
                 .await
         }
         JobResultData::AggregatedBlockProof(aggregated_proof) => {
@@ -212,11 +311,22 @@ async fn publish<DB: DatabaseInterface>(
                     r.first_block,
                     r.last_block,
                     aggregated_proof,
+                    &r.prover_name,
                 )
                 .await
         }
     };
     if let Err(e) = storage_result {
+        // The job with an expired lease could be finished by another prover.
+        if e.is::<JobAlreadyFinished>() {
+            vlog::info!(
+                "Discarded the proof for job {} from prover '{}': {}",
+                r.job_id,
+                r.prover_name,
+                e
+            );
+            return Err(actix_web::error::ErrorConflict("job is already finished"));
+        }
         vlog::error!("failed to store received proof: {}", e);
         let message = if e.to_string().contains("duplicate key") {
             "duplicate key"
@@ -254,6 +364,40 @@ async fn stopped<DB: DatabaseInterface>(
     Ok(HttpResponse::Ok().finish())
 }
 
+async fn report_failure<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    r: web::Json<JobFailure>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    vlog::warn!(
+        "Prover '{}' failed job with id: {}: {}",
+        r.prover_name,
+        r.job_id,
+        r.error
+    );
+    metrics::increment_counter!("witness_generator.failed_jobs", "prover" => r.prover_name.clone());
+
+    data.database
+        .record_job_failure(
+            &mut storage,
+            r.job_id,
+            &r.prover_name,
+            &r.error,
+            data.failed_job_retry_backoff,
+        )
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to record job failure: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
 /// Input of the `/scaler/replicas` endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequiredReplicasInput {
@@ -267,6 +411,10 @@ pub struct RequiredReplicasOutput {
     /// Amount of the prover entities required for server
     /// to run optimally.
     needed_count: u32,
+    /// Recommended amount of provers by the block chunks size.
+    block_sizes: BTreeMap<usize, u32>,
+    /// Recommended amount of provers for the aggregated proofs.
+    aggregated_proofs: u32,
 }
 
 async fn required_replicas<DB: DatabaseInterface>(
@@ -275,12 +423,16 @@ async fn required_replicas<DB: DatabaseInterface>(
 ) -> actix_web::Result<HttpResponse> {
     let mut oracle = data.scaler_oracle.write().await;
 
-    let needed_count = oracle
+    let recommendation = oracle
         .provers_required()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
 
-    let response = RequiredReplicasOutput { needed_count };
+    let response = RequiredReplicasOutput {
+        needed_count: recommendation.needed_count,
+        block_sizes: recommendation.block_sizes,
+        aggregated_proofs: recommendation.aggregated_proofs,
+    };
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -391,6 +543,7 @@ pub fn run_prover_server<DB: DatabaseInterface>(
     database: DB,
     prover_api_opts: ProverApiConfig,
     prover_opts: ProverConfig,
+    chain_opts: ChainConfig,
 ) -> JoinHandle<()> {
     let witness_generator_opts = prover_opts.witness_generator;
     let core_opts = prover_opts.core;
@@ -424,31 +577,78 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                 // Start pool maintainer threads.
                 let cache = Arc::new(RwLock::new(BTreeMap::default()));
 
-                for offset in 0..witness_generator_opts.witness_generators {
-                    let start_block = (last_verified_block + offset + 1) as u32;
-                    let block_step = witness_generator_opts.witness_generators as u32;
+                if witness_generator_opts.pipelined {
+                    let start_block = (last_verified_block + 1) as u32;
+                    let pipeline_depth = witness_generator_opts.witness_generators;
                     vlog::info!(
-                        "Starting witness generator ({},{})",
+                        "Starting pipelined witness generator ({},{})",
                         start_block,
-                        block_step
+                        pipeline_depth
                     );
-                    let start_wait = witness_generator_opts.prepare_data_interval() * offset as u32;
                     let pool_maintainer = witness_generator::WitnessGenerator::new(
                         database.clone(),
                         witness_generator_opts.prepare_data_interval(),
-                        start_wait,
+                        Duration::default(),
                         BlockNumber(start_block),
-                        BlockNumber(block_step),
+                        BlockNumber(1),
                         cache.clone(),
+                        Some(pipeline_depth),
                     );
                     pool_maintainer.start(panic_sender.clone());
+                } else {
+                    for offset in 0..witness_generator_opts.witness_generators {
+                        let start_block = (last_verified_block + offset + 1) as u32;
+                        let block_step = witness_generator_opts.witness_generators as u32;
+                        vlog::info!(
+                            "Starting witness generator ({},{})",
+                            start_block,
+                            block_step
+                        );
+                        let start_wait =
+                            witness_generator_opts.prepare_data_interval() * offset as u32;
+                        let pool_maintainer = witness_generator::WitnessGenerator::new(
+                            database.clone(),
+                            witness_generator_opts.prepare_data_interval(),
+                            start_wait,
+                            BlockNumber(start_block),
+                            BlockNumber(block_step),
+                            cache.clone(),
+                            None,
+                        );
+                        pool_maintainer.start(panic_sender.clone());
+                    }
                 }
+                // Verification keys are loaded once and shared between the HTTP workers.
+                let proof_verifier = if core_opts.verify_proofs {
+                    let verifier = ProofVerifier::new(
+                        &chain_opts.circuit.supported_block_chunks_sizes,
+                        &chain_opts.circuit.supported_aggregated_proof_sizes,
+                    )
+                    .expect("Failed to load verification keys");
+                    Some(verifier)
+                } else {
+                    vlog::warn!("Verification of the received proofs is disabled");
+                    None
+                };
+                let proof_checker =
+                    ProofChecker::new(proof_verifier, core_opts.invalid_proof_penalty());
+
                 // Start HTTP server.
                 let secret_auth = prover_api_opts.secret_auth.clone();
                 let idle_provers = core_opts.idle_provers;
+                let failed_job_retry_backoff = core_opts.failed_job_retry_backoff();
+                let job_lease_duration = core_opts.gone_timeout();
+                let prove_deadline = chain_opts.state_keeper.block_prove_deadline();
                 HttpServer::new(move || {
-                    let app_state =
-                        AppState::new(secret_auth.clone(), database.clone(), idle_provers);
+                    let app_state = AppState::new(
+                        secret_auth.clone(),
+                        database.clone(),
+                        idle_provers,
+                        proof_checker.clone(),
+                        failed_job_retry_backoff,
+                        job_lease_duration,
+                        prove_deadline,
+                    );
 
                     let auth = HttpAuthentication::bearer(move |req, credentials| async {
                         let secret_auth = req
@@ -471,10 +671,23 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                         .route("/working_on", web::post().to(working_on::<DB>))
                         .route("/publish", web::post().to(publish::<DB>))
                         .route("/stopped", web::post().to(stopped::<DB>))
+                        .route("/report_failure", web::post().to(report_failure::<DB>))
                         .route(
                             "/api/internal/prover/replicas",
                             web::post().to(required_replicas::<DB>),
                         )
+                        .route(
+                            "/api/internal/prover/status/provers",
+                            web::get().to(status::provers::<DB>),
+                        )
+                        .route(
+                            "/api/internal/prover/status/queue",
+                            web::get().to(status::queue::<DB>),
+                        )
+                        .route(
+                            "/api/internal/prover/status/throughput",
+                            web::get().to(status::throughput::<DB>),
+                        )
                 })
                 .bind(&prover_api_opts.bind_addr())
                 .expect("failed to bind")
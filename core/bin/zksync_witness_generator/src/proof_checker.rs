@@ -0,0 +1,93 @@
+//! Checking of the proofs received from the provers before storing them.
+//!
+//! An invalid proof is only discovered on-chain when the `ProveBlocks` transaction reverts,
+//! so the proofs are verified upon receipt. The prover that sent an invalid proof doesn't
+//! receive new jobs for a while.
+
+// Built-in
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+// External
+use tokio::sync::RwLock;
+// Workspace deps
+use zksync_prover_utils::api::JobResultData;
+use zksync_prover_utils::verification::ProofVerifier;
+use zksync_storage::StorageProcessor;
+use zksync_types::BlockNumber;
+// Local deps
+use crate::database_interface::DatabaseInterface;
+
+#[derive(Debug, Clone)]
+pub struct ProofChecker {
+    /// Verifier of the proofs, `None` if the verification is disabled.
+    verifier: Option<Arc<ProofVerifier>>,
+    /// Time during which the prover that sent an invalid proof doesn't receive new jobs.
+    penalty: Duration,
+    /// Provers that sent invalid proofs along with the time of the last invalid proof.
+    penalized_provers: Arc<RwLock<HashMap<String, Instant>>>,
+}
+
+impl ProofChecker {
+    pub fn new(verifier: Option<ProofVerifier>, penalty: Duration) -> Self {
+        Self {
+            verifier: verifier.map(Arc::new),
+            penalty,
+            penalized_provers: Arc::default(),
+        }
+    }
+
+    /// Checks the received proof for the given blocks. Returns `Ok(Err(_))` if the proof is invalid.
+    pub async fn check<DB: DatabaseInterface>(
+        &self,
+        database: &DB,
+        connection: &mut StorageProcessor<'_>,
+        first_block: BlockNumber,
+        last_block: BlockNumber,
+        proof: JobResultData,
+    ) -> anyhow::Result<Result<(), anyhow::Error>> {
+        let verifier = match &self.verifier {
+            Some(verifier) => verifier.clone(),
+            None => return Ok(Ok(())),
+        };
+
+        let mut blocks = Vec::new();
+        for block_number in *first_block..=*last_block {
+            let block = database
+                .load_block(connection, BlockNumber(block_number))
+                .await?
+                .ok_or_else(|| anyhow::format_err!("Block {} doesn't exist", block_number))?;
+            blocks.push((block.block_commitment, block.block_chunks_size));
+        }
+
+        // Verification takes a considerable amount of time, so the executor is not blocked.
+        let result = tokio::task::spawn_blocking(move || match proof {
+            JobResultData::BlockProof(proof) => {
+                anyhow::ensure!(blocks.len() == 1, "Single proof for several blocks");
+                let (block_commitment, block_chunks) = blocks[0];
+                verifier.verify_single_proof(&proof, block_commitment, block_chunks)
+            }
+            JobResultData::AggregatedBlockProof(proof) => {
+                verifier.verify_aggregated_proof(&proof, &blocks)
+            }
+        })
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Forbids the prover to take jobs for the penalty time.
+    pub async fn penalize(&self, prover_name: &str) {
+        self.penalized_provers
+            .write()
+            .await
+            .insert(prover_name.to_string(), Instant::now());
+    }
+
+    /// Returns `true` if the prover has sent an invalid proof recently.
+    pub async fn is_penalized(&self, prover_name: &str) -> bool {
+        let mut penalized_provers = self.penalized_provers.write().await;
+        penalized_provers.retain(|_, penalized_at| penalized_at.elapsed() < self.penalty);
+        penalized_provers.contains_key(prover_name)
+    }
+}
@@ -1,34 +1,259 @@
 //! Module with utilities for prover scaler service.
 
+// Built-in deps
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::Duration;
+// External deps
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
 // Workspace deps
+use zksync_storage::prover::records::StorageProverJobsBySize;
+use zksync_types::prover::ProverJobType;
+// Local deps
 use crate::database_interface::DatabaseInterface;
+
+/// Period used to calculate the average proving times.
+const PROVING_TIME_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+/// Period used to calculate the rate of the incoming blocks.
+const BLOCK_RATE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Prover job along with its size: the block chunks for the single proofs
+/// and the amount of blocks for the aggregated ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum JobKind {
+    SingleProof(usize),
+    AggregatedProof(usize),
+}
+
+impl JobKind {
+    fn from_storage(jobs: &StorageProverJobsBySize) -> Self {
+        let size = jobs.size as usize;
+        if jobs.job_type == ProverJobType::SingleProof.to_string() {
+            Self::SingleProof(size)
+        } else {
+            Self::AggregatedProof(size)
+        }
+    }
+
+    fn is_single(self) -> bool {
+        matches!(self, Self::SingleProof(_))
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::SingleProof(size) | Self::AggregatedProof(size) => size,
+        }
+    }
+}
+
+/// Observed state of the proving pipeline used to estimate the amount of required provers.
+#[derive(Debug, Clone, Default)]
+pub struct ProvingLoad {
+    /// Amount of the unfinished jobs.
+    pub pending_jobs: HashMap<JobKind, u64>,
+    /// Average time of the recently finished jobs.
+    pub proving_times: HashMap<JobKind, Duration>,
+    /// Amount of the blocks created within `block_rate_window` by block chunks.
+    pub created_blocks: HashMap<usize, u64>,
+    pub block_rate_window: Duration,
+}
+
+impl ProvingLoad {
+    /// Returns the average proving time of the job. If there are no observations for
+    /// the job size, the time is extrapolated from the other sizes of the same job type
+    /// assuming it's proportional to the size.
+    fn proving_time(&self, kind: JobKind) -> Option<Duration> {
+        if let Some(time) = self.proving_times.get(&kind) {
+            return Some(*time);
+        }
+        let per_unit: Vec<f64> = self
+            .proving_times
+            .iter()
+            .filter(|(observed, _)| observed.is_single() == kind.is_single() && observed.size() > 0)
+            .map(|(observed, time)| time.as_secs_f64() / observed.size() as f64)
+            .collect();
+        if per_unit.is_empty() {
+            return None;
+        }
+        let average = per_unit.iter().sum::<f64>() / per_unit.len() as f64;
+        Some(Duration::from_secs_f64(average * kind.size() as f64))
+    }
+
+    /// Returns the rate of the incoming blocks of the given size per second.
+    fn block_rate(&self, block_size: Option<usize>) -> f64 {
+        let blocks: u64 = match block_size {
+            Some(size) => self.created_blocks.get(&size).copied().unwrap_or_default(),
+            None => self.created_blocks.values().sum(),
+        };
+        blocks as f64 / self.block_rate_window.as_secs_f64().max(1.0)
+    }
+
+    /// Recommends the amount of provers along with their block sizes.
+    pub fn recommend(&self, deadline: Duration, idle_provers: u32) -> ProversRecommendation {
+        let pending = |kind: JobKind| self.pending_jobs.get(&kind).copied().unwrap_or_default();
+
+        let single_sizes: BTreeSet<usize> = self
+            .pending_jobs
+            .keys()
+            .filter(|kind| kind.is_single())
+            .map(|kind| kind.size())
+            .chain(self.created_blocks.keys().copied())
+            .collect();
+        let block_sizes: BTreeMap<usize, u32> = single_sizes
+            .into_iter()
+            .map(|size| {
+                let kind = JobKind::SingleProof(size);
+                let provers = required_provers(
+                    pending(kind),
+                    self.block_rate(Some(size)),
+                    self.proving_time(kind),
+                    deadline,
+                );
+                (size, provers)
+            })
+            .filter(|(_, provers)| *provers > 0)
+            .collect();
+
+        // Aggregated proofs are considered as a whole, since they can't be assigned
+        // to the specific provers anyway.
+        let aggregated_kinds: BTreeSet<JobKind> = self
+            .pending_jobs
+            .keys()
+            .chain(self.proving_times.keys())
+            .filter(|kind| !kind.is_single())
+            .copied()
+            .collect();
+        let mut aggregated_proofs: u32 = aggregated_kinds
+            .iter()
+            .map(|kind| required_provers(pending(*kind), 0.0, self.proving_time(*kind), deadline))
+            .sum();
+        if !aggregated_kinds.is_empty() {
+            // Every block gets into an aggregated proof, so the aggregated jobs arrive at
+            // the block rate divided by the average amount of blocks in the job.
+            let average_size = aggregated_kinds
+                .iter()
+                .map(|kind| kind.size())
+                .sum::<usize>() as f64
+                / aggregated_kinds.len() as f64;
+            let kind = JobKind::AggregatedProof(average_size.round() as usize);
+            aggregated_proofs += required_provers(
+                0,
+                self.block_rate(None) / average_size.max(1.0),
+                self.proving_time(kind),
+                deadline,
+            );
+        }
+
+        let required = block_sizes.values().sum::<u32>() + aggregated_proofs;
+        ProversRecommendation {
+            needed_count: std::cmp::max(required, idle_provers),
+            block_sizes,
+            aggregated_proofs,
+        }
+    }
+}
+
+/// Returns the amount of provers needed to finish the pending jobs within the deadline
+/// while keeping up with the incoming ones (`arrival_rate` jobs per second).
+fn required_provers(
+    pending: u64,
+    arrival_rate: f64,
+    proving_time: Option<Duration>,
+    deadline: Duration,
+) -> u32 {
+    let time = match proving_time {
+        Some(time) => time.as_secs_f64(),
+        // Nothing is known about the proving time, so every pending job gets its own prover.
+        None => return pending as u32,
+    };
+    let pending = pending as f64;
+    let deadline = deadline.as_secs_f64().max(1.0);
+
+    let required = pending * time / deadline + arrival_rate * time;
+    // There is no point in having more provers than jobs to prove.
+    let available_jobs = (pending + arrival_rate * deadline).ceil();
+    required.ceil().min(available_jobs) as u32
+}
+
+/// Amount of provers required for server to run optimally.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProversRecommendation {
+    /// Total amount of the prover entities, not less than the amount of idle provers.
+    pub needed_count: u32,
+    /// Provers required for the single proofs by the block chunks size.
+    pub block_sizes: BTreeMap<usize, u32>,
+    /// Provers required for the aggregated proofs.
+    pub aggregated_proofs: u32,
+}
+
 /// Scaler oracle provides information for prover scaler
 /// service about required amount of provers for server
 /// to operate optimally.
 #[derive(Debug)]
 pub struct ScalerOracle<DB: DatabaseInterface> {
-    /// Database access to gather the information about amount of pending blocks.
+    /// Database access to gather the information about pending jobs and proving times.
     db: DB,
 
     /// Number of idle provers running for faster up-scaling.
     idle_provers: u32,
+
+    /// Time within which the blocks are expected to be proven.
+    prove_deadline: Duration,
 }
 
 impl<DB: DatabaseInterface> ScalerOracle<DB> {
-    pub fn new(db: DB, idle_provers: u32) -> Self {
-        Self { db, idle_provers }
+    pub fn new(db: DB, idle_provers: u32, prove_deadline: Duration) -> Self {
+        Self {
+            db,
+            idle_provers,
+            prove_deadline,
+        }
     }
 
-    /// Decides how many prover entities should be created depending on the amount of pending blocks.
-    pub async fn provers_required(&mut self) -> anyhow::Result<u32> {
-        // Currently the logic of this method is very simple:
-        // We require a prover for each pending block or IDLE_RROVERS amount if there are not so many
-        // pending jobs.
-
+    /// Decides how many prover entities should be created, so the pending jobs are proven
+    /// within the deadline and the provers keep up with the incoming blocks.
+    pub async fn provers_required(&mut self) -> anyhow::Result<ProversRecommendation> {
         let mut storage = self.db.acquire_connection().await?;
-        let pending_jobs = self.db.pending_jobs_count(&mut storage).await?;
-        let provers_required = std::cmp::max(pending_jobs, self.idle_provers);
+        let now = Utc::now();
+
+        let mut load = ProvingLoad {
+            block_rate_window: BLOCK_RATE_WINDOW,
+            ..Default::default()
+        };
+        for jobs in self.db.load_pending_jobs_by_size(&mut storage).await? {
+            *load
+                .pending_jobs
+                .entry(JobKind::from_storage(&jobs))
+                .or_default() += jobs.jobs as u64;
+        }
+        let since = now - chrono::Duration::from_std(PROVING_TIME_WINDOW)?;
+        for jobs in self
+            .db
+            .load_proving_times_by_size(&mut storage, since)
+            .await?
+        {
+            if let Some(time) = jobs.average_proving_time {
+                load.proving_times.insert(
+                    JobKind::from_storage(&jobs),
+                    Duration::from_millis(time.max(0) as u64),
+                );
+            }
+        }
+        let since = now - chrono::Duration::from_std(BLOCK_RATE_WINDOW)?;
+        for blocks in self
+            .db
+            .load_created_blocks_by_size(&mut storage, since)
+            .await?
+        {
+            load.created_blocks
+                .insert(blocks.block_size as usize, blocks.blocks as u64);
+        }
 
-        Ok(provers_required)
+        let recommendation = load.recommend(self.prove_deadline, self.idle_provers);
+        metrics::gauge!(
+            "witness_generator.provers_required",
+            recommendation.needed_count as f64
+        );
+        Ok(recommendation)
     }
 }
@@ -0,0 +1,222 @@
+//! Read-only endpoints describing the state of the prover cluster.
+//!
+//! `ScalerOracle` only reports the required amount of provers, while these endpoints show
+//! what the provers are doing, how many jobs are waiting and how fast the jobs are finished.
+
+// External uses
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+// Workspace uses
+use zksync_storage::prover::records::{
+    StorageActiveProverJob, StorageProverJobQueueDepth, StorageProverStats, StorageProverThroughput,
+};
+use zksync_types::{prover::ProverJobStatus, BlockNumber};
+// Local uses
+use crate::{database_interface::DatabaseInterface, AppState};
+
+/// Default period covered by the `/api/internal/prover/status/throughput` endpoint.
+const DEFAULT_THROUGHPUT_HOURS: u32 = 24;
+/// Maximum period covered by the `/api/internal/prover/status/throughput` endpoint.
+const MAX_THROUGHPUT_HOURS: u32 = 7 * 24;
+
+/// Prover that is currently working on a job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveProver {
+    /// Name of the prover, absent for the jobs given out before the names were recorded.
+    pub prover_name: Option<String>,
+    pub job_id: i32,
+    pub job_type: String,
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+    /// Amount of the previous failed attempts to prove the job.
+    pub retries: i32,
+    pub started_at: Option<DateTime<Utc>>,
+    pub last_heartbeat: DateTime<Utc>,
+    /// Time since the last heartbeat in seconds.
+    pub heartbeat_age: i64,
+}
+
+impl ActiveProver {
+    pub fn new(job: StorageActiveProverJob, now: DateTime<Utc>) -> Self {
+        Self {
+            prover_name: job.prover_name,
+            job_id: job.id,
+            job_type: job.job_type,
+            first_block: BlockNumber(job.first_block as u32),
+            last_block: BlockNumber(job.last_block as u32),
+            retries: job.retries,
+            started_at: job.started_at,
+            last_heartbeat: job.updated_at,
+            heartbeat_age: (now - job.updated_at).num_seconds(),
+        }
+    }
+}
+
+/// Track record of a prover.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProverStats {
+    pub prover_name: String,
+    pub successes: i64,
+    pub failures: i64,
+    /// Failures since the last successful job.
+    pub consecutive_failures: i64,
+    /// Average time of the successful job in milliseconds.
+    pub average_proving_time: Option<i64>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+}
+
+impl From<StorageProverStats> for ProverStats {
+    fn from(stats: StorageProverStats) -> Self {
+        Self {
+            average_proving_time: stats.average_proving_time(),
+            prover_name: stats.prover_name,
+            successes: stats.successes,
+            failures: stats.failures,
+            consecutive_failures: stats.consecutive_failures,
+            last_failure_at: stats.last_failure_at,
+        }
+    }
+}
+
+/// Output of the `/api/internal/prover/status/provers` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProversStatus {
+    pub active_provers: Vec<ActiveProver>,
+    pub prover_stats: Vec<ProverStats>,
+}
+
+/// Unfinished jobs of the same type and status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobQueueDepth {
+    pub job_type: String,
+    /// Either `idle` or `in_progress`.
+    pub job_status: String,
+    pub jobs: i64,
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+}
+
+impl From<StorageProverJobQueueDepth> for JobQueueDepth {
+    fn from(depth: StorageProverJobQueueDepth) -> Self {
+        let job_status = match ProverJobStatus::from_number(depth.job_status) {
+            Ok(ProverJobStatus::Idle) => "idle".to_string(),
+            Ok(ProverJobStatus::InProgress) => "in_progress".to_string(),
+            Ok(ProverJobStatus::Done) => "done".to_string(),
+            Err(_) => format!("unknown ({})", depth.job_status),
+        };
+        Self {
+            job_type: depth.job_type,
+            job_status,
+            jobs: depth.jobs,
+            first_block: BlockNumber(depth.first_block as u32),
+            last_block: BlockNumber(depth.last_block as u32),
+        }
+    }
+}
+
+/// Jobs of the same type finished within an hour.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThroughputEntry {
+    /// Start of the hour.
+    pub period: DateTime<Utc>,
+    pub job_type: String,
+    pub jobs: i64,
+    pub blocks: i64,
+    /// Average time of the job in milliseconds.
+    pub average_proving_time: Option<i64>,
+}
+
+impl From<StorageProverThroughput> for ThroughputEntry {
+    fn from(throughput: StorageProverThroughput) -> Self {
+        Self {
+            period: throughput.period,
+            job_type: throughput.job_type,
+            jobs: throughput.jobs,
+            blocks: throughput.blocks,
+            average_proving_time: throughput.average_proving_time,
+        }
+    }
+}
+
+/// Query of the `/api/internal/prover/status/throughput` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThroughputQuery {
+    /// Amount of the last hours to report, 24 by default.
+    pub hours: Option<u32>,
+}
+
+fn storage_error(e: anyhow::Error) -> actix_web::Error {
+    vlog::warn!("failed to load prover cluster status: {}", e);
+    actix_web::error::ErrorInternalServerError("storage layer error")
+}
+
+pub(crate) async fn provers<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let now = Utc::now();
+    let active_provers = data
+        .database
+        .load_active_prover_jobs(&mut storage)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(|job| ActiveProver::new(job, now))
+        .collect();
+    let prover_stats = data
+        .database
+        .load_all_prover_stats(&mut storage)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(ProverStats::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ProversStatus {
+        active_provers,
+        prover_stats,
+    }))
+}
+
+pub(crate) async fn queue<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let depth: Vec<JobQueueDepth> = data
+        .database
+        .load_prover_job_queue_depth(&mut storage)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(JobQueueDepth::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(depth))
+}
+
+pub(crate) async fn throughput<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    query: web::Query<ThroughputQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let hours = query.hours.unwrap_or(DEFAULT_THROUGHPUT_HOURS);
+    if hours == 0 || hours > MAX_THROUGHPUT_HOURS {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "hours should be in range [1, {}]",
+            MAX_THROUGHPUT_HOURS
+        )));
+    }
+    let since = Utc::now() - chrono::Duration::hours(hours.into());
+
+    let mut storage = data.access_storage().await?;
+    let throughput: Vec<ThroughputEntry> = data
+        .database
+        .load_prover_throughput(&mut storage, since)
+        .await
+        .map_err(storage_error)?
+        .into_iter()
+        .map(ThroughputEntry::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(throughput))
+}
@@ -1,22 +1,29 @@
 #![allow(clippy::diverging_sub_expression)]
 // Built-in
 use std::clone::Clone;
+use std::collections::BTreeMap;
+use std::ops::Bound::{Excluded, Included};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 // External uses
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 // Workspace uses
 use zksync_crypto::params::account_tree_depth;
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_storage::chain::tree_cache::records::AccountTreeCache;
-use zksync_storage::prover::records::{StorageBlockWitness, StorageProverJobQueue, StoredProof};
+use zksync_storage::prover::records::{
+    StorageActiveProverJob, StorageBlockWitness, StorageProverJobQueue, StorageProverJobQueueDepth,
+    StorageProverStats, StorageProverThroughput, StoredProof,
+};
+use zksync_storage::prover::JobAlreadyFinished;
 use zksync_storage::StorageProcessor;
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
+    helpers::apply_updates,
     prover::{ProverJob, ProverJobStatus, ProverJobType},
     AccountId, AccountMap, AccountTree, AccountUpdates, Address, BlockNumber,
 };
@@ -32,6 +39,8 @@ pub struct MockDatabase {
     blocks: Arc<RwLock<Vec<Block>>>,
     account_tree_cache: Arc<RwLock<AccountTreeCache>>,
     accounts_state: Arc<RwLock<(u32, AccountMap)>>,
+    /// Account updates made by the blocks on top of `accounts_state`.
+    state_diffs: Arc<RwLock<BTreeMap<BlockNumber, AccountUpdates>>>,
 }
 
 impl MockDatabase {
@@ -49,6 +58,7 @@ impl MockDatabase {
                 tree_cache_binary,
             })),
             accounts_state: Arc::new(RwLock::new((0, accounts))),
+            state_diffs: Arc::new(RwLock::new(BTreeMap::new())),
         }
     }
 
@@ -75,6 +85,15 @@ impl MockDatabase {
     pub async fn add_block(&self, block: Block) {
         self.blocks.write().await.push(block);
     }
+
+    /// Adds the block along with the account updates it has made.
+    pub async fn add_block_with_updates(&self, block: Block, updates: AccountUpdates) {
+        self.state_diffs
+            .write()
+            .await
+            .insert(block.block_number, updates);
+        self.add_block(block).await;
+    }
 }
 
 #[async_trait::async_trait]
@@ -109,6 +128,12 @@ impl DatabaseInterface for MockDatabase {
             id,
             job_priority,
             job_data,
+            retries: 0,
+            last_error: None,
+            retry_after: None,
+            prover_name: None,
+            started_at: None,
+            lease_expires_at: None,
         };
 
         prover_job_queue.1.push(new_job);
@@ -135,19 +160,6 @@ impl DatabaseInterface for MockDatabase {
         Ok(BlockNumber(block_number as u32))
     }
 
-    async fn pending_jobs_count(&self, _: &mut StorageProcessor<'_>) -> anyhow::Result<u32> {
-        let count = self
-            .prover_job_queue
-            .read()
-            .await
-            .1
-            .iter()
-            .filter(|job| job.job_status == ProverJobStatus::Idle.to_number())
-            .count();
-
-        Ok(count as u32)
-    }
-
     async fn load_aggregated_op_that_affects_block(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -166,7 +178,8 @@ impl DatabaseInterface for MockDatabase {
         let single_proof = proofs
             .iter()
             .find(|proof| proof.block_number == *block_number as i64)
-            .map(|stored| serde_json::from_value(stored.proof.clone()).unwrap());
+            .and_then(|stored| stored.proof.clone())
+            .map(|proof| serde_json::from_value(proof).unwrap());
 
         Ok(single_proof)
     }
@@ -176,7 +189,9 @@ impl DatabaseInterface for MockDatabase {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
 
         for job in prover_job_queue.iter_mut() {
-            if now - job.updated_at > chrono::Duration::seconds(10) {
+            if job.job_status == ProverJobStatus::InProgress.to_number()
+                && job.lease_expires_at.map_or(false, |lease| lease <= now)
+            {
                 job.job_status = ProverJobStatus::Idle.to_number();
                 job.updated_at = now;
                 job.updated_by = "server_clean_idle".to_string();
@@ -224,23 +239,39 @@ impl DatabaseInterface for MockDatabase {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         _: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease_duration: Duration,
     ) -> anyhow::Result<Option<ProverJob>> {
+        let now = Utc::now();
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
         let idle_prover_job = prover_job_queue
             .iter_mut()
-            .filter(|job| job.job_status == ProverJobStatus::Idle.to_number())
+            .filter(|job| {
+                let is_idle = job.job_status == ProverJobStatus::Idle.to_number()
+                    && job
+                        .retry_after
+                        .map_or(true, |retry_after| retry_after <= now);
+                let is_lease_expired = job.job_status == ProverJobStatus::InProgress.to_number()
+                    && job.lease_expires_at.map_or(false, |lease| lease <= now);
+                is_idle || is_lease_expired
+            })
             .max_by_key(|job| (job.job_priority, job.id));
 
         let prover_job = if let Some(job) = idle_prover_job {
             job.job_status = ProverJobStatus::InProgress.to_number();
-            job.updated_at = Utc::now();
+            job.updated_at = now;
             job.updated_by = "server_give_job".to_string();
+            job.prover_name = Some(prover_name.to_string());
+            job.started_at = Some(now);
+            let lease_expires_at = now + chrono::Duration::from_std(lease_duration)?;
+            job.lease_expires_at = Some(lease_expires_at);
 
             Some(ProverJob::new(
                 job.id,
                 BlockNumber(job.first_block as u32),
                 BlockNumber(job.last_block as u32),
                 job.job_data.clone(),
+                lease_expires_at,
             ))
         } else {
             None
@@ -254,16 +285,27 @@ impl DatabaseInterface for MockDatabase {
         _: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()> {
+        lease_duration: Duration,
+    ) -> anyhow::Result<Option<DateTime<Utc>>> {
+        let now = Utc::now();
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
-        let prover_job = prover_job_queue.iter_mut().find(|job| job.id == job_id);
+        let prover_job = prover_job_queue.iter_mut().find(|job| {
+            job.id == job_id
+                && job.job_status == ProverJobStatus::InProgress.to_number()
+                && job.prover_name.as_deref() == Some(prover_name)
+        });
 
-        if let Some(job) = prover_job {
-            job.updated_at = Utc::now();
+        let lease = if let Some(job) = prover_job {
+            let lease_expires_at = now + chrono::Duration::from_std(lease_duration)?;
+            job.updated_at = now;
             job.updated_by = prover_name.to_string();
-        }
+            job.lease_expires_at = Some(lease_expires_at);
+            Some(lease_expires_at)
+        } else {
+            None
+        };
 
-        Ok(())
+        Ok(lease)
     }
 
     async fn store_proof(
@@ -272,11 +314,15 @@ impl DatabaseInterface for MockDatabase {
         job_id: i32,
         block_number: BlockNumber,
         proof: &SingleProof,
+        _prover_name: &str,
     ) -> anyhow::Result<()> {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
         let prover_job = prover_job_queue.iter_mut().find(|job| job.id == job_id);
 
         if let Some(job) = prover_job {
+            if job.job_status == ProverJobStatus::Done.to_number() {
+                return Err(JobAlreadyFinished { job_id }.into());
+            }
             job.updated_at = Utc::now();
             job.job_status = ProverJobStatus::Done.to_number();
             job.updated_by = "server_finish_job".to_string();
@@ -284,7 +330,9 @@ impl DatabaseInterface for MockDatabase {
         let proof = StoredProof {
             block_number: i64::from(*block_number),
             created_at: Utc::now(),
-            proof: serde_json::to_value(proof).unwrap(),
+            proof: Some(serde_json::to_value(proof).unwrap()),
+            blob_key: None,
+            blob_checksum: None,
         };
         self.proofs.write().await.push(proof);
 
@@ -298,6 +346,7 @@ impl DatabaseInterface for MockDatabase {
         _first_block: BlockNumber,
         _last_block: BlockNumber,
         _proof: &AggregatedProof,
+        _prover_name: &str,
     ) -> anyhow::Result<()> {
         unreachable!();
     }
@@ -310,7 +359,7 @@ impl DatabaseInterface for MockDatabase {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
 
         for job in prover_job_queue.iter_mut() {
-            if job.updated_by == prover_name
+            if job.prover_name.as_deref() == Some(prover_name)
                 && job.job_status == ProverJobStatus::InProgress.to_number()
             {
                 job.job_status = ProverJobStatus::Idle.to_number();
@@ -321,22 +370,82 @@ impl DatabaseInterface for MockDatabase {
         Ok(())
     }
 
+    async fn record_job_failure(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        job_id: i32,
+        prover_name: &str,
+        error: &str,
+        retry_backoff: Duration,
+    ) -> anyhow::Result<()> {
+        let prover_job_queue = &mut self.prover_job_queue.write().await.1;
+
+        for job in prover_job_queue.iter_mut() {
+            if job.id == job_id
+                && job.job_status == ProverJobStatus::InProgress.to_number()
+                && job.prover_name.as_deref() == Some(prover_name)
+            {
+                let backoff = retry_backoff * 2u32.pow(job.retries.min(5) as u32);
+                job.job_status = ProverJobStatus::Idle.to_number();
+                job.updated_at = Utc::now();
+                job.updated_by = "server_job_failed".to_string();
+                job.retries += 1;
+                job.last_error = Some(error.to_string());
+                job.retry_after = Some(Utc::now() + chrono::Duration::from_std(backoff)?);
+            }
+        }
+
+        Ok(())
+    }
+
     async fn load_committed_state(
         &self,
         _: &mut StorageProcessor<'_>,
-        _block: Option<BlockNumber>,
+        block: Option<BlockNumber>,
     ) -> anyhow::Result<(BlockNumber, AccountMap)> {
-        let (last_block, accounts) = self.accounts_state.read().await.clone();
+        let (last_block, mut accounts) = self.accounts_state.read().await.clone();
+        let updates = self
+            .state_diffs
+            .read()
+            .await
+            .iter()
+            .filter(|(block_number, _)| block.map_or(true, |block| **block_number <= block))
+            .flat_map(|(_, updates)| updates.iter().cloned())
+            .collect();
+        apply_updates(&mut accounts, updates);
         Ok((BlockNumber(last_block), accounts))
     }
 
     async fn load_state_diff(
         &self,
         _: &mut StorageProcessor<'_>,
-        _from_block: BlockNumber,
-        _to_block: Option<BlockNumber>,
+        from_block: BlockNumber,
+        to_block: Option<BlockNumber>,
     ) -> anyhow::Result<Option<(BlockNumber, AccountUpdates)>> {
-        Ok(None)
+        let state_diffs = self.state_diffs.read().await;
+        let to_block = to_block
+            .or_else(|| state_diffs.keys().next_back().copied())
+            .unwrap_or(BlockNumber(0));
+        let (start_block, end_block) = (from_block.min(to_block), from_block.max(to_block));
+        let updates: AccountUpdates = state_diffs
+            .range((Excluded(start_block), Included(end_block)))
+            .flat_map(|(_, updates)| updates.iter().cloned())
+            .collect();
+        if updates.is_empty() {
+            return Ok(None);
+        }
+
+        // Going backwards, the updates are reverted in the reverse order.
+        let updates = if from_block <= to_block {
+            updates
+        } else {
+            updates
+                .into_iter()
+                .rev()
+                .map(|(id, update)| (id, update.reversed_update()))
+                .collect()
+        };
+        Ok(Some((to_block, updates)))
     }
 
     async fn store_account_tree_cache(
@@ -367,7 +476,8 @@ impl DatabaseInterface for MockDatabase {
         let witness = block_witness
             .iter()
             .find(|witness| witness.block == *block_number as i64)
-            .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness"));
+            .and_then(|w| w.witness.as_deref())
+            .map(|w| serde_json::from_str(w).expect("Failed to deserialize witness"));
 
         Ok(witness)
     }
@@ -387,10 +497,153 @@ impl DatabaseInterface for MockDatabase {
         if is_block_not_saved_yet {
             block_witness.push(StorageBlockWitness {
                 block: *block as i64,
-                witness: witness_str,
+                witness: Some(witness_str),
+                blob_key: None,
+                blob_checksum: None,
             });
         }
 
         Ok(())
     }
+
+    async fn load_active_prover_jobs(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageActiveProverJob>> {
+        let jobs = self
+            .prover_job_queue
+            .read()
+            .await
+            .1
+            .iter()
+            .filter(|job| job.job_status == ProverJobStatus::InProgress.to_number())
+            .map(|job| StorageActiveProverJob {
+                id: job.id,
+                job_type: job.job_type.clone(),
+                first_block: job.first_block,
+                last_block: job.last_block,
+                prover_name: job.prover_name.clone(),
+                started_at: job.started_at,
+                updated_at: job.updated_at,
+                retries: job.retries,
+            })
+            .collect();
+
+        Ok(jobs)
+    }
+
+    async fn load_prover_job_queue_depth(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverJobQueueDepth>> {
+        let mut depth: Vec<StorageProverJobQueueDepth> = Vec::new();
+        for job in self.prover_job_queue.read().await.1.iter() {
+            if job.job_status == ProverJobStatus::Done.to_number() {
+                continue;
+            }
+            match depth
+                .iter_mut()
+                .find(|d| d.job_type == job.job_type && d.job_status == job.job_status)
+            {
+                Some(d) => {
+                    d.jobs += 1;
+                    d.first_block = d.first_block.min(job.first_block);
+                    d.last_block = d.last_block.max(job.last_block);
+                }
+                None => depth.push(StorageProverJobQueueDepth {
+                    job_type: job.job_type.clone(),
+                    job_status: job.job_status,
+                    jobs: 1,
+                    first_block: job.first_block,
+                    last_block: job.last_block,
+                }),
+            }
+        }
+
+        Ok(depth)
+    }
+
+    async fn load_prover_throughput(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        _since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageProverThroughput>> {
+        Ok(Vec::new())
+    }
+
+    async fn load_all_prover_stats(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverStats>> {
+        Ok(Vec::new())
+    }
+
+    async fn load_pending_jobs_by_size(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<StorageProverJobsBySize>> {
+        let blocks = self.blocks.read().await;
+        let mut jobs: Vec<StorageProverJobsBySize> = Vec::new();
+        for job in self.prover_job_queue.read().await.1.iter() {
+            if job.job_status == ProverJobStatus::Done.to_number() {
+                continue;
+            }
+            let size = if job.job_type == ProverJobType::SingleProof.to_string() {
+                blocks
+                    .iter()
+                    .find(|block| i64::from(*block.block_number) == job.first_block)
+                    .map_or(0, |block| block.block_chunks_size as i64)
+            } else {
+                job.last_block - job.first_block + 1
+            };
+            match jobs
+                .iter_mut()
+                .find(|jobs| jobs.job_type == job.job_type && jobs.size == size)
+            {
+                Some(jobs) => jobs.jobs += 1,
+                None => jobs.push(StorageProverJobsBySize {
+                    job_type: job.job_type.clone(),
+                    size,
+                    jobs: 1,
+                    average_proving_time: None,
+                }),
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    async fn load_proving_times_by_size(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        _since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageProverJobsBySize>> {
+        Ok(Vec::new())
+    }
+
+    async fn load_created_blocks_by_size(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<StorageBlocksBySize>> {
+        let mut created_blocks: Vec<StorageBlocksBySize> = Vec::new();
+        for block in self.blocks.read().await.iter() {
+            if (block.timestamp as i64) < since.timestamp() {
+                continue;
+            }
+            let block_size = block.block_chunks_size as i64;
+            match created_blocks
+                .iter_mut()
+                .find(|blocks| blocks.block_size == block_size)
+            {
+                Some(blocks) => blocks.blocks += 1,
+                None => created_blocks.push(StorageBlocksBySize {
+                    block_size,
+                    blocks: 1,
+                }),
+            }
+        }
+
+        Ok(created_blocks)
+    }
 }
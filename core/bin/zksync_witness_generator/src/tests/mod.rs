@@ -1,2 +1,5 @@
-mod mock;
+pub(crate) mod mock;
+mod proof_checker;
 mod prover_server;
+mod scaler;
+mod status;
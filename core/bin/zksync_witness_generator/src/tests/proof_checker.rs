@@ -0,0 +1,18 @@
+// Built-in deps
+use std::time::Duration;
+// Local deps
+use crate::proof_checker::ProofChecker;
+
+/// Checks that the penalized prover doesn't receive jobs until the penalty expires.
+#[tokio::test]
+async fn prover_penalty() {
+    let checker = ProofChecker::new(None, Duration::from_millis(100));
+    assert!(!checker.is_penalized("prover").await);
+
+    checker.penalize("prover").await;
+    assert!(checker.is_penalized("prover").await);
+    assert!(!checker.is_penalized("another_prover").await);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(!checker.is_penalized("prover").await);
+}
@@ -9,7 +9,7 @@ use zksync_config::{
         api::ProverApiConfig,
         prover::{Core, Prover, WitnessGenerator},
     },
-    ProverConfig,
+    ChainConfig, ProverConfig,
 };
 use zksync_crypto::franklin_crypto::bellman::pairing::ff::{PrimeField, PrimeFieldRepr};
 use zksync_prover::{client, ApiClient};
@@ -43,10 +43,14 @@ impl Default for MockProverOptions {
             core: Core {
                 gone_timeout: 60000,
                 idle_provers: 1,
+                verify_proofs: false,
+                invalid_proof_penalty: 60000,
+                failed_job_retry_backoff: 1000,
             },
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 1,
+                pipelined: false,
             },
         };
 
@@ -57,7 +61,12 @@ impl Default for MockProverOptions {
 async fn spawn_server(database: MockDatabase) {
     let prover_options = MockProverOptions::default();
 
-    run_prover_server(database, prover_options.0, prover_options.1);
+    run_prover_server(
+        database,
+        prover_options.0,
+        prover_options.1,
+        ChainConfig::from_env(),
+    );
 }
 
 #[tokio::test]
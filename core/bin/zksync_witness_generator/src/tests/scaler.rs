@@ -0,0 +1,64 @@
+// Built-in deps
+use std::time::Duration;
+// Local deps
+use crate::scaler::{JobKind, ProvingLoad};
+
+const DEADLINE: Duration = Duration::from_secs(600);
+
+/// Checks that every pending job gets its own prover if the proving time is unknown.
+#[test]
+fn unknown_proving_time() {
+    let mut load = ProvingLoad::default();
+    load.pending_jobs.insert(JobKind::SingleProof(10), 3);
+    load.pending_jobs.insert(JobKind::AggregatedProof(4), 1);
+
+    let recommendation = load.recommend(DEADLINE, 1);
+    assert_eq!(recommendation.needed_count, 4);
+    assert_eq!(recommendation.block_sizes.get(&10), Some(&3));
+    assert_eq!(recommendation.aggregated_proofs, 1);
+
+    // Idle provers are kept when there are no jobs.
+    let recommendation = ProvingLoad::default().recommend(DEADLINE, 2);
+    assert_eq!(recommendation.needed_count, 2);
+    assert!(recommendation.block_sizes.is_empty());
+}
+
+/// Checks that the pending jobs are spread over the deadline.
+#[test]
+fn pending_jobs_within_deadline() {
+    let mut load = ProvingLoad::default();
+    load.pending_jobs.insert(JobKind::SingleProof(10), 10);
+    load.proving_times
+        .insert(JobKind::SingleProof(10), Duration::from_secs(60));
+
+    // 10 jobs taking a minute each can be proven by a single prover within 10 minutes.
+    let recommendation = load.recommend(DEADLINE, 0);
+    assert_eq!(recommendation.needed_count, 1);
+
+    // The amount of provers doesn't exceed the amount of jobs.
+    let recommendation = load.recommend(Duration::from_secs(1), 0);
+    assert_eq!(recommendation.needed_count, 10);
+}
+
+/// Checks that the provers keep up with the incoming blocks, and the proving time
+/// of the unobserved block size is extrapolated from the observed ones.
+#[test]
+fn incoming_blocks() {
+    let mut load = ProvingLoad {
+        block_rate_window: Duration::from_secs(3600),
+        ..Default::default()
+    };
+    load.proving_times
+        .insert(JobKind::SingleProof(10), Duration::from_secs(60));
+    load.proving_times
+        .insert(JobKind::AggregatedProof(4), Duration::from_secs(120));
+    // A block of 32 chunks per minute, each takes 192 seconds to prove.
+    load.created_blocks.insert(32, 60);
+
+    let recommendation = load.recommend(DEADLINE, 0);
+    assert_eq!(recommendation.block_sizes.get(&32), Some(&4));
+    assert_eq!(recommendation.block_sizes.get(&10), None);
+    // An aggregated job each 4 minutes, 2 minutes to prove.
+    assert_eq!(recommendation.aggregated_proofs, 1);
+    assert_eq!(recommendation.needed_count, 5);
+}
@@ -0,0 +1,49 @@
+// External deps
+use chrono::{Duration, Utc};
+// Workspace deps
+use zksync_storage::prover::records::{StorageActiveProverJob, StorageProverJobQueueDepth};
+use zksync_types::{
+    prover::{ProverJobStatus, ProverJobType},
+    BlockNumber,
+};
+// Local deps
+use crate::status::{ActiveProver, JobQueueDepth};
+
+/// Checks that the heartbeat age is calculated from the last update of the job.
+#[test]
+fn active_prover_heartbeat_age() {
+    let now = Utc::now();
+    let job = StorageActiveProverJob {
+        id: 1,
+        job_type: ProverJobType::SingleProof.to_string(),
+        first_block: 5,
+        last_block: 5,
+        prover_name: Some("prover".to_string()),
+        started_at: Some(now - Duration::seconds(100)),
+        updated_at: now - Duration::seconds(15),
+        retries: 0,
+    };
+
+    let prover = ActiveProver::new(job, now);
+    assert_eq!(prover.heartbeat_age, 15);
+    assert_eq!(prover.first_block, BlockNumber(5));
+    assert_eq!(prover.prover_name.as_deref(), Some("prover"));
+}
+
+/// Checks that the job statuses are reported by name.
+#[test]
+fn job_queue_depth_status() {
+    let depth = |status: ProverJobStatus| StorageProverJobQueueDepth {
+        job_type: ProverJobType::AggregatedProof.to_string(),
+        job_status: status.to_number(),
+        jobs: 2,
+        first_block: 1,
+        last_block: 8,
+    };
+
+    let idle = JobQueueDepth::from(depth(ProverJobStatus::Idle));
+    assert_eq!(idle.job_status, "idle");
+    assert_eq!(idle.last_block, BlockNumber(8));
+    let in_progress = JobQueueDepth::from(depth(ProverJobStatus::InProgress));
+    assert_eq!(in_progress.job_status, "in_progress");
+}
@@ -1,12 +1,13 @@
 use std::time::Instant;
 // Built-in
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::ops::Bound::{Included, Unbounded};
 use std::sync::Arc;
 use std::{thread, time};
 // External
 use futures::channel::mpsc;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use zksync_crypto::merkle_tree::parallel_smt::SparseMerkleTreeSerializableCacheBN256;
 // Workspace deps
@@ -15,8 +16,10 @@ use zksync_circuit::serialization::ProverData;
 use zksync_circuit::witness::utils::build_block_witness;
 use zksync_crypto::circuit::CircuitAccountTree;
 use zksync_crypto::params::account_tree_depth;
+use zksync_crypto::Fr;
 use zksync_types::block::Block;
-use zksync_types::BlockNumber;
+use zksync_types::helpers::apply_updates;
+use zksync_types::{AccountMap, BlockNumber};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 /// The essential part of this structure is `maintain` function
@@ -24,6 +27,9 @@ use zksync_utils::panic_notify::ThreadPanicNotify;
 ///
 /// This will generate and store in db witnesses for blocks with indexes
 /// start_block, start_block + block_step, start_block + 2*block_step, ...
+///
+/// In the pipelined mode (see `pipeline_depth`) the witnesses are generated for every block
+/// starting from start_block, up to `pipeline_depth` blocks at a time.
 pub struct WitnessGenerator<DB: DatabaseInterface> {
     /// Connection to the database.
     database: DB,
@@ -34,6 +40,12 @@ pub struct WitnessGenerator<DB: DatabaseInterface> {
     block_step: BlockNumber,
     start_wait: time::Duration,
     cached_account_tree: Arc<RwLock<BTreeMap<BlockNumber, CircuitAccountTree>>>,
+    /// Amount of blocks which witnesses are computed concurrently in the pipelined mode.
+    /// If not set, the blocks are processed one at a time.
+    ///
+    /// Every witness being computed works on its own copy of the account tree, so the pipeline
+    /// keeps up to `pipeline_depth + 1` trees in memory, including the rolling one.
+    pipeline_depth: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -43,6 +55,52 @@ enum BlockInfo {
     NoWitness(Block),
 }
 
+/// Account tree kept in memory by the pipelined witness generator along with the accounts
+/// it was built from, so it can be moved to the next block using the block state diff only.
+struct RollingTree {
+    /// Block which state the tree corresponds to.
+    block: BlockNumber,
+    accounts: AccountMap,
+    tree: CircuitAccountTree,
+}
+
+/// Witness being computed on the blocking thread pool.
+type PendingWitness = (BlockNumber, JoinHandle<anyhow::Result<ProverData>>);
+
+/// State of the pipelined witness generator kept between the rounds.
+struct Pipeline {
+    /// Next block to schedule the witness computation for.
+    next_block: BlockNumber,
+    rolling_tree: Option<RollingTree>,
+    /// Witnesses being computed, in the order of blocks.
+    pending: VecDeque<PendingWitness>,
+}
+
+impl Pipeline {
+    fn new(start_block: BlockNumber) -> Self {
+        Self {
+            next_block: start_block,
+            rolling_tree: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Drops the pipeline state, so the blocks are scheduled again starting from `block`.
+    async fn restart(&mut self, block: BlockNumber) {
+        // Blocking tasks can't be interrupted once started, so the computations that are
+        // running already are awaited. Otherwise, the trees they hold would add up to the ones
+        // of the restarted pipeline. Tasks that are not started yet are cancelled.
+        for (_, witness) in &self.pending {
+            witness.abort();
+        }
+        for (_, witness) in self.pending.drain(..) {
+            let _ = witness.await;
+        }
+        self.rolling_tree = None;
+        self.next_block = block;
+    }
+}
+
 impl<DB: DatabaseInterface> WitnessGenerator<DB> {
     /// Creates a new `WitnessGenerator` object.
     pub fn new(
@@ -52,7 +110,12 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         start_block: BlockNumber,
         block_step: BlockNumber,
         cached_account_tree: Arc<RwLock<BTreeMap<BlockNumber, CircuitAccountTree>>>,
+        pipeline_depth: Option<usize>,
     ) -> Self {
+        assert!(
+            pipeline_depth.is_none() || *block_step == 1,
+            "pipelined witness generator must process every block"
+        );
         Self {
             database,
             rounds_interval,
@@ -60,6 +123,7 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
             block_step,
             start_wait,
             cached_account_tree,
+            pipeline_depth,
         }
     }
 
@@ -75,7 +139,10 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
                     .expect("Unable to build runtime for a witness generator");
 
                 runtime.block_on(async move {
-                    self.maintain().await;
+                    match self.pipeline_depth {
+                        Some(pipeline_depth) => self.maintain_pipelined(pipeline_depth).await,
+                        None => self.maintain().await,
+                    }
                 });
             })
             .expect("failed to start provers server");
@@ -256,9 +323,13 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
 
     /// Remove old account tree cache we want to keep more than step just to make sure that we won't go to the database
     async fn remove_cache(&self, block: BlockNumber) {
+        let oldest_kept_block = match block.checked_sub(2 * self.block_step.0) {
+            Some(block) => BlockNumber(block),
+            None => return,
+        };
         let mut cache = self.cached_account_tree.write().await;
         let keys: Vec<_> = cache
-            .range((Unbounded, Included(block - 2 * self.block_step.0)))
+            .range((Unbounded, Included(oldest_kept_block)))
             .map(|(key, _)| *key)
             .collect();
         for key in keys {
@@ -277,27 +348,140 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         let witness: ProverData = build_block_witness(&mut circuit_account_tree, &block)?.into();
         metrics::histogram!("witness_generator", start.elapsed(), "stage" => "build_witness");
 
+        self.save_witness(block.block_number, witness).await?;
+
+        metrics::histogram!("witness_generator", fn_start.elapsed(), "stage" => "prepare_witness_and_save_it");
+        Ok(())
+    }
+
+    async fn save_witness(
+        &self,
+        block_number: BlockNumber,
+        witness: ProverData,
+    ) -> anyhow::Result<()> {
         let start = Instant::now();
         let mut storage = self.database.acquire_connection().await?;
         self.database
             .store_witness(
                 &mut storage,
-                block.block_number,
+                block_number,
                 serde_json::to_value(witness).expect("Witness serialize to json"),
             )
             .await?;
         metrics::histogram!("witness_generator", start.elapsed(), "stage" => "store_witness");
 
-        metrics::histogram!("witness_generator", fn_start.elapsed(), "stage" => "prepare_witness_and_save_it");
-
         metrics::gauge!(
             "last_processed_block",
-            block.block_number.0 as f64,
+            block_number.0 as f64,
             "stage" => "witness_generator"
         );
         Ok(())
     }
 
+    /// Loads the account tree and the accounts for the block to start the pipeline from.
+    async fn load_rolling_tree(&mut self, block: BlockNumber) -> anyhow::Result<RollingTree> {
+        let tree = self.load_account_tree(block).await?;
+        let mut storage = self.database.acquire_connection().await?;
+        let (_, accounts) = self
+            .database
+            .load_committed_state(&mut storage, Some(block))
+            .await?;
+        Ok(RollingTree {
+            block,
+            accounts,
+            tree,
+        })
+    }
+
+    /// Moves the rolling tree to the next block by applying the block state diff.
+    /// The resulting root hash is compared with `expected_root_hash` if it's known.
+    async fn advance_rolling_tree(
+        &self,
+        rolling_tree: &mut RollingTree,
+        expected_root_hash: Option<Fr>,
+    ) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let block = rolling_tree.block + 1;
+        let mut storage = self.database.acquire_connection().await?;
+        let account_updates = self
+            .database
+            .load_state_diff(&mut storage, rolling_tree.block, Some(block))
+            .await?
+            .map(|(_, account_updates)| account_updates)
+            .unwrap_or_default();
+
+        let mut updated_accounts = account_updates
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        updated_accounts.sort_unstable();
+        updated_accounts.dedup();
+        apply_updates(&mut rolling_tree.accounts, account_updates);
+        for id in updated_accounts {
+            rolling_tree.tree.insert(
+                *id,
+                rolling_tree
+                    .accounts
+                    .get(&id)
+                    .cloned()
+                    .unwrap_or_default()
+                    .into(),
+            );
+        }
+        let root_hash = rolling_tree.tree.root_hash();
+        if let Some(expected_root_hash) = expected_root_hash {
+            anyhow::ensure!(
+                root_hash == expected_root_hash,
+                "account tree root hash for the block {} restored incorrectly",
+                block
+            );
+        }
+        rolling_tree.block = block;
+        metrics::histogram!("witness_generator", start.elapsed(), "stage" => "advance_rolling_tree");
+        Ok(())
+    }
+
+    /// Schedules the witness computation for the block if it's needed and moves the rolling tree
+    /// past the block. Returns `false` if the block is not created yet.
+    async fn schedule_block(
+        &mut self,
+        block_number: BlockNumber,
+        rolling_tree: &mut Option<RollingTree>,
+        pending: &mut VecDeque<PendingWitness>,
+    ) -> anyhow::Result<bool> {
+        let block = match self.should_work_on_block(block_number).await? {
+            BlockInfo::NotReadyBlock => return Ok(false),
+            BlockInfo::WithWitness => None,
+            BlockInfo::NoWitness(block) => Some(block),
+        };
+        if block.is_none() && rolling_tree.is_none() {
+            // Nothing to compute and no tree to keep up to date.
+            return Ok(true);
+        }
+
+        let mut tree = match rolling_tree.take() {
+            Some(tree) if tree.block + 1 == block_number => tree,
+            _ => self.load_rolling_tree(block_number - 1).await?,
+        };
+        // The witness is built on the copy of the tree, while the rolling tree itself
+        // is moved to the next block without waiting for the witness.
+        let witness_tree = block.as_ref().map(|_| tree.tree.clone());
+        self.advance_rolling_tree(&mut tree, block.as_ref().map(|block| block.new_root_hash))
+            .await?;
+        *rolling_tree = Some(tree);
+
+        if let (Some(block), Some(mut witness_tree)) = (block, witness_tree) {
+            let witness = tokio::task::spawn_blocking(move || -> anyhow::Result<ProverData> {
+                let start = Instant::now();
+                let witness = build_block_witness(&mut witness_tree, &block)?.into();
+                metrics::histogram!("witness_generator", start.elapsed(), "stage" => "build_witness");
+                Ok(witness)
+            });
+            pending.push_back((block_number, witness));
+        }
+        Ok(true)
+    }
+
     /// Returns next block for generating witness
     fn next_witness_block(
         current_block: BlockNumber,
@@ -356,14 +540,305 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
             sleep(self.rounds_interval).await;
         }
     }
+
+    /// Updates witness data in database in an infinite loop, keeping the account tree in memory
+    /// and computing witnesses for up to `pipeline_depth` blocks concurrently.
+    /// Witnesses are stored strictly in the order of blocks.
+    async fn maintain_pipelined(mut self, pipeline_depth: usize) {
+        vlog::info!(
+            "pipelined preparing prover data routine started with start_block({}), pipeline_depth({})",
+            *self.start_block,
+            pipeline_depth
+        );
+
+        let mut pipeline = Pipeline::new(self.start_block);
+        sleep(self.start_wait).await;
+        loop {
+            if !self.pipeline_round(&mut pipeline, pipeline_depth).await {
+                sleep(self.rounds_interval).await;
+            }
+        }
+    }
+
+    /// Fills the pipeline with the created blocks and stores the witness of the first pending
+    /// block once it's computed. If the witness can't be prepared, the pipeline is restarted
+    /// from its block. Returns `true` if the witness was stored.
+    async fn pipeline_round(&mut self, pipeline: &mut Pipeline, pipeline_depth: usize) -> bool {
+        while pipeline.pending.len() < pipeline_depth {
+            match self
+                .schedule_block(
+                    pipeline.next_block,
+                    &mut pipeline.rolling_tree,
+                    &mut pipeline.pending,
+                )
+                .await
+            {
+                Ok(true) => pipeline.next_block = pipeline.next_block + 1,
+                Ok(false) => break,
+                Err(err) => {
+                    vlog::warn!(
+                        "Pipelined witness generator failed to schedule block: {}, err: {}",
+                        pipeline.next_block,
+                        err
+                    );
+                    break;
+                }
+            }
+        }
+        metrics::gauge!(
+            "witness_generator.pending_witnesses",
+            pipeline.pending.len() as f64
+        );
+
+        let (block_number, witness) = match pipeline.pending.pop_front() {
+            Some(pending_witness) => pending_witness,
+            None => return false,
+        };
+        let witness = witness.await.expect("Witness computation panicked");
+        let result = match witness {
+            Ok(witness) => self.save_witness(block_number, witness).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            vlog::warn!(
+                "Pipelined witness generator failed to prepare witness for block: {}, err: {}",
+                block_number,
+                err
+            );
+            // Start over from the failed block, so the witnesses are still stored in order.
+            pipeline.restart(block_number).await;
+            return false;
+        }
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::database::Database;
+    use crate::tests::mock::MockDatabase;
+    use chrono::Utc;
+    use num::BigUint;
     use zksync_crypto::Fr;
-    use zksync_types::{AccountId, H256, U256};
+    use zksync_state::state::ZkSyncState;
+    use zksync_types::{
+        AccountId, AccountUpdates, Deposit, ExecutedOperations, ExecutedPriorityOp, PriorityOp,
+        TokenId, ZkSyncPriorityOp, H256, U256,
+    };
+
+    /// Creates the blocks depositing funds to the fee account, along with their account updates.
+    fn gen_blocks(count: u32) -> Vec<(Block, AccountUpdates)> {
+        let (_, accounts) = MockDatabase::get_default_tree_and_accounts();
+        let fee_account_id = AccountId(0);
+        let fee_account_address = accounts[&fee_account_id].address;
+        let mut state = ZkSyncState::from_acc_map(accounts);
+
+        (1..=count)
+            .map(|block_number| {
+                let deposit = ZkSyncPriorityOp::Deposit(Deposit {
+                    from: fee_account_address,
+                    token: TokenId(0),
+                    amount: BigUint::from(10u32 * block_number),
+                    to: fee_account_address,
+                });
+                let op_success = state.execute_priority_op(deposit.clone());
+                let serial_id = u64::from(block_number) - 1;
+                let executed_op = ExecutedOperations::PriorityOp(Box::new(ExecutedPriorityOp {
+                    op: op_success.executed_op,
+                    priority_op: PriorityOp {
+                        serial_id,
+                        data: deposit,
+                        deadline_block: 0,
+                        eth_hash: H256::zero(),
+                        eth_block: 0,
+                        eth_block_index: None,
+                    },
+                    block_index: 0,
+                    created_at: Utc::now(),
+                }));
+                let block = Block::new_from_available_block_sizes(
+                    BlockNumber(block_number),
+                    state.root_hash(),
+                    fee_account_id,
+                    vec![executed_op],
+                    (serial_id, serial_id + 1),
+                    &[10],
+                    1_000_000.into(),
+                    1_500_000.into(),
+                    H256::default(),
+                    0,
+                );
+                (block, op_success.updates)
+            })
+            .collect()
+    }
+
+    async fn pipelined_generator(
+        blocks: Vec<(Block, AccountUpdates)>,
+        pipeline_depth: usize,
+    ) -> (WitnessGenerator<MockDatabase>, MockDatabase) {
+        let database = MockDatabase::new();
+        for (block, updates) in blocks {
+            database.add_block_with_updates(block, updates).await;
+        }
+        let generator = WitnessGenerator::new(
+            database.clone(),
+            time::Duration::from_millis(10),
+            time::Duration::default(),
+            BlockNumber(1),
+            BlockNumber(1),
+            Default::default(),
+            Some(pipeline_depth),
+        );
+        (generator, database)
+    }
+
+    async fn has_witness(database: &MockDatabase, block: u32) -> bool {
+        let mut storage = database.acquire_connection().await.unwrap();
+        database
+            .load_witness(&mut storage, BlockNumber(block))
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    /// Runs the pipeline rounds until there is nothing to store.
+    async fn run_pipeline(
+        generator: &mut WitnessGenerator<MockDatabase>,
+        pipeline: &mut Pipeline,
+        pipeline_depth: usize,
+    ) {
+        while generator.pipeline_round(pipeline, pipeline_depth).await {}
+    }
+
+    /// Checks that the rolling tree follows the blocks and is checked against their root hashes.
+    #[tokio::test]
+    async fn rolling_tree_root_hash_check() {
+        let blocks = gen_blocks(2);
+        let root_hashes: Vec<_> = blocks
+            .iter()
+            .map(|(block, _)| block.new_root_hash)
+            .collect();
+        let (mut generator, _) = pipelined_generator(blocks, 1).await;
+
+        let mut rolling_tree = generator.load_rolling_tree(BlockNumber(0)).await.unwrap();
+        generator
+            .advance_rolling_tree(&mut rolling_tree, Some(root_hashes[0]))
+            .await
+            .unwrap();
+        assert_eq!(rolling_tree.block, BlockNumber(1));
+        assert_eq!(rolling_tree.tree.root_hash(), root_hashes[0]);
+
+        // The tree of the second block doesn't match the root hash of the first one.
+        let result = generator
+            .advance_rolling_tree(&mut rolling_tree, Some(root_hashes[0]))
+            .await;
+        assert!(result.is_err());
+    }
+
+    /// Checks that the pipeline computes the witnesses for all the created blocks.
+    #[tokio::test]
+    async fn pipeline_computes_witnesses() {
+        let (mut generator, database) = pipelined_generator(gen_blocks(3), 2).await;
+        let mut pipeline = Pipeline::new(BlockNumber(1));
+
+        run_pipeline(&mut generator, &mut pipeline, 2).await;
+        for block in 1..=3 {
+            assert!(has_witness(&database, block).await);
+        }
+        assert_eq!(pipeline.next_block, BlockNumber(4));
+        assert_eq!(
+            pipeline.rolling_tree.map(|tree| tree.block),
+            Some(BlockNumber(3))
+        );
+    }
+
+    /// Checks that the witnesses are stored in the order of blocks, even if the witness
+    /// of the later block is computed first.
+    #[tokio::test]
+    async fn pipeline_stores_witnesses_in_order() {
+        let blocks = gen_blocks(2);
+        let mut tree = CircuitAccountTree::new(account_tree_depth());
+        let (_, accounts) = MockDatabase::get_default_tree_and_accounts();
+        for (id, account) in accounts {
+            tree.insert(*id, account.into());
+        }
+        let witnesses: Vec<ProverData> = blocks
+            .iter()
+            .map(|(block, _)| build_block_witness(&mut tree, block).unwrap().into())
+            .collect();
+        let (mut generator, database) = pipelined_generator(blocks, 2).await;
+
+        let mut pipeline = Pipeline::new(BlockNumber(3));
+        let mut witnesses = witnesses.into_iter();
+        let first_witness = witnesses.next().unwrap();
+        let second_witness = witnesses.next().unwrap();
+        pipeline.pending.push_back((
+            BlockNumber(1),
+            tokio::task::spawn_blocking(move || {
+                thread::sleep(time::Duration::from_millis(100));
+                Ok(first_witness)
+            }),
+        ));
+        pipeline.pending.push_back((
+            BlockNumber(2),
+            tokio::task::spawn_blocking(move || Ok(second_witness)),
+        ));
+
+        assert!(generator.pipeline_round(&mut pipeline, 2).await);
+        assert!(has_witness(&database, 1).await);
+        assert!(!has_witness(&database, 2).await);
+
+        assert!(generator.pipeline_round(&mut pipeline, 2).await);
+        assert!(has_witness(&database, 2).await);
+        // The third block is not created yet.
+        assert!(!generator.pipeline_round(&mut pipeline, 2).await);
+    }
+
+    /// Checks that the pipeline is started over from the block which witness has failed.
+    #[tokio::test]
+    async fn pipeline_restarts_from_failed_block() {
+        let (mut generator, database) = pipelined_generator(gen_blocks(3), 2).await;
+
+        let mut pipeline = Pipeline::new(BlockNumber(3));
+        pipeline.pending.push_back((
+            BlockNumber(1),
+            tokio::task::spawn_blocking(|| Err(anyhow::format_err!("witness failed"))),
+        ));
+        pipeline.pending.push_back((
+            BlockNumber(2),
+            tokio::task::spawn_blocking(|| Err(anyhow::format_err!("never stored"))),
+        ));
+
+        assert!(!generator.pipeline_round(&mut pipeline, 2).await);
+        assert!(!has_witness(&database, 1).await);
+        assert!(pipeline.pending.is_empty());
+        assert!(pipeline.rolling_tree.is_none());
+        assert_eq!(pipeline.next_block, BlockNumber(1));
+
+        run_pipeline(&mut generator, &mut pipeline, 2).await;
+        for block in 1..=3 {
+            assert!(has_witness(&database, block).await);
+        }
+    }
+
+    /// Checks that the block not matching the rolling tree is not scheduled.
+    #[tokio::test]
+    async fn pipeline_stops_at_root_hash_mismatch() {
+        let mut blocks = gen_blocks(3);
+        blocks[1].0.new_root_hash = Fr::default();
+        let (mut generator, database) = pipelined_generator(blocks, 2).await;
+        let mut pipeline = Pipeline::new(BlockNumber(1));
+
+        run_pipeline(&mut generator, &mut pipeline, 2).await;
+        // Retrying doesn't help, the state doesn't match the block.
+        assert!(!generator.pipeline_round(&mut pipeline, 2).await);
+        assert!(has_witness(&database, 1).await);
+        assert!(!has_witness(&database, 2).await);
+        assert!(!has_witness(&database, 3).await);
+        assert_eq!(pipeline.next_block, BlockNumber(2));
+    }
 
     #[test]
     fn test_next_witness_block() {
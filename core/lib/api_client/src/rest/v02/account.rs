@@ -1,6 +1,7 @@
 use crate::rest::client::{Client, Result};
 
 use zksync_api_types::v02::{
+    account::AccountsBatchRequest,
     pagination::{ApiEither, PaginationQuery},
     Response,
 };
@@ -29,6 +30,13 @@ impl Client {
         .await
     }
 
+    pub async fn accounts_batch(&self, accounts: Vec<String>) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "accounts/batch")
+            .body(&AccountsBatchRequest { accounts })
+            .send()
+            .await
+    }
+
     pub async fn account_txs(
         &self,
         pagination_query: &PaginationQuery<ApiEither<TxHash>>,
@@ -0,0 +1,32 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{stats::StatsQuery, Response};
+
+impl Client {
+    pub async fn tx_counts_stats(&self, query: &StatsQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/transactions")
+            .query(query)
+            .send()
+            .await
+    }
+
+    pub async fn accounts_stats(&self, query: &StatsQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/accounts")
+            .query(query)
+            .send()
+            .await
+    }
+
+    pub async fn fees_stats(&self, query: &StatsQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/fees")
+            .query(query)
+            .send()
+            .await
+    }
+
+    pub async fn blocks_stats(&self, query: &StatsQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/blocks")
+            .query(query)
+            .send()
+            .await
+    }
+}
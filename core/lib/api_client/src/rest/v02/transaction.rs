@@ -1,6 +1,9 @@
 use crate::rest::client::{Client, Result};
 use zksync_api_types::{
-    v02::{transaction::IncomingTxBatch, Response},
+    v02::{
+        transaction::{IncomingTxBatch, IncomingTxsSearchQuery},
+        Response,
+    },
     TxWithSignature,
 };
 use zksync_types::tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx};
@@ -28,6 +31,13 @@ impl Client {
             .await
     }
 
+    pub async fn search_txs(&self, query: &IncomingTxsSearchQuery) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "transactions")
+            .query(query)
+            .send()
+            .await
+    }
+
     pub async fn tx_status(&self, tx_hash: TxHash) -> Result<Response> {
         self.get_with_scope(super::API_V02_SCOPE, format!("transactions/{}", tx_hash))
             .send()
@@ -11,6 +11,9 @@ use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 use super::pagination::PaginationDirection;
 use super::token::NFT;
 
+/// Maximum number of accounts that can be requested in a single `accounts/batch` request.
+pub const MAX_ACCOUNTS_BATCH_SIZE: usize = 1000;
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountState {
@@ -115,3 +118,22 @@ pub struct IncomingAccountTxsQuery {
     pub token: Option<String>,
     pub second_account: Option<String>,
 }
+
+/// Request of the `accounts/batch` endpoint.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsBatchRequest {
+    /// Account ids or addresses.
+    pub accounts: Vec<String>,
+}
+
+/// State of the single account in the `accounts/batch` response.
+/// Items of the response have the same order as the requested accounts.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBatchItem {
+    /// Account id or address as it was passed in the request.
+    pub requested: String,
+    pub committed: Option<Account>,
+    pub finalized: Option<Account>,
+}
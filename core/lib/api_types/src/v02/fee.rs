@@ -71,6 +71,21 @@ impl From<ApiTxFeeTypes> for TxFeeTypes {
     }
 }
 
+impl From<TxFeeTypes> for ApiTxFeeTypes {
+    fn from(fee_type: TxFeeTypes) -> ApiTxFeeTypes {
+        match fee_type {
+            TxFeeTypes::Withdraw => ApiTxFeeTypes::Withdraw,
+            TxFeeTypes::FastWithdraw => ApiTxFeeTypes::FastWithdraw,
+            TxFeeTypes::Transfer => ApiTxFeeTypes::Transfer,
+            TxFeeTypes::ChangePubKey(cpk_arg) => ApiTxFeeTypes::ChangePubKey(cpk_arg),
+            TxFeeTypes::MintNFT => ApiTxFeeTypes::MintNFT,
+            TxFeeTypes::WithdrawNFT => ApiTxFeeTypes::WithdrawNFT,
+            TxFeeTypes::FastWithdrawNFT => ApiTxFeeTypes::FastWithdrawNFT,
+            TxFeeTypes::Swap => ApiTxFeeTypes::Swap,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TxFeeRequest {
@@ -8,6 +8,7 @@ pub mod account;
 pub mod block;
 pub mod fee;
 pub mod pagination;
+pub mod stats;
 pub mod status;
 pub mod token;
 pub mod transaction;
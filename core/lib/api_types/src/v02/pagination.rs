@@ -139,3 +139,15 @@ pub struct AccountTxsRequest {
     pub token: Option<TokenId>,
     pub second_address: Option<Address>,
 }
+
+/// Filters of the transactions search, all of them should be satisfied.
+#[derive(Debug, Serialize)]
+pub struct TxsSearchRequest {
+    pub tx_hash: ApiEither<TxHash>,
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    pub token: Option<TokenId>,
+    pub tx_type: Option<String>,
+    pub from_block: Option<BlockNumber>,
+    pub to_block: Option<BlockNumber>,
+}
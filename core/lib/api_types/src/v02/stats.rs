@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use zksync_utils::BigUintSerdeWrapper;
+
+/// Maximum number of points in a single statistics series.
+pub const MAX_STATS_POINTS: i64 = 1000;
+/// Number of points in a series if the `from` parameter is omitted.
+pub const DEFAULT_STATS_POINTS: i64 = 30;
+
+/// Length of the periods in a statistics series.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum StatsInterval {
+    Hour,
+    Day,
+}
+
+impl StatsInterval {
+    /// Name of the interval as expected by the `date_trunc` SQL function.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatsInterval::Hour => "hour",
+            StatsInterval::Day => "day",
+        }
+    }
+
+    pub fn duration(&self) -> chrono::Duration {
+        match self {
+            StatsInterval::Hour => chrono::Duration::hours(1),
+            StatsInterval::Day => chrono::Duration::days(1),
+        }
+    }
+}
+
+/// Query of the statistics endpoints. Periods are included into the series if they
+/// start within the `[from, to)` range, where `from` is rounded down to the start of its
+/// period and `to` defaults to the current time.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsQuery {
+    pub interval: StatsInterval,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Number of the executed transactions (including priority operations) by their types.
+/// Failed transactions are not taken into account.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxCountsPoint {
+    pub period: DateTime<Utc>,
+    pub total: u64,
+    pub by_type: BTreeMap<String, u64>,
+}
+
+/// Number of accounts that initiated at least one transaction and
+/// number of accounts created during the period.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsPoint {
+    pub period: DateTime<Utc>,
+    pub active: u64,
+    pub new: u64,
+}
+
+/// Fees collected during the period by token symbols.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeesPoint {
+    pub period: DateTime<Utc>,
+    pub fees: BTreeMap<String, BigUintSerdeWrapper>,
+}
+
+/// Number of blocks and their average fill, i.e. the ratio of the used chunks
+/// to the total capacity of the blocks.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlocksPoint {
+    pub period: DateTime<Utc>,
+    pub blocks: u64,
+    pub average_fill: f64,
+}
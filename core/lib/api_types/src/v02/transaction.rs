@@ -1,4 +1,7 @@
-use crate::{v02::block::BlockStatus, TxWithSignature};
+use crate::{
+    v02::{block::BlockStatus, pagination::PaginationDirection},
+    TxWithSignature,
+};
 use chrono::serde::ts_milliseconds;
 use chrono::{DateTime, Utc};
 use num::BigUint;
@@ -20,6 +23,28 @@ pub struct IncomingTxBatch {
     pub signature: Option<EthBatchSignatures>,
 }
 
+/// Query of the transactions search endpoint.
+///
+/// `from` and `to` accept both account ids and addresses, `token` accepts token id, symbol
+/// or address. Unlike other paginated endpoints, the starting transaction hash is passed in
+/// the `cursor` parameter since `from` is used as a filter.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingTxsSearchQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub token: Option<String>,
+    #[serde(rename = "type")]
+    pub tx_type: Option<String>,
+    #[serde(alias = "from_block")]
+    pub from_block: Option<BlockNumber>,
+    #[serde(alias = "to_block")]
+    pub to_block: Option<BlockNumber>,
+    pub cursor: Option<String>,
+    pub limit: u32,
+    pub direction: PaginationDirection,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum TxInBlockStatus {
@@ -1,12 +1,17 @@
 // Built-in
+use std::io::{Read, Write};
 // External
+use anyhow::{ensure, format_err};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 // Workspace
 use zksync_crypto::ff::PrimeField;
 use zksync_crypto::franklin_crypto::alt_babyjubjub::AltJubjubBn256;
+use zksync_crypto::franklin_crypto::bellman::Circuit;
+use zksync_crypto::franklin_crypto::circuit::test::TestConstraintSystem;
 use zksync_crypto::franklin_crypto::rescue::bn256::Bn256RescueParams;
 use zksync_crypto::serialization::*;
 use zksync_crypto::{Engine, Fr};
+use zksync_types::block::Block;
 // Local
 use crate::account::AccountWitness;
 use crate::circuit::ZkSyncCircuit;
@@ -96,6 +101,67 @@ impl ProverData {
     }
 }
 
+/// Version of the `BlockWitnessExport` format.
+/// Must be increased on every incompatible change of the exported data, e.g. of `ProverData`.
+pub const WITNESS_EXPORT_VERSION: u32 = 1;
+
+/// Block witness along with the block it was generated for. Exported from the database,
+/// it allows to reproduce the circuit issues without access to the database.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlockWitnessExport {
+    pub version: u32,
+    pub block: Block,
+    pub witness: ProverData,
+}
+
+impl BlockWitnessExport {
+    pub fn new(block: Block, witness: ProverData) -> Self {
+        Self {
+            version: WITNESS_EXPORT_VERSION,
+            block,
+            witness,
+        }
+    }
+
+    /// Reads the JSON export, failing if it was made with an incompatible format version.
+    pub fn read<R: Read>(reader: R) -> anyhow::Result<Self> {
+        let export: serde_json::Value = serde_json::from_reader(reader)?;
+        let version = export
+            .get("version")
+            .and_then(|version| version.as_u64())
+            .ok_or_else(|| format_err!("Witness export doesn't have the format version"))?;
+        ensure!(
+            version == u64::from(WITNESS_EXPORT_VERSION),
+            "Unsupported witness export version {}, expected {}",
+            version,
+            WITNESS_EXPORT_VERSION
+        );
+        Ok(serde_json::from_value(export)?)
+    }
+
+    /// Writes the export as JSON.
+    pub fn write<W: Write>(&self, writer: W) -> anyhow::Result<()> {
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Synthesizes the block circuit from the witness and returns
+    /// the first unsatisfied constraint as an error if there is one.
+    pub fn check_constraints(&self) -> Result<(), String> {
+        let mut cs = TestConstraintSystem::<Engine>::new();
+        self.witness
+            .clone()
+            .into_circuit()
+            .synthesize(&mut cs)
+            .map_err(|e| format!("Failed to synthesize the circuit: {}", e))?;
+
+        match cs.which_is_unsatisfied() {
+            Some(constraint) => Err(constraint.to_string()),
+            None => Ok(()),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(remote = "crate::account::AccountWitness::<Engine>")]
 struct AccountWitnessDef {
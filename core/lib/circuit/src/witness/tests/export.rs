@@ -0,0 +1,105 @@
+// External deps
+use num::BigUint;
+// Workspace deps
+use zksync_types::{
+    block::Block, operations::DepositOp, AccountId, BlockNumber, Deposit, TokenId, H256, U256,
+};
+// Local deps
+use crate::{
+    serialization::{BlockWitnessExport, WITNESS_EXPORT_VERSION},
+    witness::{
+        tests::test_utils::{
+            WitnessTestAccount, ZkSyncStateGenerator, BLOCK_TIMESTAMP, FEE_ACCOUNT_ID,
+        },
+        utils::WitnessBuilder,
+        DepositWitness, Witness,
+    },
+};
+
+const BLOCK_SIZE: usize = 10;
+
+/// Creates the export of a block with a single deposit.
+fn deposit_block_export() -> BlockWitnessExport {
+    let account = WitnessTestAccount::new_empty(AccountId(1));
+    let deposit_op = DepositOp {
+        priority_op: Deposit {
+            from: account.account.address,
+            token: TokenId(0),
+            amount: BigUint::from(10u32),
+            to: account.account.address,
+        },
+        account_id: account.id,
+    };
+
+    let (_, mut circuit_account_tree) = ZkSyncStateGenerator::generate(&[account]);
+    let mut witness_accum = WitnessBuilder::new(
+        &mut circuit_account_tree,
+        FEE_ACCOUNT_ID,
+        BlockNumber(1),
+        BLOCK_TIMESTAMP,
+    );
+    let deposit_witness = DepositWitness::apply_tx(witness_accum.account_tree, &deposit_op);
+    let deposit_operations = deposit_witness.calculate_operations(());
+    let pub_data_from_witness = deposit_witness.get_pubdata();
+    let offset_commitment = deposit_witness.get_offset_commitment_data();
+    witness_accum.add_operation_with_pubdata(
+        deposit_operations,
+        pub_data_from_witness,
+        offset_commitment,
+    );
+    witness_accum.extend_pubdata_with_noops(BLOCK_SIZE);
+    witness_accum.collect_fees(&[]);
+    witness_accum.calculate_pubdata_commitment();
+
+    let block = Block::new(
+        BlockNumber(1),
+        witness_accum.root_after_fees.unwrap(),
+        FEE_ACCOUNT_ID,
+        vec![],
+        (0, 1),
+        BLOCK_SIZE,
+        U256::default(),
+        U256::default(),
+        H256::default(),
+        BLOCK_TIMESTAMP,
+    );
+    BlockWitnessExport::new(block, witness_accum.into())
+}
+
+/// Checks that the exported witness can be imported back and fed to the circuit.
+#[test]
+#[ignore]
+fn witness_export_round_trip() {
+    let export = deposit_block_export();
+    let mut serialized = Vec::new();
+    export
+        .write(&mut serialized)
+        .expect("Failed to write export");
+
+    let imported = BlockWitnessExport::read(serialized.as_slice()).expect("Failed to read export");
+    assert_eq!(imported.block.block_number, BlockNumber(1));
+    assert_eq!(imported.block.block_chunks_size, BLOCK_SIZE);
+    assert_eq!(
+        imported.witness.public_data_commitment,
+        export.witness.public_data_commitment
+    );
+    imported
+        .check_constraints()
+        .expect("Imported witness doesn't satisfy the constraints");
+}
+
+/// Checks that the export made with another format version is rejected.
+#[test]
+fn witness_export_version_mismatch() {
+    let mut export = deposit_block_export();
+    export.version = WITNESS_EXPORT_VERSION + 1;
+    let mut serialized = Vec::new();
+    export
+        .write(&mut serialized)
+        .expect("Failed to write export");
+
+    let err = BlockWitnessExport::read(serialized.as_slice()).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Unsupported witness export version"));
+}
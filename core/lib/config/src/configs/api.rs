@@ -233,7 +233,12 @@ pub struct Web3Config {
     pub port: u16,
     /// URL to access web3 JSON RPC server.
     pub url: String,
-    /// Max difference between blocks in `eth_getLogs` method.
+    /// Port to which the web3 WebSocket RPC server is listening.
+    pub ws_port: u16,
+    /// URL to access web3 WebSocket RPC server.
+    pub ws_url: String,
+    /// Max difference between blocks in `eth_getLogs` method. Also the max number of blocks
+    /// covered by a single `eth_getFilterChanges` poll.
     pub max_block_range: u32,
     pub chain_id: u64,
 }
@@ -242,6 +247,10 @@ impl Web3Config {
     pub fn bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
     }
+
+    pub fn ws_bind_addr(&self) -> SocketAddr {
+        SocketAddr::new("0.0.0.0".parse().unwrap(), self.ws_port)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -288,6 +297,8 @@ mod tests {
             web3: Web3Config {
                 port: 3002,
                 url: "http://127.0.0.1:3002".into(),
+                ws_port: 3003,
+                ws_url: "ws://127.0.0.1:3003".into(),
                 max_block_range: 10,
                 chain_id: 240,
             },
@@ -331,6 +342,8 @@ API_JSON_RPC_WS_PORT="3031"
 API_JSON_RPC_WS_URL="ws://127.0.0.1:3031"
 API_WEB3_PORT="3002"
 API_WEB3_URL="http://127.0.0.1:3002"
+API_WEB3_WS_PORT="3003"
+API_WEB3_WS_URL="ws://127.0.0.1:3003"
 API_WEB3_CHAIN_ID="240"
 API_WEB3_MAX_BLOCK_RANGE="10"
 API_PRIVATE_PORT="8090"
@@ -7,6 +7,21 @@ use serde::Deserialize;
 // Local uses
 use crate::envy_load;
 
+/// Storage of the large blobs, such as the block witnesses and proofs.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum BlobStorageBackend {
+    /// The blobs are kept in the database.
+    Database,
+    /// The blobs are kept in the local directory set by `blob_storage_path`.
+    Local,
+}
+
+impl Default for BlobStorageBackend {
+    fn default() -> Self {
+        Self::Database
+    }
+}
+
 /// Used database configuration.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct DBConfig {
@@ -18,6 +33,11 @@ pub struct DBConfig {
     pub rejected_transactions_max_age: u64,
     /// Sleep time (in hours) of the actor responsible for deleting failed transactions from the database.
     pub rejected_transactions_cleaner_interval: u64,
+    /// Storage of the block witnesses, proofs and prover job data.
+    #[serde(default)]
+    pub blob_storage_backend: BlobStorageBackend,
+    /// Directory of the `Local` blob storage.
+    pub blob_storage_path: Option<String>,
 }
 
 impl DBConfig {
@@ -47,6 +67,8 @@ mod tests {
             url: "postgres://postgres@localhost/plasma".into(),
             rejected_transactions_max_age: 336,
             rejected_transactions_cleaner_interval: 24,
+            blob_storage_backend: BlobStorageBackend::Local,
+            blob_storage_path: Some("/var/lib/zksync/blobs".into()),
         }
     }
 
@@ -57,6 +79,8 @@ DATABASE_POOL_SIZE="10"
 DATABASE_URL="postgres://postgres@localhost/plasma"
 DATABASE_REJECTED_TRANSACTIONS_MAX_AGE="336"
 DATABASE_REJECTED_TRANSACTIONS_CLEANER_INTERVAL="24"
+DATABASE_BLOB_STORAGE_BACKEND="Local"
+DATABASE_BLOB_STORAGE_PATH="/var/lib/zksync/blobs"
         "#;
         set_env(config);
 
@@ -56,10 +56,17 @@ impl Prover {
 /// Core settings related to the prover applications interacting with it.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Core {
-    /// Timeout to consider prover gone in ms.
+    /// Timeout to consider prover gone in ms. The job is leased to the prover for this time,
+    /// and is given to another prover unless the lease is extended by a heartbeat.
     pub gone_timeout: u64,
     /// Amount of provers in the cluser if there is no pending jobs.
     pub idle_provers: u32,
+    /// Whether to verify the received proofs before storing them.
+    pub verify_proofs: bool,
+    /// Time during which the prover that sent an invalid proof doesn't receive new jobs in ms.
+    pub invalid_proof_penalty: u64,
+    /// Delay before retrying the failed job in ms, doubled with every subsequent failure of the job.
+    pub failed_job_retry_backoff: u64,
 }
 
 impl Core {
@@ -67,6 +74,16 @@ impl Core {
     pub fn gone_timeout(&self) -> Duration {
         Duration::from_millis(self.gone_timeout)
     }
+
+    /// Converts `self.invalid_proof_penalty` into `Duration`.
+    pub fn invalid_proof_penalty(&self) -> Duration {
+        Duration::from_millis(self.invalid_proof_penalty)
+    }
+
+    /// Converts `self.failed_job_retry_backoff` into `Duration`.
+    pub fn failed_job_retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.failed_job_retry_backoff)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -75,6 +92,11 @@ pub struct WitnessGenerator {
     pub prepare_data_interval: u64,
     /// Amount of witness generator threads.
     pub witness_generators: usize,
+    /// Whether to run a single witness generator that keeps the account tree in memory and
+    /// computes witnesses for `witness_generators` blocks concurrently. Every witness being
+    /// computed needs its own copy of the account tree, so the memory usage grows linearly
+    /// with `witness_generators`.
+    pub pipelined: bool,
 }
 
 impl WitnessGenerator {
@@ -100,10 +122,14 @@ mod tests {
             core: Core {
                 gone_timeout: 60000,
                 idle_provers: 1,
+                verify_proofs: true,
+                invalid_proof_penalty: 600000,
+                failed_job_retry_backoff: 10000,
             },
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 2,
+                pipelined: false,
             },
         }
     }
@@ -117,8 +143,12 @@ PROVER_PROVER_REQUEST_TIMEOUT="10"
 PROVER_PROVER_DIE_AFTER_PROOF=false
 PROVER_CORE_GONE_TIMEOUT="60000"
 PROVER_CORE_IDLE_PROVERS="1"
+PROVER_CORE_VERIFY_PROOFS="true"
+PROVER_CORE_INVALID_PROOF_PENALTY="600000"
+PROVER_CORE_FAILED_JOB_RETRY_BACKOFF="10000"
 PROVER_WITNESS_GENERATOR_PREPARE_DATA_INTERVAL="500"
 PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
+PROVER_WITNESS_GENERATOR_PIPELINED="false"
         "#;
         set_env(config);
 
@@ -148,6 +178,14 @@ PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
             config.core.gone_timeout(),
             Duration::from_millis(config.core.gone_timeout)
         );
+        assert_eq!(
+            config.core.invalid_proof_penalty(),
+            Duration::from_millis(config.core.invalid_proof_penalty)
+        );
+        assert_eq!(
+            config.core.failed_job_retry_backoff(),
+            Duration::from_millis(config.core.failed_job_retry_backoff)
+        );
 
         assert_eq!(
             config.witness_generator.prepare_data_interval(),
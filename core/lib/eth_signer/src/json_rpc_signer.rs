@@ -325,7 +325,7 @@ impl JsonRpcSigner {
     }
 }
 
-mod messages {
+pub(crate) mod messages {
     use crate::RawTransaction;
     use hex::encode;
     use zksync_types::eip712_signature::{
@@ -0,0 +1,395 @@
+use crate::error::{RpcSignerError, SignerError};
+use crate::json_rpc_signer::{is_signature_from_address, messages::JsonRpcRequest};
+use crate::EthereumSigner;
+use crate::RawTransaction;
+
+use jsonrpc_core::types::response::Output;
+use serde_json::Value;
+use web3::signing::keccak256;
+use zksync_types::eip712_signature::{EIP712TypedStructure, Eip712Domain};
+use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::Address;
+
+/// Signer delegating the signing to an external service which holds the keys,
+/// e.g. Web3Signer or a compatible custodial API.
+///
+/// The service is expected to provide the following endpoints:
+/// - `GET /api/v1/eth1/publicKeys` returning the list of the available public keys;
+/// - `POST /api/v1/eth1/sign/{publicKey}` signing the `data` with the Ethereum message prefix;
+/// - JSON RPC `eth_signTypedData` and `eth_signTransaction` methods at the root URL.
+///
+/// Every signature received from the service is checked against the signer address.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    url: String,
+    client: reqwest::Client,
+    address: Address,
+    public_key: String,
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    /// Requests the signature of the message with the prefix `\x19Ethereum Signed Message:\n`
+    /// from the remote service.
+    async fn sign_message(&self, msg: &[u8]) -> Result<TxEthSignature, SignerError> {
+        let url = format!("{}/api/v1/eth1/sign/{}", self.url, self.public_key);
+        let body = serde_json::json!({ "data": format!("0x{}", hex::encode(msg)) });
+        let response = Self::send_request(self.client.post(&url).json(&body))
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        // The signature can be returned either as a plain text or as a JSON string.
+        let signature = response.trim().trim_matches('"').to_owned();
+        let signature: PackedEthSignature = serde_json::from_value(Value::String(signature))
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        if is_signature_from_address(&signature, msg, self.address)? {
+            Ok(TxEthSignature::EthereumSignature(signature))
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ))
+        }
+    }
+
+    /// Requests the signature of the typed struct according to the EIP-712 standard
+    /// from the remote service.
+    async fn sign_typed_data<S>(
+        &self,
+        eip712_domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError>
+    where
+        S: EIP712TypedStructure + Sync,
+    {
+        let mut message =
+            JsonRpcRequest::sign_typed_data(self.address, eip712_domain, typed_struct);
+        // Web3Signer only provides the unversioned method.
+        message.method = "eth_signTypedData".to_owned();
+        let ret = self
+            .rpc(&message)
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        let signature: PackedEthSignature = serde_json::from_value(ret)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        let signed_bytes =
+            PackedEthSignature::typed_data_to_signed_bytes(eip712_domain, typed_struct);
+        let recovered_address = signature
+            .signature_recover_signer_from_hash(signed_bytes)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        if self.address == recovered_address {
+            Ok(signature)
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_string(),
+            ))
+        }
+    }
+
+    /// Requests the signature of the transaction from the remote service and returns
+    /// the RLP-encoded signed transaction. The chain id is defined by the service configuration.
+    async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
+        let message = JsonRpcRequest::sign_transaction(self.address, raw_tx);
+        let ret = self
+            .rpc(&message)
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        // Web3Signer returns the encoded transaction itself, while the nodes wrap it into an object.
+        let raw_tx = match &ret {
+            Value::String(raw_tx) => Some(raw_tx.as_str()),
+            value => value.get("raw").and_then(|value| value.as_str()),
+        };
+
+        match raw_tx {
+            Some(raw_tx) => hex::decode(raw_tx.trim_start_matches("0x"))
+                .map_err(|err| SignerError::DecodeRawTxFailed(err.to_string())),
+            None => Err(SignerError::DecodeRawTxFailed(format!(
+                "Unexpected response: {}",
+                ret
+            ))),
+        }
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+impl RemoteSigner {
+    /// Creates a signer for the given address, looking up its public key
+    /// among the keys available in the remote service.
+    pub async fn new(url: impl Into<String>, address: Address) -> Result<Self, SignerError> {
+        Self::with_client(url, address, reqwest::Client::new()).await
+    }
+
+    /// Same as `RemoteSigner::new`, but uses the provided HTTP client, e.g. the one
+    /// configured with the authorization headers or TLS certificates required by the service.
+    pub async fn with_client(
+        url: impl Into<String>,
+        address: Address,
+        client: reqwest::Client,
+    ) -> Result<Self, SignerError> {
+        let url = url.into().trim_end_matches('/').to_owned();
+
+        let keys_url = format!("{}/api/v1/eth1/publicKeys", url);
+        let response = Self::send_request(client.get(&keys_url))
+            .await
+            .map_err(|err| SignerError::CustomError(err.to_string()))?;
+        let public_keys: Vec<String> = serde_json::from_str(&response)
+            .map_err(|err| SignerError::CustomError(err.to_string()))?;
+
+        let public_key = public_keys
+            .into_iter()
+            .find(|public_key| public_key_to_address(public_key) == Some(address))
+            .ok_or(SignerError::DefineAddress)?;
+
+        Ok(Self {
+            url,
+            client,
+            address,
+            public_key,
+        })
+    }
+
+    /// Get Ethereum address.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Performs a JSON RPC call to the root URL of the service and returns the call result.
+    async fn rpc(&self, message: impl serde::Serialize) -> Result<Value, RpcSignerError> {
+        let response = Self::send_request(self.client.post(&self.url).json(&message)).await?;
+        let reply: Output = serde_json::from_str(&response)
+            .map_err(|err| RpcSignerError::MalformedResponse(err.to_string()))?;
+
+        match reply {
+            Output::Success(success) => Ok(success.result),
+            Output::Failure(failure) => Err(RpcSignerError::RpcError(failure)),
+        }
+    }
+
+    /// Sends the request and returns the response body, any non-OK response is treated as an error.
+    async fn send_request(request: reqwest::RequestBuilder) -> Result<String, RpcSignerError> {
+        let res = request
+            .send()
+            .await
+            .map_err(|err| RpcSignerError::NetworkError(err.to_string()))?;
+        if res.status() != reqwest::StatusCode::OK {
+            let error = format!("Query responded with a non-OK response: {}", res.status());
+            return Err(RpcSignerError::NetworkError(error));
+        }
+
+        res.text()
+            .await
+            .map_err(|err| RpcSignerError::MalformedResponse(err.to_string()))
+    }
+}
+
+/// Converts the hex-encoded uncompressed public key to the Ethereum address.
+/// Both the raw 64-byte key and the key with the `0x04` prefix are accepted.
+fn public_key_to_address(public_key: &str) -> Option<Address> {
+    let bytes = hex::decode(public_key.trim_start_matches("0x")).ok()?;
+    let key = match bytes.len() {
+        64 => &bytes[..],
+        65 if bytes[0] == 0x04 => &bytes[1..],
+        _ => return None,
+    };
+    Some(Address::from_slice(&keccak256(key)[12..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{get, post, web, App, HttpResponse, HttpServer, Responder};
+    use futures::future::{AbortHandle, Abortable};
+    use jsonrpc_core::{Id, Output, Success, Version};
+    use secp256k1::{PublicKey, Secp256k1, SecretKey};
+    use serde_json::json;
+
+    use zksync_types::{
+        eip712_signature::{utils::get_eip712_json, Eip712Domain},
+        tx::{ChangePubKey, PackedEthSignature, TxEthSignature},
+        AccountId, Address, ChainId, Nonce, PubKeyHash, H256, U256,
+    };
+
+    use super::{is_signature_from_address, JsonRpcRequest, RemoteSigner};
+    use crate::{EthereumSigner, PrivateKeySigner, RawTransaction};
+
+    const CHAIN_ID: u64 = 270;
+
+    /// Transaction fields sent by the `eth_signTransaction` request.
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct TxParams {
+        to: Option<Address>,
+        gas: U256,
+        gas_price: U256,
+        value: U256,
+        data: String,
+        nonce: U256,
+    }
+
+    #[derive(Clone)]
+    struct State {
+        private_key: H256,
+        domain: Eip712Domain,
+        change_pub_key: ChangePubKey,
+    }
+
+    #[get("/api/v1/eth1/publicKeys")]
+    async fn public_keys(state: web::Data<State>) -> impl Responder {
+        let secret_key = SecretKey::from_slice(state.private_key.as_bytes()).unwrap();
+        let public_key = PublicKey::from_secret_key(&Secp256k1::new(), &secret_key);
+        let public_key = format!("0x{}", hex::encode(public_key.serialize_uncompressed()));
+        HttpResponse::Ok().json(json!([public_key]))
+    }
+
+    #[post("/api/v1/eth1/sign/{public_key}")]
+    async fn sign(req: web::Json<serde_json::Value>, state: web::Data<State>) -> impl Responder {
+        let data = req["data"].as_str().unwrap();
+        let data_bytes = hex::decode(&data[2..]).unwrap();
+        let signature = PackedEthSignature::sign(&state.private_key, &data_bytes).unwrap();
+        HttpResponse::Ok().body(format!("0x{}", hex::encode(signature.serialize_packed())))
+    }
+
+    #[post("/")]
+    async fn rpc(req: web::Json<JsonRpcRequest>, state: web::Data<State>) -> impl Responder {
+        let signer = PrivateKeySigner::new(state.private_key);
+        let result = match req.method.as_str() {
+            "eth_signTypedData" => {
+                assert_eq!(
+                    req.params[1],
+                    get_eip712_json(&state.domain, &state.change_pub_key)
+                );
+                let signature = signer
+                    .sign_typed_data(&state.domain, &state.change_pub_key)
+                    .await
+                    .unwrap();
+                json!(signature)
+            }
+            "eth_signTransaction" => {
+                let tx: TxParams = serde_json::from_value(req.params[0].clone()).unwrap();
+                let raw_tx = signer
+                    .sign_transaction(RawTransaction {
+                        nonce: tx.nonce,
+                        to: tx.to,
+                        gas: tx.gas,
+                        gas_price: tx.gas_price,
+                        value: tx.value,
+                        data: hex::decode(&tx.data[2..]).unwrap(),
+                        chain_id: CHAIN_ID,
+                        ..Default::default()
+                    })
+                    .await
+                    .unwrap();
+                json!(format!("0x{}", hex::encode(raw_tx)))
+            }
+            method => return HttpResponse::BadRequest().body(method.to_owned()),
+        };
+        HttpResponse::Ok().json(Output::Success(Success {
+            jsonrpc: Some(Version::V2),
+            result,
+            id: Id::Num(1),
+        }))
+    }
+
+    fn run_server(state: State) -> (String, AbortHandle) {
+        let mut url = None;
+        let mut server = None;
+        for i in 9000..9999 {
+            let new_url = format!("127.0.0.1:{}", i);
+            // Try to bind to some port, hope that 999 variants will be enough
+            let tmp_state = state.clone();
+            if let Ok(ser) = HttpServer::new(move || {
+                App::new()
+                    .app_data(web::Data::new(tmp_state.clone()))
+                    .service(public_keys)
+                    .service(sign)
+                    .service(rpc)
+            })
+            .bind(new_url.clone())
+            {
+                server = Some(ser);
+                url = Some(new_url);
+                break;
+            }
+        }
+
+        let server = server.expect("Could not bind to port from 9000 to 9999");
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let future = Abortable::new(server.run(), abort_registration);
+        tokio::spawn(future);
+        let address = format!("http://{}/", &url.unwrap());
+        (address, abort_handle)
+    }
+
+    #[actix_rt::test]
+    async fn remote_signer() {
+        let private_key = H256::from([5; 32]);
+        let chain_id = ChainId(CHAIN_ID);
+        let change_pub_key = ChangePubKey::new(
+            AccountId(0xdeadba),
+            Default::default(),
+            PubKeyHash::from_hex("sync:fefefefefefefefefefefefefefefefefefefefe").unwrap(),
+            Default::default(),
+            Default::default(),
+            Nonce(0x11223344),
+            Default::default(),
+            None,
+            None,
+            Some(chain_id),
+        );
+        let state = State {
+            private_key,
+            domain: Eip712Domain::new(chain_id),
+            change_pub_key,
+        };
+        let (url, abort_handle) = run_server(state.clone());
+
+        let local_signer = PrivateKeySigner::new(private_key);
+        let address = local_signer.get_address().await.unwrap();
+
+        // Signer can't be created for the address unknown to the service.
+        assert!(RemoteSigner::new(url.clone(), Address::repeat_byte(1))
+            .await
+            .is_err());
+        let signer = RemoteSigner::new(url, address).await.unwrap();
+        assert_eq!(signer.get_address().await.unwrap(), address);
+
+        let msg = b"some_text_message";
+        if let TxEthSignature::EthereumSignature(signature) =
+            signer.sign_message(msg).await.unwrap()
+        {
+            assert!(is_signature_from_address(&signature, msg, address).unwrap())
+        } else {
+            panic!("Wrong signature type")
+        }
+
+        let signature = signer
+            .sign_typed_data(&state.domain, &state.change_pub_key)
+            .await
+            .unwrap();
+        let expected_signature = local_signer
+            .sign_typed_data(&state.domain, &state.change_pub_key)
+            .await
+            .unwrap();
+        assert_eq!(signature, expected_signature);
+
+        let raw_tx = RawTransaction {
+            nonce: U256::from(1u32),
+            to: Some(Address::repeat_byte(2)),
+            gas: U256::from(21000u32),
+            gas_price: U256::from(2u32),
+            value: U256::from(100u32),
+            data: vec![1, 2, 3],
+            chain_id: CHAIN_ID,
+            ..Default::default()
+        };
+        let signed_tx = signer.sign_transaction(raw_tx.clone()).await.unwrap();
+        let expected_signed_tx = local_signer.sign_transaction(raw_tx).await.unwrap();
+        assert_eq!(signed_tx, expected_signed_tx);
+
+        abort_handle.abort();
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use zksync_circuit::serialization::ProverData;
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
@@ -21,6 +22,10 @@ pub struct ProverInputResponse {
     pub first_block: BlockNumber,
     pub last_block: BlockNumber,
     pub data: Option<JobRequestData>,
+    /// Time until which the job belongs to the prover, extended by every heartbeat.
+    /// Absent if there is no job or for the servers of older versions.
+    #[serde(default)]
+    pub lease_expires_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -35,6 +40,9 @@ pub enum JobRequestData {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProverOutputRequest {
+    /// Name of the prover that created the proof, empty for the provers of older versions.
+    #[serde(default)]
+    pub prover_name: String,
     pub job_id: i32,
     pub first_block: BlockNumber,
     pub last_block: BlockNumber,
@@ -58,3 +66,11 @@ pub struct WorkingOn {
 pub struct ProverStopped {
     pub prover_name: String,
 }
+
+/// Report of the prover about the job it failed to prove.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct JobFailure {
+    pub prover_name: String,
+    pub job_id: i32,
+    pub error: String,
+}
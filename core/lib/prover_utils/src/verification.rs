@@ -0,0 +1,296 @@
+//! Verification of the proofs received from the provers.
+//!
+//! The checks repeat the ones performed by the `Verifier` contract, so a faulty proof can be
+//! detected before it is sent to Ethereum in the `ProveBlocks` transaction.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::time::Instant;
+
+use anyhow::{ensure, format_err};
+use parity_crypto::digest::sha256;
+use zksync_crypto::bellman::plonk::better_better_cs::{
+    setup::VerificationKey as VkAggregate, verifier::verify as verify_aggregated,
+};
+use zksync_crypto::bellman::plonk::better_cs::verifier::verify as verify_single;
+use zksync_crypto::convert::FeConvert;
+use zksync_crypto::franklin_crypto::bellman::plonk::commitments::transcript::keccak_transcript::RollingKeccakTranscript;
+use zksync_crypto::franklin_crypto::plonk::circuit::bigint::field::RnsParameters;
+use zksync_crypto::franklin_crypto::rescue::bn256::Bn256RescueParams;
+use zksync_crypto::franklin_crypto::rescue::rescue_transcript::RescueTranscriptForRNS;
+use zksync_crypto::pairing::Engine as EngineTrait;
+use zksync_crypto::primitives::EthereumSerializer;
+use zksync_crypto::proof::{AggregatedProof, SingleProof};
+use zksync_crypto::recursive_aggregation_circuit::circuit::RecursiveAggregationCircuitBn256;
+use zksync_crypto::{Engine, Fr};
+use zksync_types::{H256, U256};
+
+use crate::fs_utils::get_recursive_verification_key_path;
+use crate::PlonkVerificationKey;
+
+/// Number of the limbs of the aggregated G1 points passed to the `Verifier` contract.
+const SUBPROOF_LIMBS: usize = 16;
+
+/// Verifies the proofs using the verification keys from the keys directory.
+///
+/// Keys are loaded once upon creation, so the verifier is expected to be long-living.
+pub struct ProofVerifier {
+    block_chunks_sizes: Vec<usize>,
+    block_vks: HashMap<usize, PlonkVerificationKey>,
+    aggregated_vks: HashMap<usize, VkAggregate<Engine, RecursiveAggregationCircuitBn256<'static>>>,
+    vk_tree_root: U256,
+}
+
+impl std::fmt::Debug for ProofVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProofVerifier")
+            .field("block_chunks_sizes", &self.block_chunks_sizes)
+            .field("aggregated_proof_sizes", &self.aggregated_vks.keys())
+            .finish()
+    }
+}
+
+impl ProofVerifier {
+    /// Loads the verification keys for the supported block chunks sizes and aggregated proof sizes.
+    pub fn new(
+        block_chunks_sizes: &[usize],
+        aggregated_proof_sizes: &[usize],
+    ) -> anyhow::Result<Self> {
+        let mut block_vks = HashMap::new();
+        for &block_chunks in block_chunks_sizes {
+            let vk = PlonkVerificationKey::read_verification_key_for_main_circuit(block_chunks)
+                .map_err(|e| {
+                    format_err!(
+                        "Failed to read verification key for block size {}: {}",
+                        block_chunks,
+                        e
+                    )
+                })?;
+            block_vks.insert(block_chunks, vk);
+        }
+
+        let mut aggregated_vks = HashMap::new();
+        for &proofs in aggregated_proof_sizes {
+            let vk = VkAggregate::read(File::open(get_recursive_verification_key_path(proofs))?)
+                .map_err(|e| {
+                    format_err!(
+                        "Failed to read recursive verification key for {} proofs: {}",
+                        proofs,
+                        e
+                    )
+                })?;
+            aggregated_vks.insert(proofs, vk);
+        }
+
+        let vk_tree_root = EthereumSerializer::serialize_fe(
+            &PlonkVerificationKey::get_vk_tree_root_hash(block_chunks_sizes),
+        );
+
+        Ok(Self {
+            block_chunks_sizes: block_chunks_sizes.to_vec(),
+            block_vks,
+            aggregated_vks,
+            vk_tree_root,
+        })
+    }
+
+    /// Checks that the proof is valid and proves the block with the given commitment and size.
+    pub fn verify_single_proof(
+        &self,
+        proof: &SingleProof,
+        block_commitment: H256,
+        block_chunks: usize,
+    ) -> anyhow::Result<()> {
+        self.verify_block_proof(proof, commitment_to_input(block_commitment)?, block_chunks)
+    }
+
+    /// Checks that the proof is valid and has the given public input, i.e. the public data
+    /// commitment of the block witness.
+    pub fn verify_block_proof(
+        &self,
+        proof: &SingleProof,
+        public_input: Fr,
+        block_chunks: usize,
+    ) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let vk = self
+            .block_vks
+            .get(&block_chunks)
+            .ok_or_else(|| format_err!("Unsupported block size: {}", block_chunks))?;
+
+        ensure!(
+            proof.0.input_values == [public_input],
+            "Proof public input doesn't match the block commitment"
+        );
+
+        let rns_params =
+            RnsParameters::<Engine, <Engine as EngineTrait>::Fq>::new_for_field(68, 110, 4);
+        let rescue_params = Bn256RescueParams::new_checked_2_into_1();
+        let valid = verify_single::<_, _, RescueTranscriptForRNS<Engine>>(
+            &proof.0,
+            &vk.0,
+            Some((&rescue_params, &rns_params)),
+        )?;
+        metrics::histogram!("prover", start.elapsed(), "stage" => "check_proof", "type" => "single_proof");
+
+        ensure!(valid, "Proof for block is invalid");
+        Ok(())
+    }
+
+    /// Checks that the aggregated proof is valid and proves the blocks with the given commitments
+    /// and sizes, in the same order.
+    ///
+    /// Provers pad the aggregated proof with the sample proofs up to the smallest supported
+    /// size, so only the leading proofs are checked against the blocks.
+    pub fn verify_aggregated_proof(
+        &self,
+        proof: &AggregatedProof,
+        blocks: &[(H256, usize)],
+    ) -> anyhow::Result<()> {
+        let start = Instant::now();
+        ensure!(!blocks.is_empty(), "Aggregated proof for no blocks");
+        let (aggregated_size, vk) = self
+            .aggregated_vks
+            .iter()
+            .filter(|(size, _)| **size >= blocks.len())
+            .min_by_key(|(size, _)| **size)
+            .ok_or_else(|| format_err!("Unsupported aggregated proof size: {}", blocks.len()))?;
+
+        ensure!(
+            proof.individual_vk_inputs.len() == *aggregated_size
+                && proof.individual_vk_idxs.len() == *aggregated_size,
+            "Aggregated proof includes {} proofs, expected {}",
+            proof.individual_vk_inputs.len(),
+            aggregated_size
+        );
+        ensure!(
+            proof.aggr_limbs.len() == SUBPROOF_LIMBS,
+            "Aggregated proof has {} limbs, expected {}",
+            proof.aggr_limbs.len(),
+            SUBPROOF_LIMBS
+        );
+        for (idx, (block_commitment, block_chunks)) in blocks.iter().enumerate() {
+            ensure!(
+                proof.individual_vk_inputs[idx] == commitment_to_input(*block_commitment)?,
+                "Public input of proof {} doesn't match the block commitment",
+                idx
+            );
+            let vk_idx = self
+                .block_chunks_sizes
+                .iter()
+                .position(|size| size == block_chunks)
+                .ok_or_else(|| format_err!("Unsupported block size: {}", block_chunks))?;
+            ensure!(
+                proof.individual_vk_idxs[idx] == vk_idx,
+                "Verification key index of proof {} doesn't match the block size",
+                idx
+            );
+        }
+
+        // The recursive circuit input commits to the whole data checked by the contract.
+        let encoded = proof.serialize_aggregated_proof();
+        ensure!(
+            encoded.aggregated_input
+                == self.recursive_input(
+                    &encoded.individual_vk_inputs,
+                    &encoded.subproof_limbs,
+                    &proof.individual_vk_idxs
+                ),
+            "Recursive proof input doesn't match the aggregated data"
+        );
+
+        let valid = verify_aggregated::<_, _, RollingKeccakTranscript<Fr>>(vk, &proof.proof, None)?;
+        metrics::histogram!("prover", start.elapsed(), "stage" => "check_proof", "type" => "aggregated_proof");
+
+        ensure!(valid, "Recursive proof is invalid");
+        Ok(())
+    }
+
+    /// Reconstructs the recursive circuit input the same way the `Verifier` contract does.
+    fn recursive_input(
+        &self,
+        individual_vk_inputs: &[U256],
+        subproof_limbs: &[U256],
+        individual_vk_idxs: &[usize],
+    ) -> U256 {
+        let mut bytes = [0u8; 32];
+        self.vk_tree_root.to_big_endian(&mut bytes);
+        let mut concatenated = bytes.to_vec();
+        concatenated.extend(individual_vk_idxs.iter().map(|idx| *idx as u8));
+        for value in individual_vk_inputs.iter().chain(subproof_limbs) {
+            value.to_big_endian(&mut bytes);
+            concatenated.extend_from_slice(&bytes);
+        }
+
+        let mut commitment = sha256(&concatenated).to_vec();
+        // Top byte is cleared, so the commitment fits the field element.
+        commitment[0] = 0;
+        U256::from_big_endian(&commitment)
+    }
+}
+
+/// Converts the block commitment into the public input of the block circuit.
+/// Top bits of the commitment are nullified to fit it into the field element.
+pub fn commitment_to_input(block_commitment: H256) -> anyhow::Result<Fr> {
+    let mut bytes = block_commitment.to_fixed_bytes();
+    bytes[0] &= 0x1f;
+    Fr::from_bytes(&bytes).map_err(|e| format_err!("Failed to convert commitment: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregated_proofs::{gen_aggregate_proof, prepare_proof_data};
+    use crate::fs_utils::load_precomputed_proofs;
+    use zksync_config::ChainConfig;
+
+    /// Checks that the aggregated proof for 3 blocks padded with the sample proofs
+    /// up to the supported size is accepted. Requires the keys and the universal setup.
+    #[test]
+    #[ignore]
+    fn verify_padded_aggregated_proof() {
+        const BLOCKS_COUNT: usize = 3;
+
+        let config = ChainConfig::from_env();
+        let block_sizes = &config.circuit.supported_block_chunks_sizes;
+        let aggregated_sizes = config
+            .circuit
+            .supported_aggregated_proof_sizes_with_setup_pow();
+        let padded_size = aggregated_sizes
+            .iter()
+            .map(|(size, _)| *size)
+            .filter(|size| *size >= BLOCKS_COUNT)
+            .min()
+            .expect("no aggregated proof size fits the blocks");
+        assert!(padded_size > BLOCKS_COUNT, "aggregated proof is not padded");
+
+        // Leading sample proofs stand for the blocks, the rest are the padding.
+        let proofs = load_precomputed_proofs().unwrap().single_proofs[..padded_size].to_vec();
+        let blocks: Vec<_> = proofs[..BLOCKS_COUNT]
+            .iter()
+            .map(|(proof, block_chunks)| {
+                let commitment = H256::from_slice(&proof.0.input_values[0].to_bytes());
+                (commitment, *block_chunks)
+            })
+            .collect();
+        let (vks, proof_data) = prepare_proof_data(block_sizes, proofs);
+        let proof = gen_aggregate_proof(vks, proof_data, &aggregated_sizes, false).unwrap();
+
+        let verifier = ProofVerifier::new(
+            block_sizes,
+            &config.circuit.supported_aggregated_proof_sizes,
+        )
+        .unwrap();
+        verifier.verify_aggregated_proof(&proof, &blocks).unwrap();
+
+        let mut reordered_blocks = blocks.clone();
+        reordered_blocks.swap(0, 1);
+        assert!(verifier
+            .verify_aggregated_proof(&proof, &reordered_blocks)
+            .is_err());
+        // The proof of the larger size is not accepted for a single block.
+        assert!(verifier
+            .verify_aggregated_proof(&proof, &blocks[..1])
+            .is_err());
+    }
+}
@@ -0,0 +1 @@
+DROP INDEX CONCURRENTLY IF EXISTS ix_tx_filters_token_sequence_number;
@@ -0,0 +1,2 @@
+-- The index is built without locking `tx_filters` for writes, which requires running outside of a transaction.
+CREATE INDEX CONCURRENTLY IF NOT EXISTS ix_tx_filters_token_sequence_number ON public.tx_filters USING btree (token, sequence_number) include(is_priority);
@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS stats_progress;
+DROP TABLE IF EXISTS stats_blocks;
+DROP TABLE IF EXISTS stats_fees;
+DROP TABLE IF EXISTS stats_new_accounts;
+DROP TABLE IF EXISTS stats_active_accounts;
+DROP TABLE IF EXISTS stats_tx_counts;
@@ -0,0 +1,41 @@
+-- Hourly aggregates of the network statistics.
+-- Daily series are obtained by summing up the hourly ones.
+CREATE TABLE stats_tx_counts (
+    period TIMESTAMP WITH TIME ZONE NOT NULL,
+    tx_type TEXT NOT NULL,
+    count BIGINT NOT NULL,
+    PRIMARY KEY (period, tx_type)
+);
+
+-- Distinct addresses are stored to count active accounts for the periods longer than an hour.
+CREATE TABLE stats_active_accounts (
+    period TIMESTAMP WITH TIME ZONE NOT NULL,
+    address BYTEA NOT NULL,
+    PRIMARY KEY (period, address)
+);
+
+CREATE TABLE stats_new_accounts (
+    period TIMESTAMP WITH TIME ZONE PRIMARY KEY,
+    count BIGINT NOT NULL
+);
+
+CREATE TABLE stats_fees (
+    period TIMESTAMP WITH TIME ZONE NOT NULL,
+    token_id INTEGER NOT NULL,
+    amount NUMERIC NOT NULL,
+    PRIMARY KEY (period, token_id)
+);
+
+CREATE TABLE stats_blocks (
+    period TIMESTAMP WITH TIME ZONE PRIMARY KEY,
+    blocks_count BIGINT NOT NULL,
+    chunks_used BIGINT NOT NULL,
+    chunks_total BIGINT NOT NULL
+);
+
+-- The last block included into the aggregates.
+CREATE TABLE stats_progress (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    last_block BIGINT NOT NULL
+);
+INSERT INTO stats_progress (id, last_block) VALUES (true, 0);
@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS prover_stats;
+
+ALTER TABLE prover_job_queue
+    DROP COLUMN IF EXISTS retries,
+    DROP COLUMN IF EXISTS last_error,
+    DROP COLUMN IF EXISTS retry_after,
+    DROP COLUMN IF EXISTS prover_name,
+    DROP COLUMN IF EXISTS started_at;
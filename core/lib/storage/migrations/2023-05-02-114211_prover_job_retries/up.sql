@@ -0,0 +1,20 @@
+-- Failures reported for the job and the time before which it's not given to the provers again.
+ALTER TABLE prover_job_queue
+    ADD COLUMN retries INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN last_error TEXT,
+    ADD COLUMN retry_after TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN prover_name TEXT,
+    ADD COLUMN started_at TIMESTAMP WITH TIME ZONE;
+
+-- Track record of the provers, used to avoid giving jobs to the unreliable ones.
+CREATE TABLE prover_stats (
+    prover_name TEXT PRIMARY KEY,
+    successes BIGINT NOT NULL DEFAULT 0,
+    failures BIGINT NOT NULL DEFAULT 0,
+    -- Failures since the last successful job.
+    consecutive_failures BIGINT NOT NULL DEFAULT 0,
+    -- Total time of the successful jobs in milliseconds.
+    total_proving_time BIGINT NOT NULL DEFAULT 0,
+    last_failure_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,5 @@
+-- Time until which the job belongs to the prover it was given to, extended by the prover heartbeats.
+ALTER TABLE prover_job_queue ADD COLUMN lease_expires_at TIMESTAMP WITH TIME ZONE;
+
+-- Jobs that are being proven keep the previous stale job timeout.
+UPDATE prover_job_queue SET lease_expires_at = updated_at + INTERVAL '120 seconds' WHERE job_status = 1;
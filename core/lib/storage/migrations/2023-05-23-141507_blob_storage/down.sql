@@ -0,0 +1,20 @@
+-- Fails if some blobs are kept in the blob storage, they must be moved back first.
+ALTER TABLE block_witness
+    ALTER COLUMN witness SET NOT NULL,
+    DROP COLUMN IF EXISTS blob_key,
+    DROP COLUMN IF EXISTS blob_checksum;
+
+ALTER TABLE proofs
+    ALTER COLUMN proof SET NOT NULL,
+    DROP COLUMN IF EXISTS blob_key,
+    DROP COLUMN IF EXISTS blob_checksum;
+
+ALTER TABLE aggregated_proofs
+    ALTER COLUMN proof SET NOT NULL,
+    DROP COLUMN IF EXISTS blob_key,
+    DROP COLUMN IF EXISTS blob_checksum;
+
+ALTER TABLE prover_job_queue
+    ALTER COLUMN job_data SET NOT NULL,
+    DROP COLUMN IF EXISTS blob_key,
+    DROP COLUMN IF EXISTS blob_checksum;
@@ -0,0 +1,21 @@
+-- Witnesses, proofs and prover job data may be kept in the blob storage, in which case the database
+-- stores only the key of the blob and its SHA-256 checksum.
+ALTER TABLE block_witness
+    ALTER COLUMN witness DROP NOT NULL,
+    ADD COLUMN blob_key TEXT,
+    ADD COLUMN blob_checksum TEXT;
+
+ALTER TABLE proofs
+    ALTER COLUMN proof DROP NOT NULL,
+    ADD COLUMN blob_key TEXT,
+    ADD COLUMN blob_checksum TEXT;
+
+ALTER TABLE aggregated_proofs
+    ALTER COLUMN proof DROP NOT NULL,
+    ADD COLUMN blob_key TEXT,
+    ADD COLUMN blob_checksum TEXT;
+
+ALTER TABLE prover_job_queue
+    ALTER COLUMN job_data DROP NOT NULL,
+    ADD COLUMN blob_key TEXT,
+    ADD COLUMN blob_checksum TEXT;
@@ -0,0 +1,138 @@
+//! Storage of the large blobs, such as the block witnesses and proofs, outside of the database.
+//!
+//! If the blob storage is configured, the database keeps only the key of the blob along with
+//! its checksum, which is verified when the blob is loaded. The keys contain the checksum as
+//! well, so the stored blobs are never overwritten with a different content.
+
+// Built-in deps
+use std::fmt::Debug;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Arc;
+// External imports
+use anyhow::format_err;
+use async_trait::async_trait;
+use parity_crypto::digest::sha256;
+// Workspace imports
+use zksync_config::configs::database::BlobStorageBackend;
+use zksync_config::DBConfig;
+
+/// Creates the blob storage selected by `blob_storage_backend` in the database config,
+/// `None` means that the blobs are kept in the database.
+pub fn blob_storage_from_config(config: &DBConfig) -> anyhow::Result<Option<Arc<dyn BlobStorage>>> {
+    match config.blob_storage_backend {
+        BlobStorageBackend::Database => Ok(None),
+        BlobStorageBackend::Local => {
+            let path = config.blob_storage_path.as_ref().ok_or_else(|| {
+                format_err!("`blob_storage_path` must be set for the local blob storage")
+            })?;
+            Ok(Some(Arc::new(LocalBlobStorage::new(path))))
+        }
+    }
+}
+
+/// Key-value storage of the blobs, e.g. a local directory or an object store.
+#[async_trait]
+pub trait BlobStorage: Debug + Send + Sync {
+    /// Saves the blob, replacing the existing one with the same key.
+    async fn put(&self, key: &str, blob: &[u8]) -> anyhow::Result<()>;
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>>;
+}
+
+/// Blob storage keeping every blob in a separate file within the directory.
+#[derive(Debug, Clone)]
+pub struct LocalBlobStorage {
+    root: PathBuf,
+}
+
+impl LocalBlobStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+#[async_trait]
+impl BlobStorage for LocalBlobStorage {
+    async fn put(&self, key: &str, blob: &[u8]) -> anyhow::Result<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // The blob is written to the temporary file first, so the readers never see a partial blob.
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, blob).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path(key)).await {
+            Ok(blob) => Ok(Some(blob)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Hex-encoded SHA-256 hash of the blob.
+pub fn blob_checksum(blob: &[u8]) -> String {
+    hex::encode(&*sha256(blob))
+}
+
+/// Blob saved to the blob storage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlobPointer {
+    pub key: String,
+    pub checksum: String,
+}
+
+impl BlobPointer {
+    /// Saves the blob under the key made of the `prefix` and the blob checksum.
+    pub async fn save(
+        storage: &dyn BlobStorage,
+        prefix: &str,
+        blob: &[u8],
+    ) -> anyhow::Result<Self> {
+        let checksum = blob_checksum(blob);
+        let key = format!("{}-{}.json", prefix, checksum);
+        storage.put(&key, blob).await?;
+        Ok(Self { key, checksum })
+    }
+
+    /// Loads the blob and checks that it's not corrupted.
+    pub async fn load(&self, storage: Option<&dyn BlobStorage>) -> anyhow::Result<Vec<u8>> {
+        let storage = storage.ok_or_else(|| {
+            format_err!(
+                "Blob {} is kept outside of the database, but the blob storage is not configured",
+                self.key
+            )
+        })?;
+        let blob = storage
+            .get(&self.key)
+            .await?
+            .ok_or_else(|| format_err!("Blob {} is missing in the blob storage", self.key))?;
+        let checksum = blob_checksum(&blob);
+        if checksum != self.checksum {
+            return Err(format_err!(
+                "Checksum mismatch for the blob {}: expected {}, got {}",
+                self.key,
+                self.checksum,
+                checksum
+            ));
+        }
+        Ok(blob)
+    }
+
+    /// Combines the key and the checksum stored in the database.
+    pub fn from_db(key: Option<String>, checksum: Option<String>) -> Option<Self> {
+        match (key, checksum) {
+            (Some(key), Some(checksum)) => Some(Self { key, checksum }),
+            _ => None,
+        }
+    }
+}
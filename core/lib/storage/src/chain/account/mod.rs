@@ -1,4 +1,5 @@
 // Built-in deps
+use std::collections::HashMap;
 use std::time::Instant;
 // External imports
 use num::{BigUint, Zero};
@@ -19,7 +20,7 @@ pub mod restore_account;
 mod stored_state;
 
 pub(crate) use self::restore_account::restore_account;
-pub use self::stored_state::StoredAccountState;
+pub use self::stored_state::{AccountSnapshot, StoredAccountState};
 use crate::tokens::records::StorageNFT;
 use num::bigint::ToBigInt;
 
@@ -448,6 +449,262 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(address)
     }
 
+    /// Finds the IDs of the accounts with the given addresses.
+    /// Addresses that don't have an account in the network are omitted from the result.
+    pub async fn account_ids_by_addresses(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<HashMap<Address, AccountId>> {
+        let start = Instant::now();
+
+        let addresses: Vec<Vec<u8>> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let records = sqlx::query!(
+            r#"
+                SELECT DISTINCT ON (address) address, account_id FROM account_creates
+                WHERE address = ANY($1) AND is_create = $2
+                ORDER BY address, block_number DESC
+            "#,
+            &addresses,
+            true
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let account_ids = records
+            .into_iter()
+            .map(|record| {
+                (
+                    Address::from_slice(&record.address),
+                    AccountId(record.account_id as u32),
+                )
+            })
+            .collect();
+        metrics::histogram!(
+            "sql.chain.account.account_ids_by_addresses",
+            start.elapsed()
+        );
+        Ok(account_ids)
+    }
+
+    /// Loads both verified and committed states for the set of accounts.
+    ///
+    /// Unlike `last_committed_state_for_account`, the number of performed queries doesn't
+    /// depend on the number of requested accounts, so this method should be used for bulk lookups.
+    /// Accounts that don't exist are omitted from the result.
+    pub async fn account_snapshots_by_ids(
+        &mut self,
+        account_ids: &[AccountId],
+    ) -> QueryResult<Vec<AccountSnapshot>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let ids: Vec<i64> = account_ids.iter().map(|id| i64::from(**id)).collect();
+        // NFT tables store account IDs as `integer`.
+        let nft_creator_ids: Vec<i32> = account_ids.iter().map(|id| **id as i32).collect();
+
+        let last_verified_block = BlockSchema(&mut transaction)
+            .get_last_verified_confirmed_block()
+            .await?
+            .0 as i64;
+
+        // Load the verified states.
+        let stored_accounts = sqlx::query_as!(
+            StorageAccount,
+            "SELECT * FROM accounts WHERE id = ANY($1)",
+            &ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let stored_balances = sqlx::query_as!(
+            StorageBalance,
+            "SELECT * FROM balances WHERE account_id = ANY($1)",
+            &ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let stored_nfts = sqlx::query_as!(
+            StorageNFT,
+            "
+                SELECT nft.*, tokens.symbol FROM nft
+                INNER JOIN tokens
+                ON tokens.id = nft.token_id
+                WHERE creator_account_id = ANY($1)
+            ",
+            &nft_creator_ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut balances: HashMap<i64, Vec<StorageBalance>> = HashMap::new();
+        for balance in stored_balances {
+            balances
+                .entry(balance.account_id)
+                .or_default()
+                .push(balance);
+        }
+        let mut verified: HashMap<AccountId, (BlockNumber, Account)> = stored_accounts
+            .iter()
+            .map(|stored_account| {
+                let (account_id, account) = restore_account(
+                    stored_account,
+                    balances.remove(&stored_account.id).unwrap_or_default(),
+                );
+                (
+                    account_id,
+                    (BlockNumber(stored_account.last_block as u32), account),
+                )
+            })
+            .collect();
+        for nft in stored_nfts {
+            if let Some((_, account)) = verified.get_mut(&AccountId(nft.creator_account_id as u32))
+            {
+                account
+                    .minted_nfts
+                    .insert(TokenId(nft.token_id as u32), nft.into());
+            }
+        }
+
+        // Load the diffs that are committed but not verified yet.
+        let account_balance_diff = sqlx::query_as!(
+            StorageAccountUpdate,
+            "
+                SELECT * FROM account_balance_updates
+                WHERE account_id = ANY($1) AND block_number > $2
+            ",
+            &ids,
+            last_verified_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let account_creation_diff = sqlx::query_as!(
+            StorageAccountCreation,
+            "
+                SELECT * FROM account_creates
+                WHERE account_id = ANY($1) AND block_number > $2
+            ",
+            &ids,
+            last_verified_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let account_pubkey_diff = sqlx::query_as!(
+            StorageAccountPubkeyUpdate,
+            "
+                SELECT * FROM account_pubkey_updates
+                WHERE account_id = ANY($1) AND block_number > $2
+            ",
+            &ids,
+            last_verified_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let mint_nft_updates = sqlx::query_as!(
+            StorageMintNFTUpdate,
+            "
+                SELECT * FROM mint_nft_updates
+                WHERE creator_account_id = ANY($1) AND block_number > $2
+            ",
+            &nft_creator_ids,
+            last_verified_block
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut account_diff = Vec::new();
+        account_diff.extend(
+            account_balance_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_creation_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_pubkey_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(mint_nft_updates.into_iter().map(StorageAccountDiff::from));
+        account_diff.sort_by(StorageAccountDiff::cmp_order);
+
+        // Group the diffs by accounts preserving their order.
+        let mut updates: HashMap<AccountId, (BlockNumber, AccountUpdates)> = HashMap::new();
+        for diff in account_diff {
+            let block_number = BlockNumber(diff.block_number() as u32);
+            let (account_id, update) = diff.into();
+            let (last_update_block, account_updates) = updates.entry(account_id).or_default();
+            *last_update_block = std::cmp::max(*last_update_block, block_number);
+            account_updates.push((account_id, update));
+        }
+
+        // Load the account types.
+        let stored_types = sqlx::query!(
+            r#"
+                SELECT eth_account_types.account_id,
+                    eth_account_types.account_type as "account_type!: DbAccountType",
+                    no_2fa_pub_key_hash.pub_key_hash as "pub_key_hash?"
+                FROM eth_account_types
+                LEFT JOIN no_2fa_pub_key_hash
+                    ON no_2fa_pub_key_hash.account_id = eth_account_types.account_id
+                WHERE eth_account_types.account_id = ANY($1)
+            "#,
+            &ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let mut account_types: HashMap<AccountId, EthAccountType> = stored_types
+            .into_iter()
+            .map(|record| {
+                let pub_key_hash = record
+                    .pub_key_hash
+                    .map(|hash| PubKeyHash::from_hex(&hash).unwrap());
+                (
+                    AccountId(record.account_id as u32),
+                    EthAccountType::from_db(record.account_type, pub_key_hash),
+                )
+            })
+            .collect();
+
+        transaction.commit().await?;
+
+        let mut snapshots = Vec::with_capacity(account_ids.len());
+        for &account_id in account_ids {
+            let verified = verified.remove(&account_id);
+            let committed = match updates.remove(&account_id) {
+                Some((last_update_block, account_updates)) => account_updates
+                    .into_iter()
+                    .map(|(_, update)| update)
+                    .fold(
+                        verified.as_ref().map(|(_, account)| account.clone()),
+                        Account::apply_update,
+                    )
+                    .map(|account| (last_update_block, account)),
+                None => verified.clone(),
+            };
+            if verified.is_none() && committed.is_none() {
+                continue;
+            }
+
+            snapshots.push(AccountSnapshot {
+                account_id,
+                verified,
+                committed,
+                account_type: account_types.remove(&account_id),
+            });
+        }
+
+        metrics::histogram!(
+            "sql.chain.account.account_snapshots_by_ids",
+            start.elapsed()
+        );
+        Ok(snapshots)
+    }
+
     /// Obtains the last committed block that affects the account.
     pub async fn last_committed_block_with_update_for_acc(
         &mut self,
@@ -1,7 +1,19 @@
-use zksync_types::{Account, AccountId};
+use zksync_types::{Account, AccountId, BlockNumber};
+
+use super::records::EthAccountType;
 
 #[derive(Debug, PartialEq)]
 pub struct StoredAccountState {
     pub committed: Option<(AccountId, Account)>,
     pub verified: Option<(AccountId, Account)>,
 }
+
+/// Verified and committed states of the account along with the numbers of blocks
+/// in which they were last updated.
+#[derive(Debug, Clone)]
+pub struct AccountSnapshot {
+    pub account_id: AccountId,
+    pub verified: Option<(BlockNumber, Account)>,
+    pub committed: Option<(BlockNumber, Account)>,
+    pub account_type: Option<EthAccountType>,
+}
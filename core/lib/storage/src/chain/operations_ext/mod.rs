@@ -3,11 +3,12 @@ use std::time::Instant;
 
 // External imports
 use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgArguments, query::QueryAs, Postgres};
 
 // Workspace imports
 use zksync_api_types::{
     v02::{
-        pagination::{AccountTxsRequest, PaginationDirection, PaginationQuery},
+        pagination::{AccountTxsRequest, PaginationDirection, PaginationQuery, TxsSearchRequest},
         transaction::{
             ApiTxBatch, BatchStatus, Receipt, Transaction, TxData, TxHashSerializeWrapper,
             TxInBlockStatus,
@@ -39,6 +40,9 @@ use itertools::Itertools;
 pub(crate) mod conversion;
 pub mod records;
 
+/// Maximum number of the transactions counted by `search_transactions_count`.
+pub const MAX_SEARCH_COUNT: u32 = 10_000;
+
 /// Direction to perform search of transactions to.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SearchDirection {
@@ -1536,6 +1540,111 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(count as u32)
     }
 
+    /// Loads the page of transactions satisfying all the search filters, starting from
+    /// the transaction specified in the request (inclusive).
+    ///
+    /// Candidates are selected from the `tx_filters` table by address and token,
+    /// so at least one of `from`, `to` or `token` filters is expected to be set.
+    /// The `latest` cursor stands for the last transaction satisfying the filters.
+    /// Returns `None` if the starting transaction doesn't exist.
+    pub async fn search_transactions(
+        &mut self,
+        query: &PaginationQuery<TxsSearchRequest>,
+    ) -> QueryResult<Option<Vec<Transaction>>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let id_from = match query.from.tx_hash.inner {
+            Either::Left(tx_hash) => {
+                match transaction
+                    .chain()
+                    .operations_ext_schema()
+                    .get_tx_sequence_number(tx_hash)
+                    .await?
+                {
+                    Some(id_from) => id_from,
+                    None => return Ok(None),
+                }
+            }
+            Either::Right(_) => {
+                // Start from the last transaction satisfying the filters, so the `latest`
+                // cursor works for both directions.
+                let query_text = format!(
+                    "SELECT max(sequence_number) FROM ({}) AS found",
+                    txs_search_query(&query.from, "", "DESC", "LIMIT 1")
+                );
+                let (last_id,): (Option<i64>,) =
+                    bind_txs_search_filters(sqlx::query_as(&query_text), &query.from)
+                        .fetch_one(transaction.conn())
+                        .await?;
+                match last_id {
+                    Some(last_id) => last_id,
+                    None => return Ok(Some(Vec::new())),
+                }
+            }
+        };
+
+        let (cursor_condition, order) = match query.direction {
+            PaginationDirection::Newer => ("AND sequence_number >= $7", "ASC"),
+            PaginationDirection::Older => ("AND sequence_number <= $7", "DESC"),
+        };
+        let query_text = format!(
+            "{} ORDER BY sequence_number {} LIMIT $8",
+            txs_search_query(&query.from, cursor_condition, order, "LIMIT $8"),
+            order
+        );
+        let raw_txs: Vec<TransactionItem> =
+            bind_txs_search_filters(sqlx::query_as(&query_text), &query.from)
+                .bind(id_from)
+                .bind(i64::from(query.limit))
+                .fetch_all(transaction.conn())
+                .await?;
+
+        let last_finalized = transaction
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        let txs = raw_txs
+            .into_iter()
+            .map(|tx| {
+                let is_finalized = tx.block_number as u32 <= *last_finalized;
+                TransactionItem::transaction_from_item(tx, is_finalized)
+            })
+            .collect();
+        transaction.commit().await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.search_transactions",
+            start.elapsed()
+        );
+        Ok(Some(txs))
+    }
+
+    /// Returns the number of transactions satisfying all the search filters,
+    /// but not more than `MAX_SEARCH_COUNT`, so the count doesn't require a full scan.
+    pub async fn search_transactions_count(
+        &mut self,
+        filters: &TxsSearchRequest,
+    ) -> QueryResult<u32> {
+        let start = Instant::now();
+
+        let query_text = format!(
+            "SELECT COUNT(*) FROM ({} LIMIT $7) AS found",
+            txs_search_query(filters, "", "DESC", "LIMIT $7")
+        );
+        let (count,): (i64,) = bind_txs_search_filters(sqlx::query_as(&query_text), filters)
+            .bind(i64::from(MAX_SEARCH_COUNT))
+            .fetch_one(self.0.conn())
+            .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.search_transactions_count",
+            start.elapsed()
+        );
+        Ok(count as u32)
+    }
+
     /// Returns `created_at` for `block_number` fields for transaction with given hash.
     pub async fn get_tx_sequence_number_for_block(
         &mut self,
@@ -1758,6 +1867,68 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Returns the web3 data of the operation executed in the given block at the given position.
+    pub async fn web3_tx_data_by_block_and_index(
+        &mut self,
+        block_number: BlockNumber,
+        block_index: u32,
+    ) -> QueryResult<Option<Web3TxData>> {
+        let start = Instant::now();
+
+        let result: Option<Web3TxData> = sqlx::query_as!(
+            Web3TxData,
+            r#"
+                WITH transaction AS (
+                    SELECT
+                        tx_hash,
+                        block_number,
+                        nonce,
+                        block_index,
+                        from_account,
+                        to_account
+                    FROM executed_transactions
+                    WHERE block_number = $1 AND block_index = $2
+                ), priority_op AS (
+                    SELECT
+                        tx_hash,
+                        block_number,
+                        priority_op_serialid as nonce,
+                        block_index,
+                        from_account,
+                        to_account
+                    FROM executed_priority_operations
+                    WHERE block_number = $1 AND block_index = $2
+                ),
+                everything AS (
+                    SELECT * FROM transaction
+                    UNION ALL
+                    SELECT * FROM priority_op
+                )
+                SELECT
+                    tx_hash as "tx_hash!",
+                    block_number as "block_number!",
+                    nonce as "nonce!",
+                    block_index as "block_index?",
+                    from_account as "from_account!",
+                    to_account as "to_account?",
+                    root_hash as "block_hash!"
+                FROM everything
+                LEFT JOIN blocks
+                    ON everything.block_number = blocks.number
+            "#,
+            i64::from(*block_number),
+            block_index as i32,
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.web3_tx_data_by_block_and_index",
+            start.elapsed()
+        );
+        Ok(result)
+    }
+
     pub async fn web3_receipt_by_hash(
         &mut self,
         hash: &[u8],
@@ -2169,3 +2340,115 @@ async fn tx_data_from_storage(
         complete_withdrawals_tx_hash,
     ))
 }
+
+/// Builds the query selecting the transactions that satisfy the search filters.
+///
+/// Filters are bound to the parameters `$1..$6` by `bind_txs_search_filters`,
+/// `extra_condition` is applied to the `tx_filters` candidates and may use the next ones.
+///
+/// Both parts of the union scan the candidates in the `order` of their sequence numbers
+/// and stop after `limit` matching transactions, so the popular tokens don't require
+/// a full scan of their transactions.
+fn txs_search_query(
+    filters: &TxsSearchRequest,
+    extra_condition: &str,
+    order: &str,
+    limit: &str,
+) -> String {
+    // Use the most selective of the indexed columns to find the candidates,
+    // the rest of the filters are checked against the executed operations.
+    let address_condition = if filters.from.is_some() {
+        "AND address = $1"
+    } else if filters.to.is_some() {
+        "AND address = $2"
+    } else {
+        ""
+    };
+    let token_condition = if filters.token.is_some() {
+        "AND token = $3"
+    } else {
+        ""
+    };
+    let candidates = |is_priority: bool| {
+        format!(
+            r#"
+                SELECT DISTINCT sequence_number FROM tx_filters
+                WHERE sequence_number IS NOT NULL AND is_priority = {} {} {} {}
+                ORDER BY sequence_number {}
+            "#,
+            is_priority, address_condition, token_condition, extra_condition, order
+        )
+    };
+
+    format!(
+        r#"
+            (
+                SELECT
+                    executed_transactions.sequence_number,
+                    tx_hash,
+                    tx as op,
+                    block_number,
+                    created_at,
+                    success,
+                    fail_reason,
+                    Null::bytea as eth_hash,
+                    Null::bigint as priority_op_serialid,
+                    block_index,
+                    batch_id
+                FROM ({}) AS candidates
+                INNER JOIN executed_transactions
+                    ON executed_transactions.sequence_number = candidates.sequence_number
+                WHERE ($1::bytea IS NULL OR from_account = $1)
+                    AND ($2::bytea IS NULL OR to_account = $2)
+                    AND ($4::text IS NULL OR tx->>'type' = $4)
+                    AND ($5::bigint IS NULL OR block_number >= $5)
+                    AND ($6::bigint IS NULL OR block_number <= $6)
+                ORDER BY candidates.sequence_number {order}
+                {limit}
+            )
+            UNION ALL
+            (
+                SELECT
+                    executed_priority_operations.sequence_number,
+                    tx_hash,
+                    operation as op,
+                    block_number,
+                    created_at,
+                    true as success,
+                    Null as fail_reason,
+                    eth_hash,
+                    priority_op_serialid,
+                    block_index,
+                    Null::bigint as batch_id
+                FROM ({}) AS candidates
+                INNER JOIN executed_priority_operations
+                    ON executed_priority_operations.sequence_number = candidates.sequence_number
+                WHERE ($1::bytea IS NULL OR from_account = $1)
+                    AND ($2::bytea IS NULL OR to_account = $2)
+                    AND ($4::text IS NULL OR operation->>'type' = $4)
+                    AND ($5::bigint IS NULL OR block_number >= $5)
+                    AND ($6::bigint IS NULL OR block_number <= $6)
+                ORDER BY candidates.sequence_number {order}
+                {limit}
+            )
+        "#,
+        candidates(false),
+        candidates(true),
+        order = order,
+        limit = limit,
+    )
+}
+
+/// Binds the search filters to the query built by `txs_search_query`.
+fn bind_txs_search_filters<'q, O>(
+    query: QueryAs<'q, Postgres, O, PgArguments>,
+    filters: &'q TxsSearchRequest,
+) -> QueryAs<'q, Postgres, O, PgArguments> {
+    query
+        .bind(filters.from.as_ref().map(|address| address.as_bytes()))
+        .bind(filters.to.as_ref().map(|address| address.as_bytes()))
+        .bind(filters.token.map(|token| token.0 as i32))
+        .bind(filters.tx_type.as_deref())
+        .bind(filters.from_block.map(|block| i64::from(*block)))
+        .bind(filters.to_block.map(|block| i64::from(*block)))
+}
@@ -0,0 +1,447 @@
+use std::cmp::{max, min};
+// Built-in deps
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, TimeZone, Utc};
+use num::{BigInt, BigUint, Zero};
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_api_types::v02::stats::StatsInterval;
+use zksync_types::{
+    block::Block, Address, BlockNumber, ExecutedOperations, SequentialTxId, TokenId, TokenLike,
+};
+// Local imports
+use self::records::{
+    StorageAccountsStats, StorageBlockStats, StorageFeeStats, StorageTxCountStats,
+};
+use crate::{chain::block::BlockSchema, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Auxiliary schema encapsulating the stats counting logic for the storage tables.
+#[derive(Debug)]
+pub struct StatsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> StatsSchema<'a, 'c> {
+    /// Returns the amount of blocks that don't have proofs yet.
+    pub async fn count_outstanding_proofs(&mut self, after_block: BlockNumber) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!(
+            "SELECT COUNT(*) FROM executed_transactions WHERE block_number > $1",
+            i64::from(*after_block)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count
+        .unwrap_or(0);
+
+        metrics::histogram!("sql.chain.stats.count_outstanding_proofs", start.elapsed());
+        Ok(count as u32)
+    }
+
+    /// Count total transactions after tx_id, and return count and max tx_id.
+    /// It allows us to cache count of transactions and make these queries much faster
+    pub async fn count_total_transactions(
+        &mut self,
+        after_tx_id: SequentialTxId,
+    ) -> QueryResult<(u32, SequentialTxId)> {
+        let after_tx_id = *after_tx_id as i64;
+        let start = Instant::now();
+        let tx_res = sqlx::query!(
+            "SELECT COUNT(*), MAX(sequence_number) FROM executed_transactions 
+                 WHERE success = true AND sequence_number > $1",
+            after_tx_id
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        let prior_ops_res = sqlx::query!(
+            "SELECT COUNT(*), MAX(sequence_number) FROM executed_priority_operations WHERE sequence_number > $1",
+            after_tx_id
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.count_total_transactions", start.elapsed());
+        Ok((
+            (tx_res.count.unwrap_or_default() + prior_ops_res.count.unwrap_or_default()) as u32,
+            SequentialTxId(max(
+                prior_ops_res.max.unwrap_or(after_tx_id),
+                tx_res.max.unwrap_or(after_tx_id),
+            ) as u64),
+        ))
+    }
+
+    /// Aggregates the statistics of the blocks finalized since the previous call into the
+    /// hourly summary tables and returns the number of processed blocks.
+    ///
+    /// At most `max_blocks` blocks are processed at once. The progress row is locked
+    /// until the end of the update, so concurrent calls don't process the same blocks twice.
+    pub async fn update_stats_summary(&mut self, max_blocks: u32) -> QueryResult<u32> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let last_processed_block = sqlx::query!("SELECT last_block FROM stats_progress FOR UPDATE")
+            .fetch_one(transaction.conn())
+            .await?
+            .last_block;
+        let last_finalized_block = BlockSchema(&mut transaction)
+            .get_last_verified_confirmed_block()
+            .await?;
+        let to_block = min(
+            i64::from(*last_finalized_block),
+            last_processed_block + i64::from(max_blocks),
+        );
+        if to_block <= last_processed_block {
+            return Ok(0);
+        }
+
+        let new_accounts: HashMap<i64, i64> = sqlx::query!(
+            r#"
+                SELECT block_number, COUNT(*) as "count!" FROM account_creates
+                WHERE block_number > $1 AND block_number <= $2 AND is_create = true
+                GROUP BY block_number
+            "#,
+            last_processed_block,
+            to_block
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|record| (record.block_number, record.count))
+        .collect();
+
+        let mut summary = StatsSummary::default();
+        for block_number in (last_processed_block + 1)..=to_block {
+            let block = BlockSchema(&mut transaction)
+                .get_block(BlockNumber(block_number as u32))
+                .await?
+                .ok_or_else(|| anyhow::format_err!("Block {} is not found", block_number))?;
+            summary.add_block(
+                &block,
+                new_accounts.get(&block_number).copied().unwrap_or_default(),
+            );
+        }
+
+        for ((period, tx_type), count) in summary.tx_counts {
+            sqlx::query!(
+                "
+                    INSERT INTO stats_tx_counts (period, tx_type, count)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (period, tx_type)
+                    DO UPDATE SET count = stats_tx_counts.count + EXCLUDED.count
+                ",
+                period,
+                tx_type,
+                count
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        for (period, addresses) in summary.active_accounts {
+            let addresses: Vec<Vec<u8>> = addresses
+                .into_iter()
+                .map(|address| address.as_bytes().to_vec())
+                .collect();
+            sqlx::query!(
+                "
+                    INSERT INTO stats_active_accounts (period, address)
+                    SELECT $1, u.address FROM UNNEST ($2::bytea[]) AS u(address)
+                    ON CONFLICT (period, address) DO NOTHING
+                ",
+                period,
+                &addresses
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        for (period, count) in summary.new_accounts {
+            sqlx::query!(
+                "
+                    INSERT INTO stats_new_accounts (period, count)
+                    VALUES ($1, $2)
+                    ON CONFLICT (period)
+                    DO UPDATE SET count = stats_new_accounts.count + EXCLUDED.count
+                ",
+                period,
+                count
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        for ((period, token_id), amount) in summary.fees {
+            sqlx::query!(
+                "
+                    INSERT INTO stats_fees (period, token_id, amount)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (period, token_id)
+                    DO UPDATE SET amount = stats_fees.amount + EXCLUDED.amount
+                ",
+                period,
+                *token_id as i32,
+                BigDecimal::from(BigInt::from(amount))
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        for (period, stats) in summary.blocks {
+            sqlx::query!(
+                "
+                    INSERT INTO stats_blocks (period, blocks_count, chunks_used, chunks_total)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (period)
+                    DO UPDATE SET
+                        blocks_count = stats_blocks.blocks_count + EXCLUDED.blocks_count,
+                        chunks_used = stats_blocks.chunks_used + EXCLUDED.chunks_used,
+                        chunks_total = stats_blocks.chunks_total + EXCLUDED.chunks_total
+                ",
+                period,
+                stats.blocks_count,
+                stats.chunks_used,
+                stats.chunks_total
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        sqlx::query!("UPDATE stats_progress SET last_block = $1", to_block)
+            .execute(transaction.conn())
+            .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.stats.update_stats_summary", start.elapsed());
+        Ok((to_block - last_processed_block) as u32)
+    }
+
+    /// Returns the number of transactions by types for every period in the `[from, to)` range.
+    pub async fn tx_counts_stats(
+        &mut self,
+        interval: StatsInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Vec<StorageTxCountStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StorageTxCountStats,
+            r#"
+                SELECT date_trunc($1, period AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' as "period!", tx_type, SUM(count)::bigint as "count!"
+                FROM stats_tx_counts
+                WHERE period >= $2 AND period < $3
+                GROUP BY 1, 2
+                ORDER BY 1
+            "#,
+            interval.as_str(),
+            from,
+            to
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.tx_counts_stats", start.elapsed());
+        Ok(stats)
+    }
+
+    /// Returns the number of active accounts for every period in the `[from, to)` range.
+    pub async fn active_accounts_stats(
+        &mut self,
+        interval: StatsInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Vec<StorageAccountsStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StorageAccountsStats,
+            r#"
+                SELECT date_trunc($1, period AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' as "period!", COUNT(DISTINCT address) as "count!"
+                FROM stats_active_accounts
+                WHERE period >= $2 AND period < $3
+                GROUP BY 1
+                ORDER BY 1
+            "#,
+            interval.as_str(),
+            from,
+            to
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.active_accounts_stats", start.elapsed());
+        Ok(stats)
+    }
+
+    /// Returns the number of created accounts for every period in the `[from, to)` range.
+    pub async fn new_accounts_stats(
+        &mut self,
+        interval: StatsInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Vec<StorageAccountsStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StorageAccountsStats,
+            r#"
+                SELECT date_trunc($1, period AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' as "period!", SUM(count)::bigint as "count!"
+                FROM stats_new_accounts
+                WHERE period >= $2 AND period < $3
+                GROUP BY 1
+                ORDER BY 1
+            "#,
+            interval.as_str(),
+            from,
+            to
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.new_accounts_stats", start.elapsed());
+        Ok(stats)
+    }
+
+    /// Returns the collected fees by tokens for every period in the `[from, to)` range.
+    pub async fn fees_stats(
+        &mut self,
+        interval: StatsInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Vec<StorageFeeStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StorageFeeStats,
+            r#"
+                SELECT date_trunc($1, period AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' as "period!", token_id, SUM(amount) as "amount!"
+                FROM stats_fees
+                WHERE period >= $2 AND period < $3
+                GROUP BY 1, 2
+                ORDER BY 1
+            "#,
+            interval.as_str(),
+            from,
+            to
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.fees_stats", start.elapsed());
+        Ok(stats)
+    }
+
+    /// Returns the number of blocks and their used chunks for every period in the `[from, to)` range.
+    pub async fn blocks_stats(
+        &mut self,
+        interval: StatsInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Vec<StorageBlockStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StorageBlockStats,
+            r#"
+                SELECT
+                    date_trunc($1, period AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' as "period!",
+                    SUM(blocks_count)::bigint as "blocks_count!",
+                    SUM(chunks_used)::bigint as "chunks_used!",
+                    SUM(chunks_total)::bigint as "chunks_total!"
+                FROM stats_blocks
+                WHERE period >= $2 AND period < $3
+                GROUP BY 1
+                ORDER BY 1
+            "#,
+            interval.as_str(),
+            from,
+            to
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.stats.blocks_stats", start.elapsed());
+        Ok(stats)
+    }
+}
+
+/// Returns the beginning of the hour containing the given time.
+fn hour_start(time: DateTime<Utc>) -> DateTime<Utc> {
+    let timestamp = time.timestamp();
+    Utc.timestamp_opt(timestamp - timestamp.rem_euclid(3600), 0)
+        .unwrap()
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct BlocksSummary {
+    blocks_count: i64,
+    chunks_used: i64,
+    chunks_total: i64,
+}
+
+/// Statistics of the processed blocks grouped by hours.
+#[derive(Debug, Default)]
+struct StatsSummary {
+    tx_counts: HashMap<(DateTime<Utc>, String), i64>,
+    active_accounts: HashMap<DateTime<Utc>, HashSet<Address>>,
+    new_accounts: HashMap<DateTime<Utc>, i64>,
+    fees: HashMap<(DateTime<Utc>, TokenId), BigUint>,
+    blocks: HashMap<DateTime<Utc>, BlocksSummary>,
+}
+
+impl StatsSummary {
+    fn add_block(&mut self, block: &Block, new_accounts: i64) {
+        let mut last_op_time = None;
+        for op in &block.block_transactions {
+            let period = match op {
+                ExecutedOperations::Tx(tx) => {
+                    if !tx.success {
+                        continue;
+                    }
+                    let period = hour_start(tx.created_at);
+                    let zksync_tx = &tx.signed_tx.tx;
+                    *self
+                        .tx_counts
+                        .entry((period, zksync_tx.variance_name()))
+                        .or_default() += 1;
+                    self.active_accounts
+                        .entry(period)
+                        .or_default()
+                        .insert(zksync_tx.account());
+                    if let Some((_, TokenLike::Id(token_id), _, fee)) = zksync_tx.get_fee_info() {
+                        if !fee.is_zero() {
+                            *self.fees.entry((period, token_id)).or_default() += fee;
+                        }
+                    }
+                    period
+                }
+                ExecutedOperations::PriorityOp(op) => {
+                    let period = hour_start(op.created_at);
+                    let data = &op.priority_op.data;
+                    *self
+                        .tx_counts
+                        .entry((period, data.variance_name()))
+                        .or_default() += 1;
+                    self.active_accounts
+                        .entry(period)
+                        .or_default()
+                        .insert(data.to_account());
+                    period
+                }
+            };
+            last_op_time = max(last_op_time, Some(period));
+        }
+
+        // Blocks created before the timestamps were introduced are attributed
+        // to the time of their operations.
+        let block_period = if block.timestamp > 0 {
+            Some(hour_start(block.timestamp_utc()))
+        } else {
+            last_op_time
+        };
+        if let Some(period) = block_period {
+            let blocks = self.blocks.entry(period).or_default();
+            blocks.blocks_count += 1;
+            blocks.chunks_used += block.chunks_used() as i64;
+            blocks.chunks_total += block.block_chunks_size as i64;
+
+            if new_accounts > 0 {
+                *self.new_accounts.entry(period).or_default() += new_accounts;
+            }
+        }
+    }
+}
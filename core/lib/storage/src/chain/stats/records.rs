@@ -0,0 +1,43 @@
+// External imports
+use chrono::{DateTime, Utc};
+use num::{bigint::ToBigInt, BigUint};
+use sqlx::{types::BigDecimal, FromRow};
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageTxCountStats {
+    pub period: DateTime<Utc>,
+    pub tx_type: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageAccountsStats {
+    pub period: DateTime<Utc>,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageFeeStats {
+    pub period: DateTime<Utc>,
+    pub token_id: i32,
+    pub amount: BigDecimal,
+}
+
+impl StorageFeeStats {
+    /// Returns the collected fee amount as an integer.
+    pub fn amount(&self) -> BigUint {
+        self.amount
+            .to_bigint()
+            .and_then(|amount| amount.to_biguint())
+            // Only the sums of the integer non-negative amounts are stored in this table.
+            .expect("Invalid fee amount has been stored")
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StorageBlockStats {
+    pub period: DateTime<Utc>,
+    pub blocks_count: i64,
+    pub chunks_used: i64,
+    pub chunks_total: i64,
+}
@@ -1,5 +1,5 @@
 // Built-in deps
-use std::{fmt, time::Duration, time::Instant};
+use std::{fmt, sync::Arc, time::Duration, time::Instant};
 // External imports
 use async_trait::async_trait;
 use deadpool::managed::{Manager, PoolConfig, RecycleResult, Timeouts};
@@ -8,6 +8,7 @@ use sqlx::{Connection, Error as SqlxError, PgConnection};
 use tokio::time;
 // Local imports
 // use self::recoverable_connection::RecoverableConnection;
+use crate::blob_storage::BlobStorage;
 use crate::{get_database_replica_url, get_database_url, StorageProcessor};
 use zksync_utils::parse_env;
 
@@ -55,6 +56,8 @@ impl Manager for DbPool {
 #[derive(Clone)]
 pub struct ConnectionPool {
     pool: Pool,
+    /// Storage of the witnesses and proofs, which are kept in the database if it's absent.
+    blob_storage: Option<Arc<dyn BlobStorage>>,
 }
 
 impl fmt::Debug for ConnectionPool {
@@ -73,7 +76,10 @@ impl ConnectionPool {
 
         let pool = DbPool::create(database_url, max_size as usize);
 
-        Self { pool }
+        Self {
+            pool,
+            blob_storage: None,
+        }
     }
 
     /// Establishes a pool of the connections to the replica of database and
@@ -86,8 +92,19 @@ impl ConnectionPool {
 
         let pool = DbPool::create(database_url, max_size as usize);
 
-        Self { pool }
+        Self {
+            pool,
+            blob_storage: None,
+        }
     }
+
+    /// Sets the blob storage used by every `StorageProcessor` created by the pool,
+    /// e.g. the one created by `blob_storage_from_config`.
+    pub fn with_blob_storage(mut self, blob_storage: Option<Arc<dyn BlobStorage>>) -> Self {
+        self.blob_storage = blob_storage;
+        self
+    }
+
     /// Creates a `StorageProcessor` entity over a recoverable connection.
     /// Upon a database outage connection will block the thread until
     /// it will be able to recover the connection (or, if connection cannot
@@ -101,7 +118,9 @@ impl ConnectionPool {
         let connection = self.get_pooled_connection().await;
         metrics::histogram!("sql.connection_acquire", start.elapsed());
 
-        Ok(StorageProcessor::from_pool(connection))
+        let mut storage = StorageProcessor::from_pool(connection);
+        storage.set_blob_storage(self.blob_storage.clone());
+        Ok(storage)
     }
 
     async fn get_pooled_connection(&self) -> PooledConnection {
@@ -77,17 +77,20 @@
 
 // Built-in deps
 use std::env;
+use std::sync::Arc;
 // External imports
 use sqlx::{postgres::Postgres, Connection, PgConnection, Transaction};
 // Workspace imports
 use zksync_types::{ActionType, BlockNumber};
 // Local imports
+use crate::blob_storage::BlobStorage;
 use crate::connection::{holder::ConnectionHolder, PooledConnection};
 
 // mod schema;
 #[cfg(test)]
 mod tests;
 
+pub mod blob_storage;
 pub mod chain;
 pub mod config;
 pub mod connection;
@@ -132,6 +135,8 @@ pub fn get_database_url() -> String {
 pub struct StorageProcessor<'a> {
     conn: ConnectionHolder<'a>,
     in_transaction: bool,
+    /// Storage of the witnesses and proofs, which are kept in the database if it's absent.
+    blob_storage: Option<Arc<dyn BlobStorage>>,
 }
 
 #[derive(sqlx::Type, Debug, Clone, PartialEq, Eq)]
@@ -158,6 +163,7 @@ impl<'a> StorageProcessor<'a> {
         Ok(StorageProcessor {
             conn: ConnectionHolder::Direct(connection),
             in_transaction: false,
+            blob_storage: None,
         })
     }
 
@@ -168,6 +174,7 @@ impl<'a> StorageProcessor<'a> {
 
         let mut processor = StorageProcessor::from_transaction(transaction);
         processor.in_transaction = true;
+        processor.blob_storage = self.blob_storage.clone();
 
         Ok(processor)
     }
@@ -181,6 +188,7 @@ impl<'a> StorageProcessor<'a> {
         StorageProcessor {
             conn: ConnectionHolder::Transaction(conn),
             in_transaction: true,
+            blob_storage: None,
         }
     }
 
@@ -200,9 +208,20 @@ impl<'a> StorageProcessor<'a> {
         Self {
             conn: ConnectionHolder::Pooled(conn),
             in_transaction: false,
+            blob_storage: None,
         }
     }
 
+    /// Returns the storage of the witnesses and proofs, if they're kept outside of the database.
+    pub fn blob_storage(&self) -> Option<Arc<dyn BlobStorage>> {
+        self.blob_storage.clone()
+    }
+
+    /// Sets the storage of the witnesses and proofs, `None` keeps them in the database.
+    pub fn set_blob_storage(&mut self, blob_storage: Option<Arc<dyn BlobStorage>>) {
+        self.blob_storage = blob_storage;
+    }
+
     /// Gains access to the `Chain` schemas.
     pub fn chain(&mut self) -> chain::ChainIntermediator<'_, 'a> {
         chain::ChainIntermediator(self)
@@ -1,21 +1,52 @@
 // Built-in deps
-use std::time::Instant;
+use std::time::{Duration, Instant};
 // External imports
 use anyhow::format_err;
+use thiserror::Error;
 // Workspace imports
 use zksync_types::BlockNumber;
 // Local imports
-use self::records::{StorageProverJobQueue, StoredAggregatedProof, StoredProof};
+use self::records::{
+    StorageActiveProverJob, StorageBlocksBySize, StorageProverJobQueue, StorageProverJobQueueDepth,
+    StorageProverJobsBySize, StorageProverStats, StorageProverThroughput, StoredAggregatedProof,
+    StoredProof,
+};
+use crate::blob_storage::BlobPointer;
 use crate::chain::operations::OperationsSchema;
 use crate::prover::records::StorageBlockWitness;
 use crate::{QueryResult, StorageProcessor};
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_types::aggregated_operations::AggregatedActionType;
 use zksync_types::prover::{ProverJob, ProverJobStatus, ProverJobType};
 
 pub mod records;
 
+/// Number of consecutive failures after which the prover is considered unreliable.
+const UNRELIABLE_PROVER_MIN_FAILURES: i64 = 3;
+/// Time since the last failure during which the unreliable prover doesn't receive jobs.
+const UNRELIABLE_PROVER_COOLDOWN_SECS: i64 = 10 * 60;
+/// Limits the growth of the failed job retry backoff to `2^MAX_RETRY_BACKOFF_EXPONENT` times.
+const MAX_RETRY_BACKOFF_EXPONENT: i32 = 5;
+
+/// Error returned on attempt to store the result of the job that is already finished.
+#[derive(Debug, Error)]
+#[error("Job {job_id} is already finished")]
+pub struct JobAlreadyFinished {
+    pub job_id: i32,
+}
+
+/// The prover is unreliable if it has failed several jobs in a row and has failed recently.
+///
+/// Only the failures since the last successful job are taken into account, so the track record
+/// of a long-living prover doesn't hide the fact that it's broken now.
+fn is_unreliable_prover(stats: &StorageProverStats) -> bool {
+    let failed_recently = stats.last_failure_at.map_or(false, |failed_at| {
+        Utc::now() - failed_at < chrono::Duration::seconds(UNRELIABLE_PROVER_COOLDOWN_SECS)
+    });
+    stats.consecutive_failures >= UNRELIABLE_PROVER_MIN_FAILURES && failed_recently
+}
+
 /// Prover schema is capable of handling the prover-related informations,
 /// such as started prover jobs, registered provers and proofs for blocks.
 #[derive(Debug)]
@@ -47,13 +78,19 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         job_type: ProverJobType,
     ) -> QueryResult<()> {
         let start = Instant::now();
+        let blob = self
+            .save_blob(
+                &format!("prover_jobs/{}_{}_{}", job_type, first_block, last_block),
+                &job_data.to_string(),
+            )
+            .await?;
         sqlx::query!(
         "
           WITH job_values as (
-            SELECT $1::int4, $2::int4, $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb
+            SELECT $1::int4, $2::int4, $3::text, 'server_add_job', $4::int8, $5::int8, $6::jsonb, $7::text, $8::text
             WHERE NOT EXISTS (SELECT * FROM prover_job_queue WHERE first_block = $4 and last_block = $5 and job_type = $3 LIMIT 1)
           )
-          INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data)
+          INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data, blob_key, blob_checksum)
           SELECT * from job_values
         ",
             ProverJobStatus::Idle.to_number(),
@@ -61,18 +98,23 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             job_type.to_string(),
             i64::from(*first_block),
             i64::from(*last_block),
-            job_data,
+            if blob.is_some() { None } else { Some(job_data) },
+            blob.as_ref().map(|blob| blob.key.clone()),
+            blob.as_ref().map(|blob| blob.checksum.clone())
         ).execute(self.0.conn()).await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "add_prover_job_to_job_queue");
         Ok(())
     }
 
+    /// Returns the jobs with expired leases to the queue.
+    ///
+    /// Such jobs are given out again even without it, so this only keeps the job statuses up to date.
     pub async fn mark_stale_jobs_as_idle(&mut self) -> QueryResult<()> {
         let start = Instant::now();
         let result = sqlx::query!(
             "UPDATE prover_job_queue SET (job_status, updated_at, updated_by) = ($1, now(), 'server_clean_idle')
-            WHERE job_status = $2 AND (now() - INTERVAL '120 seconds') >= updated_at RETURNING id",
+            WHERE job_status = $2 AND lease_expires_at <= now() RETURNING id",
             ProverJobStatus::Idle.to_number(),
             ProverJobStatus::InProgress.to_number(),
         )
@@ -83,8 +125,24 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
-    pub async fn get_idle_prover_job_from_job_queue(&mut self) -> QueryResult<Option<ProverJob>> {
+    /// Takes the next job for the prover and leases it for `lease_duration`.
+    ///
+    /// Jobs whose lease has expired are given out right away, as their provers are considered gone.
+    /// Failed jobs are not given out until their retry backoff expires. Unreliable provers
+    /// don't receive jobs until some time passes since their last failure.
+    pub async fn get_idle_prover_job_from_job_queue(
+        &mut self,
+        prover_name: &str,
+        lease_duration: Duration,
+    ) -> QueryResult<Option<ProverJob>> {
         let start = Instant::now();
+        if let Some(stats) = self.load_prover_stats(prover_name).await? {
+            if is_unreliable_prover(&stats) {
+                metrics::increment_counter!("unreliable_prover_requests");
+                return Ok(None);
+            }
+        }
+
         // Select the block to prove.
         let mut transaction = self.0.start_transaction().await?;
 
@@ -92,34 +150,53 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             StorageProverJobQueue,
             r#"
                 SELECT * FROM prover_job_queue
-                WHERE job_status = $1
+                WHERE (job_status = $1 AND (retry_after IS NULL OR retry_after <= now()))
+                    OR (job_status = $2 AND lease_expires_at <= now())
                 ORDER BY (job_priority, id, first_block)
                 FOR UPDATE SKIP LOCKED
                 LIMIT 1
             "#,
-            ProverJobStatus::Idle.to_number()
+            ProverJobStatus::Idle.to_number(),
+            ProverJobStatus::InProgress.to_number()
         )
         .fetch_optional(transaction.conn())
         .await?;
 
         let prover_job = if let Some(job) = prover_job_queue {
-            sqlx::query!(
+            if job.job_status == ProverJobStatus::InProgress.to_number() {
+                vlog::info!(
+                    "Lease of the job {} given to the prover '{}' has expired",
+                    job.id,
+                    job.prover_name.as_deref().unwrap_or_default()
+                );
+                metrics::increment_counter!("expired_job_leases");
+            }
+            let lease = sqlx::query!(
                 r#"
                 UPDATE prover_job_queue
-                SET (job_status, updated_at, updated_by) = ($1, now(), 'server_give_job')
-                WHERE id = $2;
+                SET (job_status, updated_at, updated_by, prover_name, started_at, lease_expires_at) =
+                    ($1, now(), 'server_give_job', $2, now(), now() + $3::float8 * INTERVAL '1 second')
+                WHERE id = $4
+                RETURNING lease_expires_at AS "lease_expires_at!"
             "#,
                 ProverJobStatus::InProgress.to_number(),
+                prover_name,
+                lease_duration.as_secs_f64(),
                 job.id,
             )
-            .execute(transaction.conn())
+            .fetch_one(transaction.conn())
             .await?;
 
+            let job_data = transaction
+                .prover_schema()
+                .load_blob(job.job_data, job.blob_key, job.blob_checksum)
+                .await?;
             Some(ProverJob::new(
                 job.id,
                 BlockNumber(job.first_block as u32),
                 BlockNumber(job.last_block as u32),
-                job.job_data,
+                job_data,
+                lease.lease_expires_at,
             ))
         } else {
             None
@@ -129,34 +206,41 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(prover_job)
     }
 
-    /// Updates the state of ongoing prover job.
+    /// Extends the lease of the job by `lease_duration` from now.
+    ///
+    /// Returns the new lease deadline, or `None` if the job is not leased to the prover anymore,
+    /// i.e. it was given to another prover or finished.
     pub async fn record_prover_is_working(
         &mut self,
         job_id: i32,
         prover_name: &str,
-    ) -> QueryResult<()> {
+        lease_duration: Duration,
+    ) -> QueryResult<Option<DateTime<Utc>>> {
         let start = Instant::now();
-        sqlx::query!(
-            "UPDATE prover_job_queue
-            SET (updated_at, updated_by) = (now(), $1)
-            WHERE id = $2",
-            prover_name.to_string(),
+        let lease = sqlx::query!(
+            r#"UPDATE prover_job_queue
+            SET (updated_at, updated_by, lease_expires_at) = (now(), $1, now() + $2::float8 * INTERVAL '1 second')
+            WHERE id = $3 AND job_status = $4 AND prover_name = $1
+            RETURNING lease_expires_at AS "lease_expires_at!""#,
+            prover_name,
+            lease_duration.as_secs_f64(),
             job_id,
+            ProverJobStatus::InProgress.to_number()
         )
-        .execute(self.0.conn())
+        .fetch_optional(self.0.conn())
         .await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_is_working");
-        Ok(())
+        Ok(lease.map(|lease| lease.lease_expires_at))
     }
 
-    /// Marks the prover as stopped.
+    /// Marks the prover as stopped, returning the jobs leased to it to the queue.
     pub async fn record_prover_stop(&mut self, prover_name: &str) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
             "UPDATE prover_job_queue
             SET (updated_at, job_status) = (now(), $1)
-            WHERE updated_by = $2 and job_status = $3",
+            WHERE prover_name = $2 and job_status = $3",
             ProverJobStatus::Idle.to_number(),
             prover_name,
             ProverJobStatus::InProgress.to_number()
@@ -168,36 +252,336 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
-    /// Stores the proof for a block.
-    pub async fn store_proof(
+    /// Records the failure of the job and returns it to the queue.
+    ///
+    /// The job is given out again only after the backoff, which doubles with every failure
+    /// of the job. The failure is accounted in the statistics of the prover. Jobs that are
+    /// not in progress or are leased to another prover are not affected.
+    pub async fn record_job_failure(
         &mut self,
         job_id: i32,
-        block_number: BlockNumber,
-        proof: &SingleProof,
+        prover_name: &str,
+        error: &str,
+        retry_backoff: Duration,
     ) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
-        let updated_rows = sqlx::query!(
+        let failed_job = sqlx::query!(
+            "UPDATE prover_job_queue
+            SET (updated_at, job_status, updated_by, retries, last_error, retry_after) = (
+                now(), $1, 'server_job_failed', retries + 1, $2,
+                now() + $3::float8 * POWER(2::float8, LEAST(retries, $4)) * INTERVAL '1 second'
+            )
+            WHERE id = $5 AND job_status = $6 AND prover_name = $7
+            RETURNING prover_name",
+            ProverJobStatus::Idle.to_number(),
+            error,
+            retry_backoff.as_secs_f64(),
+            MAX_RETRY_BACKOFF_EXPONENT,
+            job_id,
+            ProverJobStatus::InProgress.to_number(),
+            prover_name
+        )
+        .fetch_optional(transaction.conn())
+        .await?;
+
+        if let Some(prover_name) = failed_job.and_then(|job| job.prover_name) {
+            sqlx::query!(
+                "INSERT INTO prover_stats (prover_name, failures, consecutive_failures, last_failure_at)
+                VALUES ($1, 1, 1, now())
+                ON CONFLICT (prover_name) DO UPDATE
+                SET (failures, consecutive_failures, last_failure_at, updated_at) = (
+                    prover_stats.failures + 1, prover_stats.consecutive_failures + 1, now(), now()
+                )",
+                prover_name
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "record_job_failure");
+        Ok(())
+    }
+
+    /// Marks the job as done and accounts it in the statistics of the prover that has finished it.
+    ///
+    /// The results of the provers whose lease has expired are still accepted unless another
+    /// prover has already finished the job, in which case `JobAlreadyFinished` is returned.
+    async fn finish_job(
+        &mut self,
+        job_id: i32,
+        job_type: ProverJobType,
+        prover_name: &str,
+    ) -> QueryResult<()> {
+        let job = sqlx::query!(
+            "SELECT job_status FROM prover_job_queue
+            WHERE id = $1 AND job_type = $2
+            FOR UPDATE",
+            job_id,
+            job_type.to_string()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .ok_or_else(|| match job_type {
+            ProverJobType::SingleProof => format_err!("Missing job for stored proof"),
+            ProverJobType::AggregatedProof => {
+                format_err!("Missing job for stored aggregated proof")
+            }
+        })?;
+        if job.job_status == ProverJobStatus::Done.to_number() {
+            return Err(JobAlreadyFinished { job_id }.into());
+        }
+
+        sqlx::query!(
             "UPDATE prover_job_queue
             SET (updated_at, job_status, updated_by) = (now(), $1, 'server_finish_job')
-            WHERE id = $2 AND job_type = $3",
+            WHERE id = $2",
             ProverJobStatus::Done.to_number(),
+            job_id
+        )
+        .execute(self.0.conn())
+        .await?;
+        self.record_job_success(job_id, prover_name).await
+    }
+
+    /// Accounts the finished job in the statistics of the prover that has finished it.
+    ///
+    /// The proving time is known only if the job is still leased to this prover, the late
+    /// results of the provers whose lease has been taken over are counted without it.
+    async fn record_job_success(&mut self, job_id: i32, prover_name: &str) -> QueryResult<()> {
+        sqlx::query!(
+            "INSERT INTO prover_stats (prover_name, successes, total_proving_time)
+            SELECT $2, 1, CASE
+                WHEN prover_name = $2 AND started_at IS NOT NULL
+                THEN (EXTRACT(EPOCH FROM now() - started_at) * 1000)::BIGINT
+                ELSE 0
+            END
+            FROM prover_job_queue
+            WHERE id = $1
+            ON CONFLICT (prover_name) DO UPDATE
+            SET (successes, consecutive_failures, total_proving_time, updated_at) = (
+                prover_stats.successes + 1,
+                0,
+                prover_stats.total_proving_time + EXCLUDED.total_proving_time,
+                now()
+            )",
             job_id,
+            prover_name
+        )
+        .execute(self.0.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Loads the statistics of the jobs processed by the prover.
+    pub async fn load_prover_stats(
+        &mut self,
+        prover_name: &str,
+    ) -> QueryResult<Option<StorageProverStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StorageProverStats,
+            "SELECT * FROM prover_stats WHERE prover_name = $1",
+            prover_name
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_prover_stats");
+        Ok(stats)
+    }
+
+    /// Loads the jobs that are currently being proven.
+    pub async fn load_active_jobs(&mut self) -> QueryResult<Vec<StorageActiveProverJob>> {
+        let start = Instant::now();
+        let jobs = sqlx::query_as!(
+            StorageActiveProverJob,
+            "SELECT id, job_type, first_block, last_block, prover_name, started_at, updated_at, retries
+            FROM prover_job_queue
+            WHERE job_status = $1
+            ORDER BY id",
+            ProverJobStatus::InProgress.to_number()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_active_jobs");
+        Ok(jobs)
+    }
+
+    /// Returns the amount of the unfinished jobs and their block ranges by job type and status.
+    pub async fn load_job_queue_depth(&mut self) -> QueryResult<Vec<StorageProverJobQueueDepth>> {
+        let start = Instant::now();
+        let depth = sqlx::query_as!(
+            StorageProverJobQueueDepth,
+            r#"
+            SELECT job_type, job_status, COUNT(*) AS "jobs!",
+                MIN(first_block) AS "first_block!", MAX(last_block) AS "last_block!"
+            FROM prover_job_queue
+            WHERE job_status != $1
+            GROUP BY job_type, job_status
+            ORDER BY job_type, job_status
+            "#,
+            ProverJobStatus::Done.to_number()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_job_queue_depth");
+        Ok(depth)
+    }
+
+    /// Returns the hourly amount of the finished jobs and proven blocks since the given time.
+    /// Hours are UTC ones regardless of the session time zone.
+    pub async fn load_throughput(
+        &mut self,
+        since: DateTime<Utc>,
+    ) -> QueryResult<Vec<StorageProverThroughput>> {
+        let start = Instant::now();
+        let throughput = sqlx::query_as!(
+            StorageProverThroughput,
+            r#"
+            SELECT date_trunc('hour', updated_at AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS "period!",
+                job_type, COUNT(*) AS "jobs!",
+                SUM(last_block - first_block + 1)::BIGINT AS "blocks!",
+                AVG(EXTRACT(EPOCH FROM updated_at - started_at) * 1000)::BIGINT AS average_proving_time
+            FROM prover_job_queue
+            WHERE job_status = $1 AND updated_at >= $2
+            GROUP BY 1, 2
+            ORDER BY 1, 2
+            "#,
+            ProverJobStatus::Done.to_number(),
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_throughput");
+        Ok(throughput)
+    }
+
+    /// Returns the amount of the unfinished jobs by job type and size.
+    pub async fn load_pending_jobs_by_size(&mut self) -> QueryResult<Vec<StorageProverJobsBySize>> {
+        let start = Instant::now();
+        let jobs = sqlx::query_as!(
+            StorageProverJobsBySize,
+            r#"
+            SELECT q.job_type,
+                CASE WHEN q.job_type = $2 THEN COALESCE(b.block_size, 0)
+                    ELSE q.last_block - q.first_block + 1 END AS "size!",
+                COUNT(*) AS "jobs!",
+                NULL::BIGINT AS average_proving_time
+            FROM prover_job_queue q
+            LEFT JOIN blocks b ON b.number = q.first_block
+            WHERE q.job_status != $1
+            GROUP BY 1, 2
+            "#,
+            ProverJobStatus::Done.to_number(),
             ProverJobType::SingleProof.to_string()
         )
-        .execute(transaction.conn())
-        .await?
-        .rows_affected();
+        .fetch_all(self.0.conn())
+        .await?;
 
-        if updated_rows != 1 {
-            return Err(format_err!("Missing job for stored proof"));
-        }
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_pending_jobs_by_size");
+        Ok(jobs)
+    }
+
+    /// Returns the amount and the average proving time of the jobs finished since the given time,
+    /// by job type and size.
+    pub async fn load_proving_times_by_size(
+        &mut self,
+        since: DateTime<Utc>,
+    ) -> QueryResult<Vec<StorageProverJobsBySize>> {
+        let start = Instant::now();
+        let jobs = sqlx::query_as!(
+            StorageProverJobsBySize,
+            r#"
+            SELECT q.job_type,
+                CASE WHEN q.job_type = $2 THEN COALESCE(b.block_size, 0)
+                    ELSE q.last_block - q.first_block + 1 END AS "size!",
+                COUNT(*) AS "jobs!",
+                AVG(EXTRACT(EPOCH FROM q.updated_at - q.started_at) * 1000)::BIGINT AS average_proving_time
+            FROM prover_job_queue q
+            LEFT JOIN blocks b ON b.number = q.first_block
+            WHERE q.job_status = $1 AND q.started_at IS NOT NULL AND q.updated_at >= $3
+            GROUP BY 1, 2
+            "#,
+            ProverJobStatus::Done.to_number(),
+            ProverJobType::SingleProof.to_string(),
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_proving_times_by_size");
+        Ok(jobs)
+    }
+
+    /// Returns the amount of blocks created since the given time by block size.
+    pub async fn load_created_blocks_by_size(
+        &mut self,
+        since: DateTime<Utc>,
+    ) -> QueryResult<Vec<StorageBlocksBySize>> {
+        let start = Instant::now();
+        let blocks = sqlx::query_as!(
+            StorageBlocksBySize,
+            r#"
+            SELECT block_size, COUNT(*) AS "blocks!"
+            FROM blocks
+            WHERE timestamp >= $1
+            GROUP BY block_size
+            "#,
+            since.timestamp()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_created_blocks_by_size");
+        Ok(blocks)
+    }
+
+    /// Loads the statistics of all the provers that have ever processed a job.
+    pub async fn load_all_prover_stats(&mut self) -> QueryResult<Vec<StorageProverStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StorageProverStats,
+            "SELECT * FROM prover_stats ORDER BY prover_name"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_all_prover_stats");
+        Ok(stats)
+    }
+
+    /// Stores the proof for a block created by the prover.
+    pub async fn store_proof(
+        &mut self,
+        job_id: i32,
+        block_number: BlockNumber,
+        proof: &SingleProof,
+        prover_name: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        transaction
+            .prover_schema()
+            .finish_job(job_id, ProverJobType::SingleProof, prover_name)
+            .await?;
 
+        let proof = serde_json::to_value(proof).unwrap();
+        let blob = transaction
+            .prover_schema()
+            .save_blob(&format!("proofs/{}", block_number), &proof.to_string())
+            .await?;
         sqlx::query!(
-            "INSERT INTO proofs (block_number, proof)
-            VALUES ($1, $2)",
+            "INSERT INTO proofs (block_number, proof, blob_key, blob_checksum)
+            VALUES ($1, $2, $3, $4)",
             i64::from(*block_number),
-            serde_json::to_value(proof).unwrap()
+            if blob.is_some() { None } else { Some(proof) },
+            blob.as_ref().map(|blob| blob.key.clone()),
+            blob.as_ref().map(|blob| blob.checksum.clone())
         )
         .execute(transaction.conn())
         .await?;
@@ -239,38 +623,38 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         }
         Ok(())
     }
-    /// Stores the aggregated proof for blocks.
+    /// Stores the aggregated proof for blocks created by the prover.
     pub async fn store_aggregated_proof(
         &mut self,
         job_id: i32,
         first_block: BlockNumber,
         last_block: BlockNumber,
         proof: &AggregatedProof,
+        prover_name: &str,
     ) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
-        let updated_rows = sqlx::query!(
-            "UPDATE prover_job_queue
-            SET (updated_at, job_status, updated_by) = (now(), $1, 'server_finish_job')
-            WHERE id = $2 AND job_type = $3",
-            ProverJobStatus::Done.to_number(),
-            job_id,
-            ProverJobType::AggregatedProof.to_string()
-        )
-        .execute(transaction.conn())
-        .await?
-        .rows_affected() as usize;
-
-        if updated_rows != 1 {
-            return Err(format_err!("Missing job for stored aggregated proof"));
-        }
+        transaction
+            .prover_schema()
+            .finish_job(job_id, ProverJobType::AggregatedProof, prover_name)
+            .await?;
 
+        let proof = serde_json::to_value(proof).unwrap();
+        let blob = transaction
+            .prover_schema()
+            .save_blob(
+                &format!("aggregated_proofs/{}_{}", first_block, last_block),
+                &proof.to_string(),
+            )
+            .await?;
         sqlx::query!(
-            "INSERT INTO aggregated_proofs (first_block, last_block, proof)
-            VALUES ($1, $2, $3)",
+            "INSERT INTO aggregated_proofs (first_block, last_block, proof, blob_key, blob_checksum)
+            VALUES ($1, $2, $3, $4, $5)",
             i64::from(*first_block),
             i64::from(*last_block),
-            serde_json::to_value(proof).unwrap()
+            if blob.is_some() { None } else { Some(proof) },
+            blob.as_ref().map(|blob| blob.key.clone()),
+            blob.as_ref().map(|blob| blob.checksum.clone())
         )
         .execute(transaction.conn())
         .await?;
@@ -290,14 +674,22 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         block_number: BlockNumber,
     ) -> QueryResult<Option<SingleProof>> {
         let start = Instant::now();
-        let proof = sqlx::query_as!(
+        let stored = sqlx::query_as!(
             StoredProof,
             "SELECT * FROM proofs WHERE block_number = $1",
             i64::from(*block_number),
         )
         .fetch_optional(self.0.conn())
-        .await?
-        .map(|stored| serde_json::from_value(stored.proof).unwrap());
+        .await?;
+        let proof = match stored {
+            Some(stored) => {
+                let proof = self
+                    .load_blob(stored.proof, stored.blob_key, stored.blob_checksum)
+                    .await?;
+                Some(serde_json::from_value(proof).unwrap())
+            }
+            None => None,
+        };
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "load_proof");
         Ok(proof)
@@ -310,15 +702,23 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         last_block: BlockNumber,
     ) -> QueryResult<Option<AggregatedProof>> {
         let start = Instant::now();
-        let proof = sqlx::query_as!(
+        let stored = sqlx::query_as!(
             StoredAggregatedProof,
             "SELECT * FROM aggregated_proofs WHERE first_block = $1 and last_block = $2",
             i64::from(*first_block),
             i64::from(*last_block)
         )
         .fetch_optional(self.0.conn())
-        .await?
-        .map(|stored| serde_json::from_value(stored.proof).unwrap());
+        .await?;
+        let proof = match stored {
+            Some(stored) => {
+                let proof = self
+                    .load_blob(stored.proof, stored.blob_key, stored.blob_checksum)
+                    .await?;
+                Some(serde_json::from_value(proof).unwrap())
+            }
+            None => None,
+        };
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "load_aggregated_proof");
         Ok(proof)
@@ -332,13 +732,22 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
     ) -> QueryResult<()> {
         let start = Instant::now();
         let witness_str = serde_json::to_string(&witness).expect("Failed to serialize witness");
+        let blob = self
+            .save_blob(&format!("witnesses/{}", block), &witness_str)
+            .await?;
         sqlx::query!(
-            "INSERT INTO block_witness (block, witness)
-            VALUES ($1, $2)
+            "INSERT INTO block_witness (block, witness, blob_key, blob_checksum)
+            VALUES ($1, $2, $3, $4)
             ON CONFLICT (block)
             DO NOTHING",
             i64::from(*block),
-            witness_str
+            if blob.is_some() {
+                None
+            } else {
+                Some(witness_str)
+            },
+            blob.as_ref().map(|blob| blob.key.clone()),
+            blob.as_ref().map(|blob| blob.checksum.clone())
         )
         .execute(self.0.conn())
         .await?;
@@ -360,10 +769,213 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
+        let witness = match block_witness {
+            Some(w) => {
+                let witness = w
+                    .witness
+                    .map(|w| serde_json::from_str(&w).expect("Failed to deserialize witness"));
+                Some(self.load_blob(witness, w.blob_key, w.blob_checksum).await?)
+            }
+            None => None,
+        };
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "get_witness");
-        Ok(block_witness
-            .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness")))
+        Ok(witness)
+    }
+
+    /// Saves the JSON blob to the blob storage if it's configured, so the database keeps
+    /// only the returned pointer instead of the blob itself.
+    async fn save_blob(&mut self, prefix: &str, blob: &str) -> QueryResult<Option<BlobPointer>> {
+        match self.0.blob_storage() {
+            Some(storage) => Ok(Some(
+                BlobPointer::save(storage.as_ref(), prefix, blob.as_bytes()).await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the JSON blob kept either in the database or in the blob storage.
+    async fn load_blob(
+        &mut self,
+        value: Option<serde_json::Value>,
+        blob_key: Option<String>,
+        blob_checksum: Option<String>,
+    ) -> QueryResult<serde_json::Value> {
+        if let Some(value) = value {
+            return Ok(value);
+        }
+        let pointer = BlobPointer::from_db(blob_key, blob_checksum).ok_or_else(|| {
+            format_err!("Blob is missing both in the database and the blob storage")
+        })?;
+        let storage = self.0.blob_storage();
+        let blob = pointer.load(storage.as_deref()).await?;
+        Ok(serde_json::from_slice(&blob)?)
+    }
+
+    /// Moves up to `limit` witnesses from the database to the blob storage.
+    /// Returns the amount of the moved witnesses.
+    pub async fn move_witnesses_to_blob_storage(&mut self, limit: i64) -> QueryResult<usize> {
+        let start = Instant::now();
+        let witnesses = sqlx::query_as!(
+            StorageBlockWitness,
+            "SELECT * FROM block_witness WHERE witness IS NOT NULL ORDER BY block LIMIT $1",
+            limit
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        for witness in &witnesses {
+            let blob = self
+                .save_blob(
+                    &format!("witnesses/{}", witness.block),
+                    witness.witness.as_deref().unwrap_or_default(),
+                )
+                .await?
+                .ok_or_else(|| format_err!("Blob storage is not configured"))?;
+            sqlx::query!(
+                "UPDATE block_witness SET (witness, blob_key, blob_checksum) = (NULL, $1, $2)
+                WHERE block = $3",
+                blob.key,
+                blob.checksum,
+                witness.block
+            )
+            .execute(self.0.conn())
+            .await?;
+        }
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "move_witnesses_to_blob_storage");
+        Ok(witnesses.len())
+    }
+
+    /// Moves up to `limit` proofs from the database to the blob storage.
+    /// Returns the amount of the moved proofs.
+    pub async fn move_proofs_to_blob_storage(&mut self, limit: i64) -> QueryResult<usize> {
+        let start = Instant::now();
+        let proofs = sqlx::query_as!(
+            StoredProof,
+            "SELECT * FROM proofs WHERE proof IS NOT NULL ORDER BY block_number LIMIT $1",
+            limit
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        for proof in &proofs {
+            let blob = self
+                .save_blob(
+                    &format!("proofs/{}", proof.block_number),
+                    &proof
+                        .proof
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                )
+                .await?
+                .ok_or_else(|| format_err!("Blob storage is not configured"))?;
+            sqlx::query!(
+                "UPDATE proofs SET (proof, blob_key, blob_checksum) = (NULL, $1, $2)
+                WHERE block_number = $3",
+                blob.key,
+                blob.checksum,
+                proof.block_number
+            )
+            .execute(self.0.conn())
+            .await?;
+        }
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "move_proofs_to_blob_storage");
+        Ok(proofs.len())
+    }
+
+    /// Moves up to `limit` aggregated proofs from the database to the blob storage.
+    /// Returns the amount of the moved proofs.
+    pub async fn move_aggregated_proofs_to_blob_storage(
+        &mut self,
+        limit: i64,
+    ) -> QueryResult<usize> {
+        let start = Instant::now();
+        let proofs = sqlx::query_as!(
+            StoredAggregatedProof,
+            "SELECT * FROM aggregated_proofs WHERE proof IS NOT NULL
+            ORDER BY first_block, last_block LIMIT $1",
+            limit
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        for proof in &proofs {
+            let blob = self
+                .save_blob(
+                    &format!(
+                        "aggregated_proofs/{}_{}",
+                        proof.first_block, proof.last_block
+                    ),
+                    &proof
+                        .proof
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                )
+                .await?
+                .ok_or_else(|| format_err!("Blob storage is not configured"))?;
+            sqlx::query!(
+                "UPDATE aggregated_proofs SET (proof, blob_key, blob_checksum) = (NULL, $1, $2)
+                WHERE first_block = $3 AND last_block = $4",
+                blob.key,
+                blob.checksum,
+                proof.first_block,
+                proof.last_block
+            )
+            .execute(self.0.conn())
+            .await?;
+        }
+
+        metrics::histogram!(
+            "sql",
+            start.elapsed(),
+            "prover" => "move_aggregated_proofs_to_blob_storage"
+        );
+        Ok(proofs.len())
+    }
+
+    /// Moves the data of up to `limit` prover jobs from the database to the blob storage.
+    /// Returns the amount of the moved jobs.
+    pub async fn move_job_data_to_blob_storage(&mut self, limit: i64) -> QueryResult<usize> {
+        let start = Instant::now();
+        let jobs = sqlx::query!(
+            "SELECT id, job_type, first_block, last_block, job_data FROM prover_job_queue
+            WHERE job_data IS NOT NULL ORDER BY id LIMIT $1",
+            limit
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        for job in &jobs {
+            let blob = self
+                .save_blob(
+                    &format!(
+                        "prover_jobs/{}_{}_{}",
+                        job.job_type, job.first_block, job.last_block
+                    ),
+                    &job.job_data
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                )
+                .await?
+                .ok_or_else(|| format_err!("Blob storage is not configured"))?;
+            sqlx::query!(
+                "UPDATE prover_job_queue SET (job_data, blob_key, blob_checksum) = (NULL, $1, $2)
+                WHERE id = $3",
+                blob.key,
+                blob.checksum,
+                job.id
+            )
+            .execute(self.0.conn())
+            .await?;
+        }
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "move_job_data_to_blob_storage");
+        Ok(jobs.len())
     }
 
     pub async fn get_last_block_prover_job_queue(
@@ -408,7 +1020,8 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(result)
     }
 
-    // Removes witnesses for blocks with number greater than `last_block`
+    // Removes witnesses for blocks with number greater than `last_block`.
+    // The blobs are kept in the blob storage, since their removal couldn't be rolled back.
     pub async fn remove_witnesses(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
@@ -422,7 +1035,8 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
-    // Removes proofs for blocks with number greater than `last_block`
+    // Removes proofs for blocks with number greater than `last_block`.
+    // The blobs are kept in the blob storage, since their removal couldn't be rolled back.
     pub async fn remove_proofs(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
@@ -436,7 +1050,8 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
-    // Removes aggregated proofs for blocks with number greater than `last_block`
+    // Removes aggregated proofs for blocks with number greater than `last_block`.
+    // The blobs are kept in the blob storage, since their removal couldn't be rolled back.
     pub async fn remove_aggregated_proofs(&mut self, last_block: BlockNumber) -> QueryResult<()> {
         let start = Instant::now();
         sqlx::query!(
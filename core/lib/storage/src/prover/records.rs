@@ -20,19 +20,25 @@ pub struct NewProof {
     pub proof: serde_json::Value,
 }
 
+/// Stored proof, either kept in the database or in the blob storage.
 #[derive(Debug, FromRow)]
 pub struct StoredProof {
     pub block_number: i64,
-    pub proof: serde_json::Value,
+    pub proof: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
+    pub blob_key: Option<String>,
+    pub blob_checksum: Option<String>,
 }
 
+/// Stored aggregated proof, either kept in the database or in the blob storage.
 #[derive(Debug, FromRow)]
 pub struct StoredAggregatedProof {
     pub first_block: i64,
     pub last_block: i64,
-    pub proof: serde_json::Value,
+    pub proof: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
+    pub blob_key: Option<String>,
+    pub blob_checksum: Option<String>,
 }
 
 // Every time before a prover worker starts generating the proof, a prover run is recorded for monitoring purposes
@@ -50,10 +56,13 @@ pub struct IntegerNumber {
     pub integer_value: i64,
 }
 
+/// Stored witness, either kept in the database or in the blob storage.
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct StorageBlockWitness {
     pub block: i64,
-    pub witness: String,
+    pub witness: Option<String>,
+    pub blob_key: Option<String>,
+    pub blob_checksum: Option<String>,
 }
 
 #[derive(Debug, FromRow)]
@@ -67,5 +76,90 @@ pub struct StorageProverJobQueue {
     pub updated_at: DateTime<Utc>,
     pub first_block: i64,
     pub last_block: i64,
-    pub job_data: serde_json::Value,
+    pub job_data: Option<serde_json::Value>,
+    pub retries: i32,
+    pub last_error: Option<String>,
+    pub retry_after: Option<DateTime<Utc>>,
+    pub prover_name: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub lease_expires_at: Option<DateTime<Utc>>,
+    pub blob_key: Option<String>,
+    pub blob_checksum: Option<String>,
+}
+
+/// Statistics of the jobs processed by a prover.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StorageProverStats {
+    pub prover_name: String,
+    pub successes: i64,
+    pub failures: i64,
+    /// Failures since the last successful job.
+    pub consecutive_failures: i64,
+    /// Total time of the successful jobs in milliseconds.
+    pub total_proving_time: i64,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StorageProverStats {
+    /// Average time of the successful job in milliseconds.
+    pub fn average_proving_time(&self) -> Option<i64> {
+        if self.successes == 0 {
+            None
+        } else {
+            Some(self.total_proving_time / self.successes)
+        }
+    }
+}
+
+/// Job that is currently being proven, without the job data.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StorageActiveProverJob {
+    pub id: i32,
+    pub job_type: String,
+    pub first_block: i64,
+    pub last_block: i64,
+    pub prover_name: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    /// Time of the last heartbeat from the prover.
+    pub updated_at: DateTime<Utc>,
+    pub retries: i32,
+}
+
+/// Amount of the unfinished jobs of the same type and status along with their block range.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StorageProverJobQueueDepth {
+    pub job_type: String,
+    pub job_status: i32,
+    pub jobs: i64,
+    pub first_block: i64,
+    pub last_block: i64,
+}
+
+/// Jobs of the same type finished within an hour.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StorageProverThroughput {
+    pub period: DateTime<Utc>,
+    pub job_type: String,
+    pub jobs: i64,
+    pub blocks: i64,
+    /// Average time of the job in milliseconds.
+    pub average_proving_time: Option<i64>,
+}
+
+/// Prover jobs of the same type and size: the block chunks for the single proofs
+/// and the amount of blocks for the aggregated ones.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StorageProverJobsBySize {
+    pub job_type: String,
+    pub size: i64,
+    pub jobs: i64,
+    /// Average time of the job in milliseconds, only set for the finished jobs.
+    pub average_proving_time: Option<i64>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StorageBlocksBySize {
+    pub block_size: i64,
+    pub blocks: i64,
 }
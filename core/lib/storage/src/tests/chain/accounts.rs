@@ -155,6 +155,36 @@ async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         );
     }
 
+    // Check that bulk lookups return the same states.
+    let account_ids: Vec<AccountId> = accounts_block.keys().copied().collect();
+    let snapshots = AccountSchema(&mut storage)
+        .account_snapshots_by_ids(&account_ids)
+        .await?;
+    assert_eq!(snapshots.len(), account_ids.len());
+    for snapshot in snapshots {
+        let (verified, committed) = AccountSchema(&mut storage)
+            .last_committed_state_for_account(snapshot.account_id)
+            .await?;
+        assert!(verified.1.is_none());
+        assert!(snapshot.verified.is_none());
+        assert_eq!(
+            snapshot.committed,
+            committed.map(|account| (BlockNumber(1), account))
+        );
+    }
+    let addresses: Vec<Address> = accounts_block
+        .values()
+        .map(|account| account.address)
+        .chain(std::iter::once(Address::repeat_byte(0xfe)))
+        .collect();
+    let ids_by_address = AccountSchema(&mut storage)
+        .account_ids_by_addresses(&addresses)
+        .await?;
+    assert_eq!(ids_by_address.len(), accounts_block.len());
+    for (account_id, account) in accounts_block.iter() {
+        assert_eq!(ids_by_address.get(&account.address), Some(account_id));
+    }
+
     // Now add a proof, verify block and apply a state update.
     OperationsSchema(&mut storage)
         .store_aggregated_action(gen_unique_aggregated_operation(
@@ -167,6 +197,16 @@ async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         .apply_state_update(BlockNumber(1))
         .await?;
 
+    let account_ids: Vec<AccountId> = accounts_block.keys().copied().collect();
+    let snapshots = AccountSchema(&mut storage)
+        .account_snapshots_by_ids(&account_ids)
+        .await?;
+    assert_eq!(snapshots.len(), account_ids.len());
+    for snapshot in snapshots {
+        assert!(snapshot.verified.is_some());
+        assert_eq!(snapshot.verified, snapshot.committed);
+    }
+
     // After that all the accounts should have a verified state.
     for (account_id, account) in accounts_block {
         let (last_finalized, _) = AccountSchema(&mut storage)
@@ -1,9 +1,11 @@
 // Built-in imports
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 // External imports
 // Workspace imports
 use zksync_api_types::v02::{
-    pagination::{AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery},
+    pagination::{
+        AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery, TxsSearchRequest,
+    },
     transaction::{Receipt, TxInBlockStatus},
 };
 use zksync_crypto::{franklin_crypto::bellman::pairing::ff::Field, Fr};
@@ -11,7 +13,7 @@ use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
     tx::TxHash,
-    AccountId, AccountUpdate, BlockNumber, ExecutedOperations, Nonce, ZkSyncOp, H256,
+    AccountId, AccountUpdate, BlockNumber, ExecutedOperations, Nonce, TokenId, ZkSyncOp, H256,
 };
 // Local imports
 use self::setup::TransactionsHistoryTestSetup;
@@ -588,6 +590,131 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
     Ok(())
 }
 
+/// Checks that the transactions search starts from the last matching transaction
+/// for the `latest` cursor in both directions.
+#[db_test]
+async fn search_transactions_from_latest(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let from = setup.from_zksync_account.address;
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+    commit_block(&mut storage, BlockNumber(2)).await?;
+
+    let request = |direction| PaginationQuery {
+        from: TxsSearchRequest {
+            tx_hash: "latest".parse().unwrap(),
+            from: Some(from),
+            to: None,
+            token: None,
+            tx_type: None,
+            from_block: None,
+            to_block: None,
+        },
+        limit: 2,
+        direction,
+    };
+    let older: Vec<TxHash> = storage
+        .chain()
+        .operations_ext_schema()
+        .search_transactions(&request(PaginationDirection::Older))
+        .await?
+        .unwrap()
+        .into_iter()
+        .map(|tx| tx.tx_hash)
+        .collect();
+    assert_eq!(older.len(), 2);
+    // There are no newer transactions than the latest one.
+    let newer: Vec<TxHash> = storage
+        .chain()
+        .operations_ext_schema()
+        .search_transactions(&request(PaginationDirection::Newer))
+        .await?
+        .unwrap()
+        .into_iter()
+        .map(|tx| tx.tx_hash)
+        .collect();
+    assert_eq!(newer, vec![older[0]]);
+
+    Ok(())
+}
+
+/// Searches the transactions of the token starting from the given one in the older direction.
+async fn search_token_transactions(
+    storage: &mut StorageProcessor<'_>,
+    token: TokenId,
+    tx_hash: ApiEither<TxHash>,
+    limit: u32,
+) -> QueryResult<Vec<TxHash>> {
+    let query = PaginationQuery {
+        from: TxsSearchRequest {
+            tx_hash,
+            from: None,
+            to: None,
+            token: Some(token),
+            tx_type: None,
+            from_block: None,
+            to_block: None,
+        },
+        limit,
+        direction: PaginationDirection::Older,
+    };
+    let txs = storage
+        .chain()
+        .operations_ext_schema()
+        .search_transactions(&query)
+        .await?
+        .unwrap();
+    Ok(txs.into_iter().map(|tx| tx.tx_hash).collect())
+}
+
+/// Checks the transactions search with only the token filter set,
+/// which should page through the transactions without duplicates and count them.
+#[db_test]
+async fn search_transactions_by_token(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+    commit_block(&mut storage, BlockNumber(2)).await?;
+    let token = setup.tokens[1].id;
+
+    let all_txs =
+        search_token_transactions(&mut storage, token, "latest".parse().unwrap(), 100).await?;
+    assert!(!all_txs.is_empty());
+    let unique_txs: HashSet<_> = all_txs.iter().collect();
+    assert_eq!(unique_txs.len(), all_txs.len());
+
+    let count = storage
+        .chain()
+        .operations_ext_schema()
+        .search_transactions_count(&TxsSearchRequest {
+            tx_hash: "latest".parse().unwrap(),
+            from: None,
+            to: None,
+            token: Some(token),
+            tx_type: None,
+            from_block: None,
+            to_block: None,
+        })
+        .await?;
+    assert_eq!(count as usize, all_txs.len());
+
+    // Pages start from the cursor (inclusive) and follow the order of the whole list.
+    let first_page =
+        search_token_transactions(&mut storage, token, "latest".parse().unwrap(), 1).await?;
+    assert_eq!(first_page, all_txs[..1]);
+    if all_txs.len() > 1 {
+        let next_page =
+            search_token_transactions(&mut storage, token, ApiEither::from(all_txs[1]), 2).await?;
+        assert_eq!(next_page, all_txs[1..all_txs.len().min(3)]);
+    }
+
+    Ok(())
+}
+
 /// Test `get_tx_created_at_and_block_number` method
 #[db_test]
 async fn get_tx_sequnecner_id(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -0,0 +1,123 @@
+// External imports
+use chrono::{Duration, TimeZone, Utc};
+// Workspace imports
+use zksync_api_types::v02::stats::StatsInterval;
+use zksync_types::BlockNumber;
+// Local imports
+use super::operations_ext::{
+    commit_block, commit_schema_data, setup::TransactionsHistoryTestSetup, verify_block,
+};
+use crate::{
+    tests::{db_test, ACCOUNT_MUTEX},
+    QueryResult, StorageProcessor,
+};
+
+/// Checks that the statistics summary is updated only with the finalized blocks
+/// and every block is taken into account exactly once.
+#[db_test]
+async fn stats_summary(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+    for block_number in 1..=2 {
+        commit_block(&mut storage, BlockNumber(block_number)).await?;
+    }
+    verify_block(&mut storage, BlockNumber(1)).await?;
+
+    let (from, to) = (
+        Utc::now() - Duration::days(1),
+        Utc::now() + Duration::days(1),
+    );
+    let ops_in_block = setup.blocks[0].block_transactions.len() as i64;
+
+    // Only the finalized block is processed.
+    let processed = storage
+        .chain()
+        .stats_schema()
+        .update_stats_summary(10)
+        .await?;
+    assert_eq!(processed, 1);
+    let processed = storage
+        .chain()
+        .stats_schema()
+        .update_stats_summary(10)
+        .await?;
+    assert_eq!(processed, 0);
+
+    let tx_counts = storage
+        .chain()
+        .stats_schema()
+        .tx_counts_stats(StatsInterval::Day, from, to)
+        .await?;
+    let total: i64 = tx_counts.iter().map(|record| record.count).sum();
+    assert_eq!(total, ops_in_block);
+
+    verify_block(&mut storage, BlockNumber(2)).await?;
+    let processed = storage
+        .chain()
+        .stats_schema()
+        .update_stats_summary(10)
+        .await?;
+    assert_eq!(processed, 1);
+
+    let tx_counts = storage
+        .chain()
+        .stats_schema()
+        .tx_counts_stats(StatsInterval::Hour, from, to)
+        .await?;
+    let total: i64 = tx_counts.iter().map(|record| record.count).sum();
+    assert_eq!(total, 2 * ops_in_block);
+
+    let blocks = storage
+        .chain()
+        .stats_schema()
+        .blocks_stats(StatsInterval::Day, from, to)
+        .await?;
+    let blocks_count: i64 = blocks.iter().map(|record| record.blocks_count).sum();
+    assert_eq!(blocks_count, 2);
+    assert!(blocks
+        .iter()
+        .all(|record| record.chunks_used <= record.chunks_total));
+
+    // Both accounts from the test setup are active, but each of them is counted only once.
+    let active_accounts = storage
+        .chain()
+        .stats_schema()
+        .active_accounts_stats(StatsInterval::Day, from, to)
+        .await?;
+    assert!(active_accounts.iter().all(|record| record.count <= 2));
+
+    Ok(())
+}
+
+/// Checks that the statistics are grouped into UTC periods regardless of the session time zone.
+#[db_test]
+async fn stats_periods_are_utc(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    sqlx::query("SET LOCAL TIME ZONE 'Asia/Kolkata'")
+        .execute(storage.conn())
+        .await?;
+    // Both hours belong to the same UTC day, but to different days in UTC+05:30.
+    let day = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+    for hour in [17, 20].iter() {
+        sqlx::query("INSERT INTO stats_tx_counts (period, tx_type, count) VALUES ($1, $2, $3)")
+            .bind(day + Duration::hours(*hour))
+            .bind("Transfer")
+            .bind(1i64)
+            .execute(storage.conn())
+            .await?;
+    }
+
+    let tx_counts = storage
+        .chain()
+        .stats_schema()
+        .tx_counts_stats(StatsInterval::Day, day, day + Duration::days(1))
+        .await?;
+    assert_eq!(tx_counts.len(), 1);
+    assert_eq!(tx_counts[0].period, day);
+    assert_eq!(tx_counts[0].count, 2);
+
+    Ok(())
+}
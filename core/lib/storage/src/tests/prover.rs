@@ -1,22 +1,64 @@
+// Built-in imports
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 // External imports
 use anyhow::format_err;
+use chrono::{TimeZone, Utc};
 use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 // Workspace imports
 use zksync_types::{
-    prover::{ProverJob, ProverJobType},
+    prover::{ProverJob, ProverJobStatus, ProverJobType},
     BlockNumber,
 };
 // Local imports
+use crate::blob_storage::{blob_checksum, BlobStorage, LocalBlobStorage};
 use crate::test_data::{gen_sample_block, get_sample_aggregated_proof, get_sample_single_proof};
 use crate::tests::db_test;
-use crate::{prover::ProverSchema, QueryResult, StorageProcessor};
+use crate::{
+    prover::{JobAlreadyFinished, ProverSchema},
+    QueryResult, StorageProcessor,
+};
 
 static MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
+const LEASE_DURATION: Duration = Duration::from_secs(60);
+
+/// Blob storage in a temporary directory, which is removed when the storage is dropped.
+struct TempBlobStorage {
+    root: PathBuf,
+    storage: Arc<dyn BlobStorage>,
+}
+
+impl TempBlobStorage {
+    fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!(
+            "zksync_blob_storage_{}_{}_{}",
+            name,
+            std::process::id(),
+            Utc::now().timestamp_nanos()
+        ));
+        Self {
+            storage: Arc::new(LocalBlobStorage::new(root.clone())),
+            root,
+        }
+    }
+
+    fn storage(&self) -> Arc<dyn BlobStorage> {
+        self.storage.clone()
+    }
+}
+
+impl Drop for TempBlobStorage {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
 async fn get_idle_job_from_queue(storage: &mut StorageProcessor<'_>) -> QueryResult<ProverJob> {
     let job = ProverSchema(storage)
-        .get_idle_prover_job_from_job_queue()
+        .get_idle_prover_job_from_job_queue("test_prover", LEASE_DURATION)
         .await?;
 
     job.ok_or_else(|| format_err!("expect idle job from job queue"))
@@ -55,10 +97,16 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
     let aggregated_proof = get_sample_aggregated_proof();
 
     let stored_proof = ProverSchema(storage)
-        .store_proof(1, BlockNumber(1), &proof)
+        .store_proof(1, BlockNumber(1), &proof, "test_prover")
         .await;
     let stored_aggregated_proof = ProverSchema(storage)
-        .store_aggregated_proof(1, BlockNumber(1), BlockNumber(1), &aggregated_proof)
+        .store_aggregated_proof(
+            1,
+            BlockNumber(1),
+            BlockNumber(1),
+            &aggregated_proof,
+            "test_prover",
+        )
         .await;
 
     assert!(stored_proof
@@ -102,7 +150,7 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
 
     // Store proofs.
     let stored_proof = ProverSchema(storage)
-        .store_proof(stored_job_id, BlockNumber(1), &proof)
+        .store_proof(stored_job_id, BlockNumber(1), &proof, "test_prover")
         .await;
     let stored_aggregated_proof = ProverSchema(storage)
         .store_aggregated_proof(
@@ -110,6 +158,7 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
             BlockNumber(1),
             BlockNumber(1),
             &aggregated_proof,
+            "test_prover",
         )
         .await;
 
@@ -183,13 +232,13 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
 
     // Record prover is working and stopped it.
     ProverSchema(storage)
-        .record_prover_is_working(first_job.job_id, "test_prover")
+        .record_prover_is_working(first_job.job_id, "test_prover", LEASE_DURATION)
         .await?;
     ProverSchema(storage)
-        .record_prover_is_working(second_job.job_id, "test_prover")
+        .record_prover_is_working(second_job.job_id, "test_prover", LEASE_DURATION)
         .await?;
     ProverSchema(storage)
-        .record_prover_is_working(third_job.job_id, "test_prover")
+        .record_prover_is_working(third_job.job_id, "test_prover", LEASE_DURATION)
         .await?;
 
     // Store one proof and then turn off the prover.
@@ -198,6 +247,7 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
             third_job.job_id,
             third_job.first_block,
             &get_sample_single_proof(),
+            "test_prover",
         )
         .await?;
     let jobs_count = ProverSchema(storage).pending_jobs_count().await?;
@@ -213,6 +263,493 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
     Ok(())
 }
 
+/// Checks that the jobs of the stopped prover are returned to the queue right away,
+/// even if the prover has not reported that it's working on them yet.
+#[db_test]
+async fn test_record_prover_stop(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    for block in 1..=2 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block),
+                BlockNumber(block),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    let other_job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("another_prover", LEASE_DURATION)
+        .await?
+        .expect("the second job should be available");
+
+    ProverSchema(&mut storage)
+        .record_prover_stop("test_prover")
+        .await?;
+
+    // Only the job of the stopped prover is given out again.
+    let reassigned_job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("third_prover", LEASE_DURATION)
+        .await?
+        .expect("the job of the stopped prover should be available");
+    assert_eq!(reassigned_job.job_id, job.job_id);
+    assert_ne!(reassigned_job.job_id, other_job.job_id);
+    assert!(ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("third_prover", LEASE_DURATION)
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks that the failed job is returned to the queue after the backoff
+/// and the failure is accounted in the prover statistics.
+#[db_test]
+async fn test_record_job_failure(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(1),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+    let job = get_idle_job_from_queue(&mut storage).await?;
+
+    // Only the prover the job is assigned to can fail it.
+    ProverSchema(&mut storage)
+        .record_job_failure(
+            job.job_id,
+            "another_prover",
+            "out of memory",
+            Duration::from_secs(0),
+        )
+        .await?;
+    assert!(ProverSchema(&mut storage)
+        .load_prover_stats("test_prover")
+        .await?
+        .is_none());
+    assert!(ProverSchema(&mut storage)
+        .load_prover_stats("another_prover")
+        .await?
+        .is_none());
+
+    // The failed job is not given out until the backoff expires.
+    ProverSchema(&mut storage)
+        .record_job_failure(
+            job.job_id,
+            "test_prover",
+            "out of memory",
+            Duration::from_secs(3600),
+        )
+        .await?;
+    assert!(ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("test_prover", LEASE_DURATION)
+        .await?
+        .is_none());
+    let stats = ProverSchema(&mut storage)
+        .load_prover_stats("test_prover")
+        .await?
+        .expect("prover stats should be created");
+    assert_eq!(stats.failures, 1);
+    assert_eq!(stats.consecutive_failures, 1);
+    assert_eq!(stats.successes, 0);
+    assert!(stats.last_failure_at.is_some());
+
+    // Without the backoff, the same job can be taken again.
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(2),
+            BlockNumber(2),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    ProverSchema(&mut storage)
+        .record_job_failure(
+            job.job_id,
+            "test_prover",
+            "out of memory",
+            Duration::from_secs(0),
+        )
+        .await?;
+    let retried_job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(retried_job.job_id, job.job_id);
+
+    // Finished jobs are not affected.
+    ProverSchema(&mut storage)
+        .store_proof(
+            job.job_id,
+            job.first_block,
+            &get_sample_single_proof(),
+            "test_prover",
+        )
+        .await?;
+    ProverSchema(&mut storage)
+        .record_job_failure(
+            job.job_id,
+            "test_prover",
+            "out of memory",
+            Duration::from_secs(0),
+        )
+        .await?;
+    let stats = ProverSchema(&mut storage)
+        .load_prover_stats("test_prover")
+        .await?
+        .unwrap();
+    assert_eq!(stats.failures, 2);
+    assert_eq!(stats.successes, 1);
+    // The successful job resets the failures streak.
+    assert_eq!(stats.consecutive_failures, 0);
+    assert!(stats.average_proving_time().is_some());
+
+    Ok(())
+}
+
+/// Checks that the prover which keeps failing jobs doesn't receive new ones,
+/// even if it has finished a lot of jobs before.
+#[db_test]
+async fn test_unreliable_prover(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    sqlx::query("INSERT INTO prover_stats (prover_name, successes) VALUES ('test_prover', 100)")
+        .execute(storage.conn())
+        .await?;
+
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(1),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+
+    for _ in 0..3 {
+        let job = get_idle_job_from_queue(&mut storage).await?;
+        ProverSchema(&mut storage)
+            .record_job_failure(
+                job.job_id,
+                "test_prover",
+                "out of memory",
+                Duration::from_secs(0),
+            )
+            .await?;
+    }
+
+    assert!(ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("test_prover", LEASE_DURATION)
+        .await?
+        .is_none());
+    // The job is still available for other provers.
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("another_prover", LEASE_DURATION)
+        .await?;
+    assert!(job.is_some());
+
+    Ok(())
+}
+
+/// Checks that the job with an expired lease is given to another prover, while the late
+/// result of the previous prover is accepted only if the job is not finished yet.
+#[db_test]
+async fn test_job_lease(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    for block in 1..=2 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block),
+                BlockNumber(block),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+
+    // The leased job is not given to other provers.
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert!(job.lease_expires_at > Utc::now());
+    let next_job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("another_prover", LEASE_DURATION)
+        .await?
+        .expect("the second job should be available");
+    assert_ne!(next_job.job_id, job.job_id);
+    assert!(ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("another_prover", LEASE_DURATION)
+        .await?
+        .is_none());
+
+    // The heartbeat extends the lease of the prover holding the job only.
+    let lease = ProverSchema(&mut storage)
+        .record_prover_is_working(job.job_id, "test_prover", Duration::from_secs(0))
+        .await?
+        .expect("the job is leased to the prover");
+    assert!(lease <= job.lease_expires_at);
+    assert!(ProverSchema(&mut storage)
+        .record_prover_is_working(job.job_id, "another_prover", LEASE_DURATION)
+        .await?
+        .is_none());
+
+    // The lease has expired, so the job is given out right away.
+    let reassigned_job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("another_prover", LEASE_DURATION)
+        .await?
+        .expect("the job with expired lease should be available");
+    assert_eq!(reassigned_job.job_id, job.job_id);
+    assert!(ProverSchema(&mut storage)
+        .record_prover_is_working(job.job_id, "test_prover", LEASE_DURATION)
+        .await?
+        .is_none());
+
+    // The failure of the previous prover doesn't take the job from the new one.
+    ProverSchema(&mut storage)
+        .record_job_failure(
+            job.job_id,
+            "test_prover",
+            "out of memory",
+            Duration::from_secs(0),
+        )
+        .await?;
+    assert!(ProverSchema(&mut storage)
+        .load_prover_stats("test_prover")
+        .await?
+        .is_none());
+    assert!(ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("test_prover", LEASE_DURATION)
+        .await?
+        .is_none());
+
+    // The late proof is accepted and credited to the prover that has sent it,
+    // while the proof of the new prover is not.
+    ProverSchema(&mut storage)
+        .store_proof(
+            job.job_id,
+            job.first_block,
+            &get_sample_single_proof(),
+            "test_prover",
+        )
+        .await?;
+    let result = ProverSchema(&mut storage)
+        .store_proof(
+            job.job_id,
+            job.first_block,
+            &get_sample_single_proof(),
+            "another_prover",
+        )
+        .await;
+    let error = result.err().unwrap();
+    assert_eq!(
+        error
+            .downcast_ref::<JobAlreadyFinished>()
+            .expect("the job should be already finished")
+            .job_id,
+        job.job_id
+    );
+
+    let stats = ProverSchema(&mut storage)
+        .load_prover_stats("test_prover")
+        .await?
+        .expect("the late proof should be accounted");
+    assert_eq!(stats.successes, 1);
+    assert_eq!(stats.failures, 0);
+    // The job has been leased to another prover, so the proving time is unknown.
+    assert_eq!(stats.total_proving_time, 0);
+    assert!(ProverSchema(&mut storage)
+        .load_prover_stats("another_prover")
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks the queries used to report the state of the prover cluster.
+#[db_test]
+async fn test_prover_cluster_status(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    for block in 1..=3 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block),
+                BlockNumber(block),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+    let first_job = get_idle_job_from_queue(&mut storage).await?;
+    let second_job = get_idle_job_from_queue(&mut storage).await?;
+    ProverSchema(&mut storage)
+        .store_proof(
+            first_job.job_id,
+            first_job.first_block,
+            &get_sample_single_proof(),
+            "test_prover",
+        )
+        .await?;
+
+    let active_jobs = ProverSchema(&mut storage).load_active_jobs().await?;
+    assert_eq!(active_jobs.len(), 1);
+    assert_eq!(active_jobs[0].id, second_job.job_id);
+    assert_eq!(active_jobs[0].prover_name.as_deref(), Some("test_prover"));
+
+    let queue_depth = ProverSchema(&mut storage).load_job_queue_depth().await?;
+    assert_eq!(queue_depth.len(), 2);
+    for depth in queue_depth {
+        assert_eq!(depth.job_type, ProverJobType::SingleProof.to_string());
+        assert_eq!(depth.jobs, 1);
+        if depth.job_status == ProverJobStatus::InProgress.to_number() {
+            assert_eq!(depth.first_block, 2);
+        } else {
+            assert_eq!(depth.job_status, ProverJobStatus::Idle.to_number());
+            assert_eq!(depth.first_block, 3);
+        }
+    }
+
+    let throughput = ProverSchema(&mut storage)
+        .load_throughput(Utc::now() - chrono::Duration::hours(1))
+        .await?;
+    assert_eq!(throughput.len(), 1);
+    assert_eq!(throughput[0].jobs, 1);
+    assert_eq!(throughput[0].blocks, 1);
+
+    let stats = ProverSchema(&mut storage).load_all_prover_stats().await?;
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].successes, 1);
+
+    Ok(())
+}
+
+/// Checks that the prover throughput is grouped into UTC hours regardless of the session time zone.
+#[db_test]
+async fn test_prover_throughput_is_utc(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    sqlx::query("SET LOCAL TIME ZONE 'Asia/Kolkata'")
+        .execute(storage.conn())
+        .await?;
+    // Both jobs are finished within the same UTC hour, but in different hours in UTC+05:30.
+    let hour = Utc.ymd(2021, 1, 1).and_hms(17, 0, 0);
+    for (block, minutes) in [(1, 10), (2, 40)].iter() {
+        let finished_at = hour + chrono::Duration::minutes(*minutes);
+        sqlx::query(
+            "INSERT INTO prover_job_queue (job_status, job_priority, job_type, updated_by, first_block, last_block, job_data, updated_at, started_at)
+            VALUES ($1, 1, $2, 'test', $3, $3, 'null', $4, $4)",
+        )
+        .bind(ProverJobStatus::Done.to_number())
+        .bind(ProverJobType::SingleProof.to_string())
+        .bind(*block as i64)
+        .bind(finished_at)
+        .execute(storage.conn())
+        .await?;
+    }
+
+    let throughput = ProverSchema(&mut storage).load_throughput(hour).await?;
+    assert_eq!(throughput.len(), 1);
+    assert_eq!(throughput[0].period, hour);
+    assert_eq!(throughput[0].jobs, 2);
+
+    Ok(())
+}
+
+/// Checks the queries used to estimate the amount of required provers.
+#[db_test]
+async fn test_prover_jobs_by_size(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    for (block_number, block_size) in [(1, 10), (2, 32)] {
+        let mut block = gen_sample_block(BlockNumber(block_number), block_size, Default::default());
+        block.timestamp = Utc::now().timestamp() as u64;
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(block)
+            .await?;
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block_number),
+                BlockNumber(block_number),
+                Default::default(),
+                1,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(2),
+            Default::default(),
+            0,
+            ProverJobType::AggregatedProof,
+        )
+        .await?;
+
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(job.first_block, BlockNumber(1));
+    ProverSchema(&mut storage)
+        .store_proof(
+            job.job_id,
+            job.first_block,
+            &get_sample_single_proof(),
+            "test_prover",
+        )
+        .await?;
+
+    let mut pending = ProverSchema(&mut storage)
+        .load_pending_jobs_by_size()
+        .await?
+        .into_iter()
+        .map(|jobs| (jobs.job_type, jobs.size, jobs.jobs))
+        .collect::<Vec<_>>();
+    pending.sort();
+    assert_eq!(
+        pending,
+        vec![
+            (ProverJobType::AggregatedProof.to_string(), 2, 1),
+            (ProverJobType::SingleProof.to_string(), 32, 1),
+        ]
+    );
+
+    let since = Utc::now() - chrono::Duration::hours(1);
+    let proving_times = ProverSchema(&mut storage)
+        .load_proving_times_by_size(since)
+        .await?;
+    assert_eq!(proving_times.len(), 1);
+    assert_eq!(proving_times[0].size, 10);
+    assert!(proving_times[0].average_proving_time.is_some());
+
+    let mut created_blocks = ProverSchema(&mut storage)
+        .load_created_blocks_by_size(since)
+        .await?
+        .into_iter()
+        .map(|blocks| (blocks.block_size, blocks.blocks))
+        .collect::<Vec<_>>();
+    created_blocks.sort_unstable();
+    assert_eq!(created_blocks, vec![(10, 1), (32, 1)]);
+
+    Ok(())
+}
+
 /// Checks that the witness can be stored and loaded.
 #[db_test]
 async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -312,6 +849,231 @@ async fn test_remove_witnesses(mut storage: StorageProcessor<'_>) -> QueryResult
     Ok(())
 }
 
+/// Checks that the witnesses and proofs are kept in the blob storage if it's configured.
+#[db_test]
+async fn test_blob_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    let temp_storage = TempBlobStorage::new("store");
+    let blob_storage = temp_storage.storage();
+    storage.set_blob_storage(Some(blob_storage.clone()));
+
+    storage
+        .chain()
+        .block_schema()
+        .save_full_block(gen_sample_block(BlockNumber(1), 100, Default::default()))
+        .await?;
+    let witness = serde_json::to_value(String::from("test")).unwrap();
+    storage
+        .prover_schema()
+        .store_witness(BlockNumber(1), witness.clone())
+        .await?;
+    assert_eq!(
+        storage.prover_schema().get_witness(BlockNumber(1)).await?,
+        Some(witness.clone())
+    );
+
+    let proof = get_sample_single_proof();
+    let job_data = serde_json::json!({ "block": 1 });
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(1),
+            job_data.clone(),
+            0,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+    let stored_job_data: Option<serde_json::Value> =
+        sqlx::query_scalar("SELECT job_data FROM prover_job_queue WHERE first_block = 1")
+            .fetch_one(storage.conn())
+            .await?;
+    assert!(stored_job_data.is_none());
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(job.job_data, job_data);
+    let job_id = job.job_id;
+    ProverSchema(&mut storage)
+        .store_proof(job_id, BlockNumber(1), &proof, "test_prover")
+        .await?;
+    let loaded = ProverSchema(&mut storage)
+        .load_proof(BlockNumber(1))
+        .await?
+        .expect("Proof is not stored");
+    assert_eq!(
+        serde_json::to_value(loaded).unwrap(),
+        serde_json::to_value(&proof).unwrap()
+    );
+
+    // The blobs can't be loaded without the blob storage.
+    storage.set_blob_storage(None);
+    assert!(storage
+        .prover_schema()
+        .get_witness(BlockNumber(1))
+        .await
+        .is_err());
+    assert!(ProverSchema(&mut storage)
+        .load_proof(BlockNumber(1))
+        .await
+        .is_err());
+
+    // The corrupted blob is detected.
+    let witness_str = serde_json::to_string(&witness).unwrap();
+    let key = format!("witnesses/1-{}.json", blob_checksum(witness_str.as_bytes()));
+    blob_storage.put(&key, b"\"corrupted\"").await?;
+    storage.set_blob_storage(Some(blob_storage));
+    let err = storage
+        .prover_schema()
+        .get_witness(BlockNumber(1))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Checksum mismatch"), "{}", err);
+
+    Ok(())
+}
+
+/// Checks that the witnesses, proofs and job data kept in the database are moved to the blob storage.
+#[db_test]
+async fn test_move_to_blob_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    let witness = serde_json::to_value(String::from("test")).unwrap();
+    for block_number in 1..=3 {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                100,
+                Default::default(),
+            ))
+            .await?;
+        storage
+            .prover_schema()
+            .store_witness(BlockNumber(block_number), witness.clone())
+            .await?;
+    }
+    let aggregated_proof = get_sample_aggregated_proof();
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(3),
+            serde_json::Value::default(),
+            1,
+            ProverJobType::AggregatedProof,
+        )
+        .await?;
+    let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
+    ProverSchema(&mut storage)
+        .store_aggregated_proof(
+            job_id,
+            BlockNumber(1),
+            BlockNumber(3),
+            &aggregated_proof,
+            "test_prover",
+        )
+        .await?;
+
+    let job_data = serde_json::json!({ "block": 4 });
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(4),
+            BlockNumber(4),
+            job_data.clone(),
+            1,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+
+    // Moving fails if the blob storage is not configured.
+    storage.set_blob_storage(None);
+    assert!(storage
+        .prover_schema()
+        .move_witnesses_to_blob_storage(2)
+        .await
+        .is_err());
+
+    let temp_storage = TempBlobStorage::new("move");
+    storage.set_blob_storage(Some(temp_storage.storage()));
+    assert_eq!(
+        storage
+            .prover_schema()
+            .move_witnesses_to_blob_storage(2)
+            .await?,
+        2
+    );
+    assert_eq!(
+        storage
+            .prover_schema()
+            .move_witnesses_to_blob_storage(2)
+            .await?,
+        1
+    );
+    assert_eq!(
+        storage
+            .prover_schema()
+            .move_witnesses_to_blob_storage(2)
+            .await?,
+        0
+    );
+    assert_eq!(
+        storage
+            .prover_schema()
+            .move_aggregated_proofs_to_blob_storage(2)
+            .await?,
+        1
+    );
+    assert_eq!(
+        storage
+            .prover_schema()
+            .move_job_data_to_blob_storage(2)
+            .await?,
+        2
+    );
+    assert_eq!(
+        storage
+            .prover_schema()
+            .move_job_data_to_blob_storage(2)
+            .await?,
+        0
+    );
+
+    for block_number in 1..=3 {
+        assert_eq!(
+            storage
+                .prover_schema()
+                .get_witness(BlockNumber(block_number))
+                .await?,
+            Some(witness.clone())
+        );
+    }
+    let loaded = ProverSchema(&mut storage)
+        .load_aggregated_proof(BlockNumber(1), BlockNumber(3))
+        .await?
+        .expect("Proof is not stored");
+    assert_eq!(
+        serde_json::to_value(loaded).unwrap(),
+        serde_json::to_value(&aggregated_proof).unwrap()
+    );
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert_eq!(job.job_data, job_data);
+
+    // Everything is loaded from the blob storage now.
+    storage.set_blob_storage(None);
+    assert!(storage
+        .prover_schema()
+        .get_witness(BlockNumber(1))
+        .await
+        .is_err());
+    assert!(ProverSchema(&mut storage)
+        .load_aggregated_proof(BlockNumber(1), BlockNumber(3))
+        .await
+        .is_err());
+
+    Ok(())
+}
+
 /// Checks that block proofs are removed correctly.
 #[db_test]
 async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -334,7 +1096,7 @@ async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()
             .await?;
         let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
         ProverSchema(&mut storage)
-            .store_proof(job_id, BlockNumber(block_number), &proof)
+            .store_proof(job_id, BlockNumber(block_number), &proof, "test_prover")
             .await?;
     }
 
@@ -367,7 +1129,13 @@ async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()
         .await?;
     let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
     ProverSchema(&mut storage)
-        .store_aggregated_proof(job_id, BlockNumber(1), BlockNumber(2), &aggregated_proof)
+        .store_aggregated_proof(
+            job_id,
+            BlockNumber(1),
+            BlockNumber(2),
+            &aggregated_proof,
+            "test_prover",
+        )
         .await?;
 
     ProverSchema(&mut storage)
@@ -381,7 +1149,13 @@ async fn test_remove_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()
         .await?;
     let job_id = get_idle_job_from_queue(&mut storage).await?.job_id;
     ProverSchema(&mut storage)
-        .store_aggregated_proof(job_id, BlockNumber(3), BlockNumber(5), &aggregated_proof)
+        .store_aggregated_proof(
+            job_id,
+            BlockNumber(3),
+            BlockNumber(5),
+            &aggregated_proof,
+            "test_prover",
+        )
         .await?;
 
     // Remove aggregated proofs for blocks with numbers greater than 3. It means that proof for 3-5 blocks should be deleted.
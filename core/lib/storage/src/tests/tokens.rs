@@ -126,6 +126,13 @@ async fn tokens_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         .expect("token by symbol not found");
     assert_eq!(token_b, token_b_by_symbol_case_insensitive);
 
+    // Tokens are loaded by symbols in one go, the unknown symbols are skipped.
+    let tokens_by_symbols = TokensSchema(&mut storage)
+        .get_tokens_by_symbols(&["abc".to_string(), "DEF".to_string(), "XYZ".to_string()])
+        .await
+        .expect("get tokens query failed");
+    assert_eq!(tokens_by_symbols, vec![token_a.clone(), token_b.clone()]);
+
     let db_nft_token = TokensSchema(&mut storage)
         .get_token(TokenLike::Id(nft.id))
         .await
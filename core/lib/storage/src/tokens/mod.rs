@@ -464,6 +464,27 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_token.map(|t| t.into()))
     }
 
+    /// Returns the tokens with the given symbols, the search is case-insensitive.
+    /// Symbols of the missing tokens are skipped.
+    pub async fn get_tokens_by_symbols(&mut self, symbols: &[String]) -> QueryResult<Vec<Token>> {
+        let start = Instant::now();
+        let symbols: Vec<String> = symbols.iter().map(|symbol| symbol.to_lowercase()).collect();
+        let tokens = sqlx::query_as!(
+            DbToken,
+            r#"
+            SELECT id, address, decimals, kind as "kind: _", symbol FROM tokens
+            WHERE lower(symbol) = ANY($1)
+            ORDER BY id
+            "#,
+            &symbols
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_tokens_by_symbols", start.elapsed());
+        Ok(tokens.into_iter().map(Token::from).collect())
+    }
+
     pub async fn get_token_market_volume(
         &mut self,
         token_id: TokenId,
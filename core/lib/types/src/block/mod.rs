@@ -358,7 +358,8 @@ impl Block {
         self.processed_priority_ops.1 - self.processed_priority_ops.0
     }
 
-    fn chunks_used(&self) -> usize {
+    /// Returns the number of chunks used by the operations of the block.
+    pub fn chunks_used(&self) -> usize {
         self.block_transactions
             .iter()
             .filter_map(ExecutedOperations::get_executed_op)
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
 use zksync_basic_types::BlockNumber;
@@ -37,6 +38,8 @@ pub struct ProverJob {
     pub first_block: BlockNumber,
     pub last_block: BlockNumber,
     pub job_data: serde_json::Value,
+    /// Time until which the job belongs to the prover unless it sends heartbeats.
+    pub lease_expires_at: DateTime<Utc>,
 }
 
 impl ProverJob {
@@ -45,12 +48,14 @@ impl ProverJob {
         first_block: BlockNumber,
         last_block: BlockNumber,
         job_data: serde_json::Value,
+        lease_expires_at: DateTime<Utc>,
     ) -> Self {
         Self {
             job_id,
             first_block,
             last_block,
             job_data,
+            lease_expires_at,
         }
     }
 }
@@ -27,6 +27,8 @@ export async function status() {
 async function setStatus(value: boolean, redeploy: boolean) {
     env.modify('CONTRACTS_TEST_DUMMY_VERIFIER', `CONTRACTS_TEST_DUMMY_VERIFIER="${value}"`);
     env.modify_contracts_toml('CONTRACTS_TEST_DUMMY_VERIFIER', `CONTRACTS_TEST_DUMMY_VERIFIER="${value}"`);
+    // Proofs produced by the dummy prover can't be verified by the server.
+    env.modify('PROVER_CORE_VERIFY_PROOFS', `PROVER_CORE_VERIFY_PROOFS="${!value}"`);
     await status();
     if (redeploy) {
         console.log('Redeploying the contract...');
@@ -10,6 +10,13 @@ function verfiyKeysTarball() {
     return `verify-keys-${keyDir}-account-${accountTreeDepth}_-balance-${balanceTreeDepth}.tar.gz`;
 }
 
+// Constraints report of the main circuit the changes of the circuit are compared with.
+function circuitReportBaseline() {
+    const accountTreeDepth = process.env.CHAIN_CIRCUIT_ACCOUNT_TREE_DEPTH;
+    const balanceTreeDepth = process.env.CHAIN_CIRCUIT_BALANCE_TREE_DEPTH;
+    return `keys/circuit-reports/account-${accountTreeDepth}_balance-${balanceTreeDepth}.json`;
+}
+
 export async function gen(command: 'contract' | 'all' | 'circuit-size') {
     const accountTreeDepth = process.env.CHAIN_CIRCUIT_ACCOUNT_TREE_DEPTH;
     const balanceTreeDepth = process.env.CHAIN_CIRCUIT_BALANCE_TREE_DEPTH;
@@ -25,7 +32,11 @@ export async function gen(command: 'contract' | 'all' | 'circuit-size') {
     } else if (command == 'contract') {
         await utils.spawn('cargo run --bin key_generator --release -- contract');
     } else if (command == 'circuit-size') {
-        await utils.spawn('cargo run --bin key_generator --release -- circuit-size');
+        // The report is stored as the baseline if there is none yet.
+        const baseline = circuitReportBaseline();
+        const args = fs.existsSync(baseline) ? `--baseline ${baseline}` : `--report ${baseline}`;
+        fs.mkdirSync(path.dirname(baseline), { recursive: true });
+        await utils.spawn(`cargo run --bin key_generator --release -- circuit-size ${args}`);
     }
 
     fs.copyFileSync(`${outputDir}/KeysWithPlonkVerifier.sol`, 'contracts/contracts/KeysWithPlonkVerifier.sol');
@@ -219,6 +219,7 @@ export async function testkit(command: string, timeout: number) {
 
 export async function rustSDK() {
     await utils.spawn('cargo test -p zksync --release -- --ignored --test-threads=1');
+    await utils.spawn('cargo test -p zksync-cli --release -- --ignored --test-threads=1');
 }
 
 export const command = new Command('integration').description('zksync integration tests').alias('i');
@@ -0,0 +1,291 @@
+//! Execution of the CLI commands.
+
+use std::{convert::TryFrom, str::FromStr, time::Duration};
+
+use anyhow::{bail, format_err, Context};
+use serde_json::{json, Value};
+
+use zksync::{
+    ethereum::{zksync_contract, PriorityOpHandle},
+    operations::SyncTransactionHandle,
+    types::BlockStatus,
+    utils::biguint_to_u256,
+    EthereumProvider, RpcProvider, Wallet,
+};
+use zksync_eth_signer::PrivateKeySigner;
+use zksync_types::{PriorityOp, PriorityOpId, TokenId, H256};
+
+use crate::Command;
+
+/// State of the operation to wait for before exiting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaitFor {
+    None,
+    Commit,
+    Verify,
+}
+
+impl FromStr for WaitFor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "commit" => Ok(Self::Commit),
+            "verify" => Ok(Self::Verify),
+            another => Err(format!("Unknown state to wait for: {}", another)),
+        }
+    }
+}
+
+pub struct CommandRunner {
+    pub wallet: Wallet<PrivateKeySigner, RpcProvider>,
+    pub web3_url: Option<String>,
+    pub wait: WaitFor,
+    pub timeout: Duration,
+}
+
+impl CommandRunner {
+    pub async fn run(&self, command: Command) -> anyhow::Result<Value> {
+        let wallet = &self.wallet;
+        let address = wallet.address();
+
+        match command {
+            Command::Balances => {
+                let account_info = wallet.account_info().await?;
+                Ok(serde_json::to_value(account_info)?)
+            }
+            Command::Deposit { token, amount, to } => {
+                let ethereum = self.ethereum().await?;
+                if !wallet.tokens.is_eth(token.as_str().into())
+                    && !ethereum.is_erc20_deposit_approved(token.as_str()).await?
+                {
+                    let approve_tx = ethereum
+                        .approve_erc20_token_deposits(token.as_str())
+                        .await?;
+                    self.wait_eth_tx(&ethereum, approve_tx).await?;
+                }
+
+                let eth_tx_hash = ethereum
+                    .deposit(
+                        token.as_str(),
+                        biguint_to_u256(amount),
+                        to.unwrap_or(address),
+                    )
+                    .await?;
+                self.wait_priority_op(&ethereum, eth_tx_hash).await
+            }
+            Command::Transfer {
+                to,
+                token,
+                amount,
+                fee,
+            } => {
+                let mut builder = wallet
+                    .start_transfer()
+                    .to(to)
+                    .token(token.as_str())?
+                    .amount_exact(amount)?;
+                if let Some(fee) = fee {
+                    builder = builder.fee_exact(fee)?;
+                }
+                self.wait_tx(builder.send().await?).await
+            }
+            Command::Withdraw {
+                to,
+                token,
+                amount,
+                fee,
+            } => {
+                let mut builder = wallet
+                    .start_withdraw()
+                    .to(to.unwrap_or(address))
+                    .token(token.as_str())?
+                    .amount_exact(amount)?;
+                if let Some(fee) = fee {
+                    builder = builder.fee_exact(fee)?;
+                }
+                self.wait_tx(builder.send().await?).await
+            }
+            Command::MintNft {
+                recipient,
+                content_hash,
+                fee_token,
+                fee,
+            } => {
+                let mut builder = wallet
+                    .start_mint_nft()
+                    .recipient(recipient.unwrap_or(address))
+                    .content_hash(content_hash)
+                    .fee_token(fee_token.as_str())?;
+                if let Some(fee) = fee {
+                    builder = builder.fee_exact(fee)?;
+                }
+                self.wait_tx(builder.send().await?).await
+            }
+            Command::TransferNft {
+                to,
+                token_id,
+                fee_token,
+                fee,
+            } => {
+                let nft = wallet
+                    .get_nft(BlockStatus::Committed, TokenId(token_id))
+                    .await?
+                    .ok_or_else(|| format_err!("Account doesn't own NFT {}", token_id))?;
+                let mut builder = wallet
+                    .start_transfer_nft()
+                    .to(to)
+                    .nft(nft)
+                    .fee_token(fee_token.as_str())?;
+                if let Some(fee) = fee {
+                    builder = builder.fee_exact(fee)?;
+                }
+
+                // NFT transfer is sent as a batch with the fee payment.
+                let mut outputs = Vec::new();
+                for handle in builder.send().await? {
+                    outputs.push(self.wait_tx(handle).await?);
+                }
+                Ok(Value::Array(outputs))
+            }
+            Command::WithdrawNft {
+                to,
+                token_id,
+                fee_token,
+                fee,
+            } => {
+                let mut builder = wallet
+                    .start_withdraw_nft()
+                    .to(to.unwrap_or(address))
+                    .token(TokenId(token_id))?
+                    .fee_token(fee_token.as_str())?;
+                if let Some(fee) = fee {
+                    builder = builder.fee_exact(fee)?;
+                }
+                self.wait_tx(builder.send().await?).await
+            }
+            Command::ChangePubkey { fee_token, fee } => {
+                if wallet.is_signing_key_set().await? {
+                    bail!("Signing key is already set for the account");
+                }
+                let mut builder = wallet.start_change_pubkey().fee_token(fee_token.as_str())?;
+                if let Some(fee) = fee {
+                    builder = builder.fee_exact(fee)?;
+                }
+                self.wait_tx(builder.send().await?).await
+            }
+            Command::FullExit { token } => {
+                let account_id = wallet
+                    .account_id()
+                    .context("Account doesn't exist in zkSync")?;
+                let ethereum = self.ethereum().await?;
+                let eth_tx_hash = ethereum.full_exit(token.as_str(), account_id).await?;
+                self.wait_priority_op(&ethereum, eth_tx_hash).await
+            }
+            Command::FullExitNft { token_id } => {
+                let account_id = wallet
+                    .account_id()
+                    .context("Account doesn't exist in zkSync")?;
+                let ethereum = self.ethereum().await?;
+                let eth_tx_hash = ethereum
+                    .full_exit_nft(TokenId(token_id), account_id)
+                    .await?;
+                self.wait_priority_op(&ethereum, eth_tx_hash).await
+            }
+            Command::WaitTx { tx_hash } => {
+                self.wait_tx(SyncTransactionHandle::new(tx_hash, wallet.provider.clone()))
+                    .await
+            }
+        }
+    }
+
+    async fn ethereum(&self) -> anyhow::Result<EthereumProvider<PrivateKeySigner>> {
+        let web3_url = self
+            .web3_url
+            .as_ref()
+            .context("Ethereum node URL is required for this command, set it via `--web3-url`")?;
+        let mut ethereum = self.wallet.ethereum(web3_url).await?;
+        ethereum.set_confirmation_timeout(self.timeout);
+        Ok(ethereum)
+    }
+
+    /// Waits for the zkSync transaction to reach the requested state.
+    async fn wait_tx(&self, handle: SyncTransactionHandle<RpcProvider>) -> anyhow::Result<Value> {
+        let tx_hash = handle.hash();
+        let info = match self.wait {
+            WaitFor::None => None,
+            WaitFor::Commit => Some(
+                handle
+                    .commit_timeout(self.timeout)
+                    .wait_for_commit()
+                    .await?,
+            ),
+            WaitFor::Verify => Some(
+                handle
+                    .verify_timeout(self.timeout)
+                    .wait_for_verify()
+                    .await?,
+            ),
+        };
+
+        Ok(json!({
+            "txHash": tx_hash,
+            "info": info,
+        }))
+    }
+
+    /// Waits for the Ethereum transaction to be mined successfully.
+    async fn wait_eth_tx(
+        &self,
+        ethereum: &EthereumProvider<PrivateKeySigner>,
+        eth_tx_hash: H256,
+    ) -> anyhow::Result<Vec<zksync::web3::types::Log>> {
+        let receipt = ethereum.wait_for_tx(eth_tx_hash).await?;
+        if receipt.status != Some(1.into()) {
+            bail!("Ethereum transaction {:?} failed", eth_tx_hash);
+        }
+        Ok(receipt.logs)
+    }
+
+    /// Waits for the priority operation sent by the Ethereum transaction to reach the requested state.
+    async fn wait_priority_op(
+        &self,
+        ethereum: &EthereumProvider<PrivateKeySigner>,
+        eth_tx_hash: H256,
+    ) -> anyhow::Result<Value> {
+        if self.wait == WaitFor::None {
+            return Ok(json!({ "ethTxHash": eth_tx_hash }));
+        }
+
+        let logs = self.wait_eth_tx(ethereum, eth_tx_hash).await?;
+        let event = zksync_contract().event("NewPriorityRequest")?.signature();
+        let priority_op = logs
+            .into_iter()
+            .filter(|log| log.topics.first() == Some(&event))
+            .find_map(|log| PriorityOp::try_from(log).ok())
+            .context("Ethereum transaction doesn't contain a priority operation")?;
+
+        let handle = PriorityOpHandle::new(
+            PriorityOpId(priority_op.serial_id),
+            self.wallet.provider.clone(),
+        );
+        let info = if self.wait == WaitFor::Commit {
+            handle
+                .commit_timeout(self.timeout)
+                .wait_for_commit()
+                .await?
+        } else {
+            handle
+                .verify_timeout(self.timeout)
+                .wait_for_verify()
+                .await?
+        };
+
+        Ok(json!({
+            "ethTxHash": eth_tx_hash,
+            "serialId": priority_op.serial_id,
+            "info": info,
+        }))
+    }
+}
@@ -0,0 +1,22 @@
+//! Loading of the account key from the Ethereum keystore file.
+
+use std::path::Path;
+
+use anyhow::{ensure, Context};
+use zksync_types::{tx::PackedEthSignature, Address, H256};
+
+/// Decrypts the keystore file (Web3 Secret Storage format) and returns
+/// the Ethereum private key along with its address.
+pub fn load_private_key(path: &Path, password: &str) -> anyhow::Result<(Address, H256)> {
+    let secret = eth_keystore::decrypt_key(path, password)
+        .with_context(|| format!("Failed to decrypt keystore {}", path.display()))?;
+    ensure!(
+        secret.len() == 32,
+        "Unexpected private key length in keystore: {}",
+        secret.len()
+    );
+
+    let private_key = H256::from_slice(&secret);
+    let address = PackedEthSignature::address_from_private_key(&private_key)?;
+    Ok((address, private_key))
+}
@@ -0,0 +1,177 @@
+//! Command-line wallet for the zkSync network.
+//!
+//! Every command prints its result as a JSON object to stdout, so the tool can be used in scripts.
+
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use num::BigUint;
+use structopt::StructOpt;
+
+use zksync::{Network, RpcProvider, Wallet, WalletCredentials};
+use zksync_eth_signer::PrivateKeySigner;
+use zksync_types::{tx::TxHash, Address, H256};
+
+use crate::commands::{CommandRunner, WaitFor};
+
+mod commands;
+mod keystore;
+
+/// Parses the hex value, with or without the `0x` prefix.
+fn parse_hex<T: FromStr>(value: &str) -> Result<T, T::Err> {
+    T::from_str(value.trim_start_matches("0x"))
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Shows the committed and verified account state, including balances and NFTs
+    Balances,
+    /// Deposits funds from Ethereum to zkSync
+    Deposit {
+        #[structopt(long)]
+        token: String,
+        /// Amount in the smallest token units.
+        #[structopt(long)]
+        amount: BigUint,
+        /// Recipient of the deposit, the wallet address by default.
+        #[structopt(long, parse(try_from_str = parse_hex))]
+        to: Option<Address>,
+    },
+    /// Transfers funds inside zkSync
+    Transfer {
+        #[structopt(long, parse(try_from_str = parse_hex))]
+        to: Address,
+        #[structopt(long)]
+        token: String,
+        /// Amount in the smallest token units, must be packable.
+        #[structopt(long)]
+        amount: BigUint,
+        /// Fee in the transferred token, requested from the server by default.
+        #[structopt(long)]
+        fee: Option<BigUint>,
+    },
+    /// Withdraws funds from zkSync to Ethereum
+    Withdraw {
+        /// Ethereum recipient, the wallet address by default.
+        #[structopt(long, parse(try_from_str = parse_hex))]
+        to: Option<Address>,
+        #[structopt(long)]
+        token: String,
+        /// Amount in the smallest token units, must be packable.
+        #[structopt(long)]
+        amount: BigUint,
+        /// Fee in the withdrawn token, requested from the server by default.
+        #[structopt(long)]
+        fee: Option<BigUint>,
+    },
+    /// Mints an NFT
+    MintNft {
+        /// Recipient of the NFT, the wallet address by default.
+        #[structopt(long, parse(try_from_str = parse_hex))]
+        recipient: Option<Address>,
+        #[structopt(long, parse(try_from_str = parse_hex))]
+        content_hash: H256,
+        #[structopt(long)]
+        fee_token: String,
+        #[structopt(long)]
+        fee: Option<BigUint>,
+    },
+    /// Transfers an NFT inside zkSync
+    TransferNft {
+        #[structopt(long, parse(try_from_str = parse_hex))]
+        to: Address,
+        #[structopt(long)]
+        token_id: u32,
+        #[structopt(long)]
+        fee_token: String,
+        #[structopt(long)]
+        fee: Option<BigUint>,
+    },
+    /// Withdraws an NFT from zkSync to Ethereum
+    WithdrawNft {
+        /// Ethereum recipient, the wallet address by default.
+        #[structopt(long, parse(try_from_str = parse_hex))]
+        to: Option<Address>,
+        #[structopt(long)]
+        token_id: u32,
+        #[structopt(long)]
+        fee_token: String,
+        #[structopt(long)]
+        fee: Option<BigUint>,
+    },
+    /// Sets the signing key of the account, derived from the Ethereum key
+    ChangePubkey {
+        #[structopt(long)]
+        fee_token: String,
+        #[structopt(long)]
+        fee: Option<BigUint>,
+    },
+    /// Requests the full exit of the token balance via Ethereum
+    FullExit {
+        #[structopt(long)]
+        token: String,
+    },
+    /// Requests the full exit of the NFT via Ethereum
+    FullExitNft {
+        #[structopt(long)]
+        token_id: u32,
+    },
+    /// Waits for the zkSync transaction to reach the state given by `--wait`
+    WaitTx { tx_hash: TxHash },
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zksync-cli", author = "Matter Labs")]
+#[structopt(about = "Command-line wallet for the zkSync network")]
+struct Opt {
+    #[structopt(long, env = "ZKSYNC_NETWORK", default_value = "localhost")]
+    network: Network,
+    /// zkSync JSON RPC API address, the default one for the network is used if not set.
+    #[structopt(long, env = "ZKSYNC_RPC_ADDR")]
+    rpc_addr: Option<String>,
+    /// Ethereum node URL, required for deposits and full exits.
+    #[structopt(long, env = "WEB3_URL")]
+    web3_url: Option<String>,
+    /// Path to the Ethereum keystore file of the account.
+    #[structopt(long, env = "ZKSYNC_KEYSTORE")]
+    keystore: PathBuf,
+    /// Password of the keystore file.
+    #[structopt(long, env = "ZKSYNC_KEYSTORE_PASSWORD", hide_env_values = true)]
+    password: String,
+    /// State of the operation to wait for: `none`, `commit` or `verify`.
+    #[structopt(long, default_value = "none")]
+    wait: WaitFor,
+    /// Timeout of the waiting in seconds.
+    #[structopt(long, default_value = "600")]
+    timeout: u64,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    let (address, private_key) = keystore::load_private_key(&opt.keystore, &opt.password)?;
+    let provider = match opt.rpc_addr {
+        Some(rpc_addr) => RpcProvider::from_addr_and_network(rpc_addr, opt.network),
+        None => RpcProvider::new(opt.network),
+    };
+    let credentials = WalletCredentials::from_eth_signer(
+        address,
+        PrivateKeySigner::new(private_key),
+        opt.network,
+    )
+    .await?;
+    let wallet = Wallet::new(provider, credentials).await?;
+
+    let runner = CommandRunner {
+        wallet,
+        web3_url: opt.web3_url,
+        wait: opt.wait,
+        timeout: Duration::from_secs(opt.timeout),
+    };
+    let output = runner.run(opt.command).await?;
+    println!("{}", serde_json::to_string_pretty(&output)?);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Tests of the `zksync-cli` binary against the local zkSync server.
+
+use std::{env, fs, process::Command};
+
+use serde_json::Value;
+
+const KEYSTORE_PASSWORD: &str = "password";
+
+/// Runs the CLI with a keystore of the random account and returns its stdout parsed as JSON.
+fn run_cli(args: &[&str]) -> Value {
+    let keystore_dir = env::temp_dir().join(format!("zksync-cli-test-{}", std::process::id()));
+    fs::create_dir_all(&keystore_dir).unwrap();
+    let private_key: [u8; 32] = rand::random();
+    let keystore_name = eth_keystore::encrypt_key(
+        &keystore_dir,
+        &mut rand::thread_rng(),
+        private_key,
+        KEYSTORE_PASSWORD,
+        None,
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_zksync-cli"))
+        .arg("--network")
+        .arg("localhost")
+        .arg("--keystore")
+        .arg(keystore_dir.join(keystore_name))
+        .arg("--password")
+        .arg(KEYSTORE_PASSWORD)
+        .args(args)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&keystore_dir).unwrap();
+
+    assert!(
+        output.status.success(),
+        "zksync-cli failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("stdout should be a JSON object")
+}
+
+#[test]
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+fn balances_output_is_json() {
+    let output = run_cli(&["balances"]);
+
+    assert!(output["address"].is_string());
+    assert!(output["committed"]["balances"].is_object());
+    assert!(output["verified"]["balances"].is_object());
+}
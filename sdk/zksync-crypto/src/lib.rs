@@ -3,6 +3,7 @@
 
 #[cfg(test)]
 mod tests;
+mod tx;
 mod utils;
 
 const PACKED_POINT_SIZE: usize = 32;
@@ -29,6 +30,7 @@ use franklin_crypto::{
 
 pub type Signature = EddsaSignature<Engine>;
 
+use crate::tx::{parse_tx, MintNFT, Order, Swap, WithdrawNFT};
 use crate::utils::set_panic_hook;
 use sha2::{Digest, Sha256};
 
@@ -180,6 +182,65 @@ pub fn sign_musig(private_key: &[u8], msg: &[u8]) -> Result<Vec<u8>, JsValue> {
     Ok(packed_full_signature)
 }
 
+/// Encodes the order given in the JSON format used by `zksync_types`.
+#[wasm_bindgen(js_name = "orderToBytes")]
+pub fn order_to_bytes(order: &str) -> Result<Vec<u8>, JsValue> {
+    parse_tx::<Order>(order)
+        .and_then(|order| order.get_bytes())
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+/// Signs the order given in the JSON format used by `zksync_types`,
+/// the signature format is the same as for `sign_musig`.
+#[wasm_bindgen(js_name = "signOrder")]
+pub fn sign_order(private_key: &[u8], order: &str) -> Result<Vec<u8>, JsValue> {
+    sign_musig(private_key, &order_to_bytes(order)?)
+}
+
+/// Encodes the swap given in the JSON format used by `zksync_types`, including the orders data.
+#[wasm_bindgen(js_name = "swapToBytes")]
+pub fn swap_to_bytes(swap: &str) -> Result<Vec<u8>, JsValue> {
+    parse_tx::<Swap>(swap)
+        .and_then(|swap| swap.get_bytes())
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+/// Signs the swap given in the JSON format used by `zksync_types`.
+/// The orders are represented by their hash in the signed message.
+#[wasm_bindgen(js_name = "signSwap")]
+pub fn sign_swap(private_key: &[u8], swap: &str) -> Result<Vec<u8>, JsValue> {
+    let msg = parse_tx::<Swap>(swap)
+        .and_then(|swap| swap.get_sign_bytes())
+        .map_err(|err| JsValue::from_str(&err))?;
+    sign_musig(private_key, &msg)
+}
+
+/// Encodes the `MintNFT` transaction given in the JSON format used by `zksync_types`.
+#[wasm_bindgen(js_name = "mintNFTToBytes")]
+pub fn mint_nft_to_bytes(tx: &str) -> Result<Vec<u8>, JsValue> {
+    parse_tx::<MintNFT>(tx)
+        .and_then(|tx| tx.get_bytes())
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = "signMintNFT")]
+pub fn sign_mint_nft(private_key: &[u8], tx: &str) -> Result<Vec<u8>, JsValue> {
+    sign_musig(private_key, &mint_nft_to_bytes(tx)?)
+}
+
+/// Encodes the `WithdrawNFT` transaction given in the JSON format used by `zksync_types`.
+#[wasm_bindgen(js_name = "withdrawNFTToBytes")]
+pub fn withdraw_nft_to_bytes(tx: &str) -> Result<Vec<u8>, JsValue> {
+    parse_tx::<WithdrawNFT>(tx)
+        .and_then(|tx| tx.get_bytes())
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = "signWithdrawNFT")]
+pub fn sign_withdraw_nft(private_key: &[u8], tx: &str) -> Result<Vec<u8>, JsValue> {
+    sign_musig(private_key, &withdraw_nft_to_bytes(tx)?)
+}
+
 #[wasm_bindgen]
 pub fn verify_musig(msg: &[u8], signature: &[u8]) -> Result<bool, JsValue> {
     if signature.len() != PACKED_POINT_SIZE + PACKED_SIGNATURE_SIZE {
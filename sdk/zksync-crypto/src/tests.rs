@@ -1,12 +1,20 @@
 //! Compare crypto primitives to those that we use in our `zksync_types` crate;
 
-use super::{private_key_to_pubkey_hash, read_signing_key, sign_musig, verify_musig};
+use super::{
+    mint_nft_to_bytes, order_to_bytes, private_key_to_pubkey_hash, read_signing_key, sign_mint_nft,
+    sign_musig, sign_order, sign_swap, sign_withdraw_nft, swap_to_bytes, verify_musig,
+    withdraw_nft_to_bytes,
+};
 
 use crypto_lib::{public_key_from_private, Engine};
 use franklin_crypto::bellman::pairing::ff::{self, PrimeField, PrimeFieldRepr};
 use franklin_crypto::eddsa::PrivateKey;
+use num::BigUint;
 use rand::{Rng, SeedableRng, XorShiftRng};
-use zksync_types::{tx::TxSignature, PubKeyHash};
+use zksync_types::{
+    tx::{TimeRange, TxSignature},
+    AccountId, Address, MintNFT, Nonce, Order, PubKeyHash, Swap, TokenId, WithdrawNFT, H256,
+};
 
 fn gen_private_key_and_its_be_bytes() -> (PrivateKey<Engine>, Vec<u8>) {
     let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
@@ -78,3 +86,126 @@ fn test_verify_signature() {
     let valid = verify_musig(&msg, &wasm_signature).unwrap();
     assert!(valid);
 }
+
+/// Checks that the signature is made by the given key for the given message.
+fn assert_signed_by(signature: &[u8], msg: &[u8], pk: &PrivateKey<Engine>) {
+    let signature =
+        TxSignature::deserialize_from_packed_bytes(signature).expect("failed to unpack signature");
+    let pubkey = public_key_from_private(pk);
+    assert_eq!(
+        signature.verify_musig(msg).map(|pk| pk.0.into_xy()),
+        Some(pubkey.0.into_xy())
+    );
+}
+
+fn test_order(pk: &PrivateKey<Engine>, amount: u128) -> Order {
+    Order::new_signed(
+        AccountId(5),
+        Address::repeat_byte(7),
+        Nonce(1),
+        TokenId(1),
+        TokenId(2),
+        (BigUint::from(3u32), BigUint::from(70_000_000_000u64)),
+        BigUint::from(amount),
+        TimeRange::new(0, u64::MAX),
+        pk,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_order_bytes_and_signature() {
+    let (pk, serialized_pk) = gen_private_key_and_its_be_bytes();
+
+    // The last amount is not packable, so it's rounded the same way `zksync_types` does.
+    for amount in &[0, 123_456_789_000_000_000_000, 123_456_789_123_456_789] {
+        let order = test_order(&pk, *amount);
+        let json = serde_json::to_string(&order).unwrap();
+
+        assert_eq!(order_to_bytes(&json).unwrap(), order.get_bytes());
+        assert_signed_by(
+            &sign_order(&serialized_pk, &json).unwrap(),
+            &order.get_bytes(),
+            &pk,
+        );
+    }
+}
+
+#[test]
+fn test_swap_bytes_and_signature() {
+    let (pk, serialized_pk) = gen_private_key_and_its_be_bytes();
+
+    let swap = Swap::new_signed(
+        AccountId(3),
+        Address::repeat_byte(3),
+        Nonce(10),
+        (
+            test_order(&pk, 123_456_789_000_000_000_000),
+            test_order(&pk, 0),
+        ),
+        (BigUint::from(1_000_000u32), BigUint::from(2_500_000u32)),
+        BigUint::from(1_230_000u32),
+        TokenId(0),
+        &pk,
+    )
+    .unwrap();
+    let json = serde_json::to_string(&swap).unwrap();
+
+    assert_eq!(swap_to_bytes(&json).unwrap(), swap.get_bytes());
+    assert_signed_by(
+        &sign_swap(&serialized_pk, &json).unwrap(),
+        &swap.get_sign_bytes(),
+        &pk,
+    );
+}
+
+#[test]
+fn test_mint_nft_bytes_and_signature() {
+    let (pk, serialized_pk) = gen_private_key_and_its_be_bytes();
+
+    let tx = MintNFT::new_signed(
+        AccountId(3),
+        Address::repeat_byte(3),
+        H256::repeat_byte(0xab),
+        Address::repeat_byte(4),
+        BigUint::from(1_230_000u32),
+        TokenId(1),
+        Nonce(10),
+        &pk,
+    )
+    .unwrap();
+    let json = serde_json::to_string(&tx).unwrap();
+
+    assert_eq!(mint_nft_to_bytes(&json).unwrap(), tx.get_bytes());
+    assert_signed_by(
+        &sign_mint_nft(&serialized_pk, &json).unwrap(),
+        &tx.get_bytes(),
+        &pk,
+    );
+}
+
+#[test]
+fn test_withdraw_nft_bytes_and_signature() {
+    let (pk, serialized_pk) = gen_private_key_and_its_be_bytes();
+
+    let tx = WithdrawNFT::new_signed(
+        AccountId(3),
+        Address::repeat_byte(3),
+        Address::repeat_byte(4),
+        TokenId(70_000),
+        TokenId(1),
+        BigUint::from(1_230_000u32),
+        Nonce(10),
+        TimeRange::new(100, 200),
+        &pk,
+    )
+    .unwrap();
+    let json = serde_json::to_string(&tx).unwrap();
+
+    assert_eq!(withdraw_nft_to_bytes(&json).unwrap(), tx.get_bytes());
+    assert_signed_by(
+        &sign_withdraw_nft(&serialized_pk, &json).unwrap(),
+        &tx.get_bytes(),
+        &pk,
+    );
+}
@@ -0,0 +1,303 @@
+//! Transactions encoding mirroring the one from the `zksync_types` crate.
+//!
+//! Transactions are deserialized from the same JSON representation as the `zksync_types`
+//! ones, so the clients don't have to re-implement the byte layouts. The `zksync_types`
+//! crate itself can't be compiled into wasm, so the encoding is duplicated here and
+//! compared to the original one in tests.
+
+use serde::{de::Error, Deserialize, Deserializer};
+
+const CURRENT_TX_VERSION: u8 = 1;
+
+const AMOUNT_EXPONENT_BIT_WIDTH: usize = 5;
+const AMOUNT_MANTISSA_BIT_WIDTH: usize = 35;
+const FEE_EXPONENT_BIT_WIDTH: usize = 5;
+const FEE_MANTISSA_BIT_WIDTH: usize = 11;
+const PRICE_BIT_WIDTH: usize = 120;
+
+const ADDRESS_SIZE: usize = 20;
+const HASH_SIZE: usize = 32;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeRange {
+    pub valid_from: u64,
+    pub valid_until: u64,
+}
+
+impl TimeRange {
+    fn as_be_bytes(&self) -> Vec<u8> {
+        [
+            self.valid_from.to_be_bytes(),
+            self.valid_until.to_be_bytes(),
+        ]
+        .concat()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub account_id: u32,
+    #[serde(rename = "recipient", deserialize_with = "deserialize_address")]
+    pub recipient_address: [u8; ADDRESS_SIZE],
+    pub nonce: u32,
+    pub token_buy: u32,
+    pub token_sell: u32,
+    #[serde(rename = "ratio", deserialize_with = "deserialize_amount_pair")]
+    pub price: (u128, u128),
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub amount: u128,
+    #[serde(flatten)]
+    pub time_range: TimeRange,
+}
+
+impl Order {
+    const MSG_TYPE: u8 = b'o';
+
+    pub fn get_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&[Self::MSG_TYPE]);
+        out.extend_from_slice(&[CURRENT_TX_VERSION]);
+        out.extend_from_slice(&self.account_id.to_be_bytes());
+        out.extend_from_slice(&self.recipient_address);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.token_sell.to_be_bytes());
+        out.extend_from_slice(&self.token_buy.to_be_bytes());
+        out.extend_from_slice(&pack_price(self.price.0)?);
+        out.extend_from_slice(&pack_price(self.price.1)?);
+        out.extend_from_slice(&pack_token_amount(self.amount)?);
+        out.extend_from_slice(&self.time_range.as_be_bytes());
+        Ok(out)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Swap {
+    pub submitter_id: u32,
+    #[serde(deserialize_with = "deserialize_address")]
+    pub submitter_address: [u8; ADDRESS_SIZE],
+    pub nonce: u32,
+    pub orders: (Order, Order),
+    #[serde(deserialize_with = "deserialize_amount_pair")]
+    pub amounts: (u128, u128),
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub fee: u128,
+    pub fee_token: u32,
+}
+
+impl Swap {
+    const TX_TYPE: u8 = 11;
+
+    /// Encodes the transaction including the full orders data.
+    pub fn get_bytes(&self) -> Result<Vec<u8>, String> {
+        self.get_swap_bytes(&self.orders_bytes()?)
+    }
+
+    /// Encodes the transaction with the orders represented by their hash,
+    /// this is the message to be signed.
+    pub fn get_sign_bytes(&self) -> Result<Vec<u8>, String> {
+        let orders_hash = crate::utils::rescue_hash_orders(&self.orders_bytes()?);
+        self.get_swap_bytes(&orders_hash)
+    }
+
+    fn orders_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut orders_bytes = self.orders.0.get_bytes()?;
+        orders_bytes.extend(self.orders.1.get_bytes()?);
+        Ok(orders_bytes)
+    }
+
+    fn get_swap_bytes(&self, order_bytes: &[u8]) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&[255u8 - Self::TX_TYPE]);
+        out.extend_from_slice(&[CURRENT_TX_VERSION]);
+        out.extend_from_slice(&self.submitter_id.to_be_bytes());
+        out.extend_from_slice(&self.submitter_address);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(order_bytes);
+        out.extend_from_slice(&self.fee_token.to_be_bytes());
+        out.extend_from_slice(&pack_fee_amount(self.fee)?);
+        out.extend_from_slice(&pack_token_amount(self.amounts.0)?);
+        out.extend_from_slice(&pack_token_amount(self.amounts.1)?);
+        Ok(out)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintNFT {
+    pub creator_id: u32,
+    #[serde(deserialize_with = "deserialize_address")]
+    pub creator_address: [u8; ADDRESS_SIZE],
+    #[serde(deserialize_with = "deserialize_hash")]
+    pub content_hash: [u8; HASH_SIZE],
+    #[serde(deserialize_with = "deserialize_address")]
+    pub recipient: [u8; ADDRESS_SIZE],
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub fee: u128,
+    #[serde(default)]
+    pub fee_token: u32,
+    pub nonce: u32,
+}
+
+impl MintNFT {
+    const TX_TYPE: u8 = 9;
+
+    pub fn get_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&[255u8 - Self::TX_TYPE]);
+        out.extend_from_slice(&[CURRENT_TX_VERSION]);
+        out.extend_from_slice(&self.creator_id.to_be_bytes());
+        out.extend_from_slice(&self.creator_address);
+        out.extend_from_slice(&self.content_hash);
+        out.extend_from_slice(&self.recipient);
+        out.extend_from_slice(&self.fee_token.to_be_bytes());
+        out.extend_from_slice(&pack_fee_amount(self.fee)?);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        Ok(out)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawNFT {
+    pub account_id: u32,
+    #[serde(deserialize_with = "deserialize_address")]
+    pub from: [u8; ADDRESS_SIZE],
+    #[serde(deserialize_with = "deserialize_address")]
+    pub to: [u8; ADDRESS_SIZE],
+    pub token: u32,
+    pub fee_token: u32,
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub fee: u128,
+    pub nonce: u32,
+    #[serde(flatten)]
+    pub time_range: TimeRange,
+}
+
+impl WithdrawNFT {
+    const TX_TYPE: u8 = 10;
+
+    pub fn get_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&[255u8 - Self::TX_TYPE]);
+        out.extend_from_slice(&[CURRENT_TX_VERSION]);
+        out.extend_from_slice(&self.account_id.to_be_bytes());
+        out.extend_from_slice(&self.from);
+        out.extend_from_slice(&self.to);
+        out.extend_from_slice(&self.token.to_be_bytes());
+        out.extend_from_slice(&self.fee_token.to_be_bytes());
+        out.extend_from_slice(&pack_fee_amount(self.fee)?);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.as_be_bytes());
+        Ok(out)
+    }
+}
+
+/// Parses the transaction from its JSON representation.
+pub fn parse_tx<'de, T: Deserialize<'de>>(json: &'de str) -> Result<T, String> {
+    serde_json::from_str(json).map_err(|err| format!("Failed to parse transaction: {}", err))
+}
+
+fn pack_token_amount(amount: u128) -> Result<Vec<u8>, String> {
+    pack_float(amount, AMOUNT_EXPONENT_BIT_WIDTH, AMOUNT_MANTISSA_BIT_WIDTH)
+}
+
+fn pack_fee_amount(amount: u128) -> Result<Vec<u8>, String> {
+    pack_float(amount, FEE_EXPONENT_BIT_WIDTH, FEE_MANTISSA_BIT_WIDTH)
+}
+
+fn pack_price(price: u128) -> Result<Vec<u8>, String> {
+    if price >> PRICE_BIT_WIDTH != 0 {
+        return Err(format!("Price {} is greater than supported", price));
+    }
+    Ok(price.to_be_bytes()[16 - PRICE_BIT_WIDTH / 8..].to_vec())
+}
+
+/// Packs the amount to the floating-point number with the exponent base 10, rounding
+/// it to the closest packable value, same as `FloatConversions::pack` does.
+/// The result is encoded as `mantissa || exponent` in big-endian bytes.
+fn pack_float(integer: u128, exponent_len: usize, mantissa_len: usize) -> Result<Vec<u8>, String> {
+    let exponent_base = 10u128;
+    let max_exponent = exponent_base.saturating_pow((1 << exponent_len) - 1);
+    let max_mantissa = (1u128 << mantissa_len) - 1;
+    let limit = max_mantissa.saturating_mul(max_exponent);
+    if integer > limit {
+        return Err(format!(
+            "Amount {} is greater than the maximum packable {}",
+            integer, limit
+        ));
+    }
+
+    let mut exponent = 0;
+    let mut exponent_temp = 1u128;
+    while integer > max_mantissa * exponent_temp {
+        exponent_temp *= exponent_base;
+        exponent += 1;
+    }
+    let (exponent, mantissa) = if exponent == 0 {
+        (0, integer)
+    } else {
+        let mantissa = integer / exponent_temp;
+        let variant1 = mantissa * exponent_temp;
+        let variant2 = max_mantissa * exponent_temp / exponent_base;
+        if integer - variant1 < integer - variant2 {
+            (exponent, mantissa)
+        } else {
+            (exponent - 1, max_mantissa)
+        }
+    };
+
+    let packed = (mantissa << exponent_len) | exponent;
+    let size = (exponent_len + mantissa_len) / 8;
+    Ok(packed.to_be_bytes()[16 - size..].to_vec())
+}
+
+fn deserialize_hex<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    size: usize,
+) -> Result<Vec<u8>, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    let bytes = hex::decode(value.trim_start_matches("0x")).map_err(Error::custom)?;
+    if bytes.len() != size {
+        return Err(Error::custom(format!(
+            "Expected {} bytes, got {}",
+            size,
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
+
+fn deserialize_address<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[u8; ADDRESS_SIZE], D::Error> {
+    let mut address = [0u8; ADDRESS_SIZE];
+    address.copy_from_slice(&deserialize_hex(deserializer, ADDRESS_SIZE)?);
+    Ok(address)
+}
+
+fn deserialize_hash<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[u8; HASH_SIZE], D::Error> {
+    let mut hash = [0u8; HASH_SIZE];
+    hash.copy_from_slice(&deserialize_hex(deserializer, HASH_SIZE)?);
+    Ok(hash)
+}
+
+/// Amounts are represented as the radix 10 strings, same as `BigUintSerdeAsRadix10Str` does.
+fn deserialize_amount<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(Error::custom)
+}
+
+fn deserialize_amount_pair<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(u128, u128), D::Error> {
+    let (first, second) = <(String, String)>::deserialize(deserializer)?;
+    Ok((
+        first.parse().map_err(Error::custom)?,
+        second.parse().map_err(Error::custom)?,
+    ))
+}
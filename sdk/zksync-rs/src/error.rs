@@ -12,6 +12,8 @@ pub enum ClientError {
     RpcError(RpcFailure),
     #[error("Network error: {0}")]
     NetworkError(String),
+    #[error("REST API error: {message}")]
+    RestApiError { code: u16, message: String },
 
     #[error("Provided account credentials are incorrect")]
     IncorrectCredentials,
@@ -41,6 +43,15 @@ pub enum ClientError {
     #[error("Provided function arguments are incorrect")]
     IncorrectInput,
 
+    #[error("Operation is not supported by the provider: {0}")]
+    NotSupported(String),
+
+    #[error("Nonce manager storage error: {0}")]
+    NonceStorageError(String),
+
+    #[error("File error: {0}")]
+    FileError(String),
+
     #[error("Other")]
     Other,
 }
@@ -0,0 +1,271 @@
+//! Local nonce management for the wallets sending transactions concurrently.
+//!
+//! By default, builders take the nonce from the committed account state, so several
+//! transactions built at the same time get the same nonce. `NonceManager` reserves the
+//! nonces locally instead, tracks the submitted transactions and reuses the nonces of
+//! the transactions which were not submitted or were rejected.
+
+// Built-in imports
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+// External uses
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{tx::TxHash, Address, Nonce};
+
+// Local imports
+use crate::{error::ClientError, operations::SyncTransactionHandle, provider::Provider};
+
+/// Transaction submitted with a nonce reserved by the `NonceManager`,
+/// which is not executed yet.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTx {
+    pub nonce: Nonce,
+    pub tx_hash: TxHash,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NonceState {
+    /// Nonce following the last reserved one, `None` until it's loaded from the server.
+    next_nonce: Option<Nonce>,
+    /// Nonces reserved for the transactions which were not submitted yet.
+    reserved: BTreeSet<Nonce>,
+    /// Nonces below the `next_nonce` which are free to be used again.
+    gaps: BTreeSet<Nonce>,
+    pending: Vec<PendingTx>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: NonceState,
+    path: Option<PathBuf>,
+}
+
+impl Inner {
+    fn save(&self) -> Result<(), ClientError> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let contents = serde_json::to_vec_pretty(&self.state).map_err(storage_error)?;
+        // Write to the temporary file first, so the state is never left half-written.
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, contents).map_err(storage_error)?;
+        fs::rename(&tmp_path, path).map_err(storage_error)
+    }
+
+    /// Marks the nonce as unused, so it can be reserved again.
+    fn free_nonce(&mut self, nonce: Nonce) {
+        match self.state.next_nonce {
+            Some(next_nonce) if next_nonce == nonce + 1 => {
+                self.state.next_nonce = Some(nonce);
+                // The gaps right below the new next nonce are not gaps anymore.
+                while let Some(&gap) = self.state.gaps.iter().next_back() {
+                    if gap + 1 != self.state.next_nonce.unwrap() {
+                        break;
+                    }
+                    self.state.gaps.remove(&gap);
+                    self.state.next_nonce = Some(gap);
+                }
+            }
+            Some(next_nonce) if nonce < next_nonce => {
+                self.state.gaps.insert(nonce);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn storage_error(err: impl ToString) -> ClientError {
+    ClientError::NonceStorageError(err.to_string())
+}
+
+/// Reserves the nonces of the wallet transactions locally and tracks the submitted transactions.
+///
+/// The manager can be shared between several wallets of the same account, and its state
+/// can be persisted to a file, so a restarted service doesn't reuse the nonces of the
+/// transactions sent before the restart.
+#[derive(Debug, Clone)]
+pub struct NonceManager {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Default for NonceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NonceManager {
+    /// Creates a nonce manager keeping its state in memory.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                state: NonceState::default(),
+                path: None,
+            })),
+        }
+    }
+
+    /// Creates a nonce manager persisting its state to the given file.
+    /// If the file exists, the state is restored from it.
+    pub fn with_file(path: impl AsRef<Path>) -> Result<Self, ClientError> {
+        let path = path.as_ref().to_path_buf();
+        let state = if path.exists() {
+            let contents = fs::read(&path).map_err(storage_error)?;
+            serde_json::from_slice(&contents).map_err(storage_error)?
+        } else {
+            NonceState::default()
+        };
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                state,
+                path: Some(path),
+            })),
+        })
+    }
+
+    /// Reserves `count` consecutive nonces and returns the first of them.
+    ///
+    /// A single nonce is taken from the gaps left by the unused nonces first. If the manager
+    /// has no state yet, the committed nonce of the account is used as the starting point.
+    pub async fn reserve<P: Provider>(
+        &self,
+        provider: &P,
+        address: Address,
+        count: u32,
+    ) -> Result<Nonce, ClientError> {
+        let initialized = self.inner.lock().unwrap().state.next_nonce.is_some();
+        let committed_nonce = if initialized {
+            None
+        } else {
+            Some(provider.account_info(address).await?.committed.nonce)
+        };
+
+        let mut inner = self.inner.lock().unwrap();
+        let state = &mut inner.state;
+        // Another reservation could have initialized the state while the account was requested.
+        let next_nonce = state
+            .next_nonce
+            .get_or_insert_with(|| committed_nonce.unwrap());
+
+        let first_gap = state.gaps.iter().next().copied();
+        let first = match first_gap {
+            Some(gap) if count == 1 => {
+                state.gaps.remove(&gap);
+                gap
+            }
+            _ => {
+                let first = *next_nonce;
+                *next_nonce = first + count;
+                first
+            }
+        };
+        state
+            .reserved
+            .extend((0..count).map(|offset| first + offset));
+        inner.save()?;
+
+        Ok(first)
+    }
+
+    /// Records the transaction submitted with the given nonce.
+    pub fn track(&self, nonce: Nonce, tx_hash: TxHash) -> Result<(), ClientError> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state.reserved.remove(&nonce);
+        inner.state.pending.push(PendingTx { nonce, tx_hash });
+        inner.save()
+    }
+
+    /// Returns the nonce reserved for the transaction which was not submitted.
+    /// Nonces which were not reserved by this manager are ignored.
+    pub fn release(&self, nonce: Nonce) -> Result<(), ClientError> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state.reserved.remove(&nonce) {
+            inner.free_nonce(nonce);
+            inner.save()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the submitted transactions which are not executed yet.
+    pub fn pending(&self) -> Vec<PendingTx> {
+        self.inner.lock().unwrap().state.pending.clone()
+    }
+
+    /// Returns the handles of the submitted transactions which are not executed yet.
+    pub fn pending_handles<P: Provider + Clone>(
+        &self,
+        provider: &P,
+    ) -> Vec<SyncTransactionHandle<P>> {
+        self.pending()
+            .into_iter()
+            .map(|tx| SyncTransactionHandle::new(tx.tx_hash, provider.clone()))
+            .collect()
+    }
+
+    /// Checks the state of the pending transactions and stops tracking the executed ones.
+    ///
+    /// Returns the rejected transactions. Their nonces were not used, so they become gaps
+    /// which are filled by the next reservations. Note that the transactions with the
+    /// subsequent nonces are rejected as well once the server reaches them.
+    pub async fn check_pending<P: Provider>(
+        &self,
+        provider: &P,
+    ) -> Result<Vec<PendingTx>, ClientError> {
+        let pending = self.pending();
+
+        let mut executed = Vec::new();
+        let mut rejected: Vec<PendingTx> = Vec::new();
+        for tx in pending {
+            let tx_info = provider.tx_info(tx.tx_hash).await?;
+            if !tx_info.executed {
+                continue;
+            }
+            executed.push(tx.tx_hash);
+            if tx_info.success == Some(false) {
+                rejected.push(tx);
+            }
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .state
+            .pending
+            .retain(|tx| !executed.contains(&tx.tx_hash));
+        // Free the higher nonces first, so the next nonce is rolled back as far as possible.
+        rejected.sort_by_key(|tx| tx.nonce);
+        for tx in rejected.iter().rev() {
+            inner.free_nonce(tx.nonce);
+        }
+        inner.save()?;
+
+        Ok(rejected)
+    }
+
+    /// Drops the local state and starts over from the committed nonce of the account.
+    /// Should be used if the account was used by some other sender.
+    pub async fn reset<P: Provider>(
+        &self,
+        provider: &P,
+        address: Address,
+    ) -> Result<(), ClientError> {
+        let committed_nonce = provider.account_info(address).await?.committed.nonce;
+
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = NonceState {
+            next_nonce: Some(committed_nonce),
+            ..NonceState::default()
+        };
+        inner.save()
+    }
+}
@@ -0,0 +1,332 @@
+//! Offline signing of the transactions and their subsequent broadcasting.
+//!
+//! `OfflineProvider` allows to create a `Wallet` on a machine without network access. The account
+//! data and the tokens are provided explicitly instead of being requested from the server, and the
+//! builders must be given the fee explicitly as well. The signed transactions are stored as
+//! `SignedTransaction` (or `SignedBatch` for the batches) files, which are then submitted to the
+//! network with their `send` method from another machine.
+
+// Built-in imports
+use std::{fs, path::Path};
+
+// External uses
+use async_trait::async_trait;
+use num::BigUint;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{
+    network::Network,
+    tx::{PackedEthSignature, TxEthSignature, TxEthSignatureVariant, TxHash},
+    AccountId, Address, Nonce, Swap, Token, TokenLike, TxFeeTypes, ZkSyncTx,
+};
+
+// Local imports
+use crate::{
+    error::ClientError,
+    operations::SyncTransactionHandle,
+    provider::{Provider, ResponseResult},
+    types::{AccountInfo, AccountState, ContractAddress, EthOpInfo, Fee, Tokens, TransactionInfo},
+};
+
+/// `Provider` serving the explicitly provided data without any network access.
+///
+/// The nonce is returned as the committed one for every request, so the nonces of several
+/// transactions signed at once must be set explicitly or reserved via `NonceManager`.
+/// All the methods requiring the server, including the fee requests, return the
+/// `ClientError::NotSupported` error.
+#[derive(Debug, Clone)]
+pub struct OfflineProvider {
+    network: Network,
+    tokens: Tokens,
+    account_id: AccountId,
+    nonce: Nonce,
+}
+
+impl OfflineProvider {
+    /// Creates a provider for the account with the given id and the current committed nonce.
+    pub fn new(
+        network: Network,
+        tokens: impl IntoIterator<Item = Token>,
+        account_id: AccountId,
+        nonce: Nonce,
+    ) -> Self {
+        Self {
+            network,
+            tokens: tokens
+                .into_iter()
+                .map(|token| (token.symbol.clone(), token))
+                .collect(),
+            account_id,
+            nonce,
+        }
+    }
+}
+
+fn offline_error<T>(method: &str) -> ResponseResult<T> {
+    Err(ClientError::NotSupported(format!(
+        "`{}` is not available in the offline mode",
+        method
+    )))
+}
+
+#[async_trait]
+impl Provider for OfflineProvider {
+    async fn account_info(&self, address: Address) -> ResponseResult<AccountInfo> {
+        let state = AccountState {
+            nonce: self.nonce,
+            ..AccountState::default()
+        };
+        Ok(AccountInfo {
+            address,
+            id: Some(self.account_id),
+            depositing: Default::default(),
+            committed: state.clone(),
+            verified: state,
+        })
+    }
+
+    async fn tokens(&self) -> ResponseResult<Tokens> {
+        Ok(self.tokens.clone())
+    }
+
+    async fn tx_info(&self, _tx_hash: TxHash) -> ResponseResult<TransactionInfo> {
+        offline_error("tx_info")
+    }
+
+    async fn get_tx_fee(
+        &self,
+        _tx_type: TxFeeTypes,
+        _address: Address,
+        _token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<Fee> {
+        offline_error("get_tx_fee")
+    }
+
+    async fn get_txs_batch_fee(
+        &self,
+        _tx_types: Vec<TxFeeTypes>,
+        _addresses: Vec<Address>,
+        _token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<BigUint> {
+        offline_error("get_txs_batch_fee")
+    }
+
+    async fn ethop_info(&self, _serial_id: u32) -> ResponseResult<EthOpInfo> {
+        offline_error("ethop_info")
+    }
+
+    async fn get_eth_tx_for_withdrawal(
+        &self,
+        _withdrawal_hash: TxHash,
+    ) -> ResponseResult<Option<String>> {
+        offline_error("get_eth_tx_for_withdrawal")
+    }
+
+    async fn contract_address(&self) -> ResponseResult<ContractAddress> {
+        offline_error("contract_address")
+    }
+
+    async fn send_tx(
+        &self,
+        _tx: ZkSyncTx,
+        _eth_signature: Option<PackedEthSignature>,
+    ) -> ResponseResult<TxHash> {
+        offline_error("send_tx")
+    }
+
+    async fn send_swap(
+        &self,
+        _swap: Swap,
+        _eth_signature: Option<PackedEthSignature>,
+        _orders_signatures: (Option<PackedEthSignature>, Option<PackedEthSignature>),
+    ) -> ResponseResult<TxHash> {
+        offline_error("send_swap")
+    }
+
+    async fn send_txs_batch(
+        &self,
+        _txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
+        _eth_signature: Option<PackedEthSignature>,
+    ) -> ResponseResult<Vec<TxHash>> {
+        offline_error("send_txs_batch")
+    }
+
+    fn network(&self) -> Network {
+        self.network
+    }
+}
+
+/// Signed transaction ready to be submitted to the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedTransaction {
+    pub tx: ZkSyncTx,
+    pub eth_signature: TxEthSignatureVariant,
+}
+
+impl SignedTransaction {
+    /// Creates a signed transaction with a single Ethereum signature.
+    pub fn new(tx: ZkSyncTx, eth_signature: Option<PackedEthSignature>) -> Self {
+        Self {
+            tx,
+            eth_signature: TxEthSignatureVariant::Single(
+                eth_signature.map(TxEthSignature::EthereumSignature),
+            ),
+        }
+    }
+
+    /// Creates a signed swap with the Ethereum signatures of the swap itself and its orders.
+    pub fn swap(
+        swap: Swap,
+        eth_signature: Option<PackedEthSignature>,
+        orders_signatures: (Option<PackedEthSignature>, Option<PackedEthSignature>),
+    ) -> Self {
+        Self {
+            tx: ZkSyncTx::Swap(Box::new(swap)),
+            eth_signature: TxEthSignatureVariant::Triple(
+                eth_signature.map(TxEthSignature::EthereumSignature),
+                orders_signatures.0.map(TxEthSignature::EthereumSignature),
+                orders_signatures.1.map(TxEthSignature::EthereumSignature),
+            ),
+        }
+    }
+
+    /// Returns the hash of the transaction.
+    pub fn hash(&self) -> TxHash {
+        self.tx.hash()
+    }
+
+    /// Loads the signed transaction from the JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ClientError> {
+        load_json(path)
+    }
+
+    /// Saves the signed transaction to the JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ClientError> {
+        save_json(self, path)
+    }
+
+    /// Submits the transaction to the network, returning the handle for its awaiting.
+    pub async fn send<P: Provider + Clone>(
+        self,
+        provider: &P,
+    ) -> Result<SyncTransactionHandle<P>, ClientError> {
+        let tx_hash = match (self.tx, self.eth_signature) {
+            (
+                ZkSyncTx::Swap(swap),
+                TxEthSignatureVariant::Triple(swap_signature, first, second),
+            ) => {
+                provider
+                    .send_swap(
+                        *swap,
+                        packed_signature(swap_signature)?,
+                        (packed_signature(first)?, packed_signature(second)?),
+                    )
+                    .await?
+            }
+            // Only swaps are signed with multiple signatures, and swaps always include the
+            // signatures of their orders.
+            (ZkSyncTx::Swap(_), TxEthSignatureVariant::Single(_))
+            | (_, TxEthSignatureVariant::Triple(..)) => return Err(ClientError::IncorrectInput),
+            (tx, TxEthSignatureVariant::Single(eth_signature)) => {
+                provider
+                    .send_tx(tx, packed_signature(eth_signature)?)
+                    .await?
+            }
+        };
+
+        Ok(SyncTransactionHandle::new(tx_hash, provider.clone()))
+    }
+}
+
+/// Signed transactions batch ready to be submitted to the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedBatch {
+    pub txs: Vec<SignedTransaction>,
+    pub eth_signature: Option<TxEthSignature>,
+}
+
+impl SignedBatch {
+    /// Creates a signed batch with the Ethereum signatures of its transactions and the batch itself.
+    pub fn new(
+        txs: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
+        eth_signature: Option<PackedEthSignature>,
+    ) -> Self {
+        Self {
+            txs: txs
+                .into_iter()
+                .map(|(tx, eth_signature)| SignedTransaction::new(tx, eth_signature))
+                .collect(),
+            eth_signature: eth_signature.map(TxEthSignature::EthereumSignature),
+        }
+    }
+
+    /// Returns the hashes of the batch transactions.
+    pub fn hashes(&self) -> Vec<TxHash> {
+        self.txs.iter().map(SignedTransaction::hash).collect()
+    }
+
+    /// Loads the signed batch from the JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ClientError> {
+        load_json(path)
+    }
+
+    /// Saves the signed batch to the JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ClientError> {
+        save_json(self, path)
+    }
+
+    /// Submits the batch to the network, returning the handles for its transactions.
+    pub async fn send<P: Provider + Clone>(
+        self,
+        provider: &P,
+    ) -> Result<Vec<SyncTransactionHandle<P>>, ClientError> {
+        let mut txs = Vec::with_capacity(self.txs.len());
+        for signed in self.txs {
+            match signed.eth_signature {
+                TxEthSignatureVariant::Single(eth_signature) => {
+                    txs.push((signed.tx, packed_signature(eth_signature)?))
+                }
+                // Swaps can't be the part of a batch.
+                TxEthSignatureVariant::Triple(..) => return Err(ClientError::IncorrectInput),
+            }
+        }
+        let tx_hashes = provider
+            .send_txs_batch(txs, packed_signature(self.eth_signature)?)
+            .await?;
+
+        Ok(tx_hashes
+            .into_iter()
+            .map(|tx_hash| SyncTransactionHandle::new(tx_hash, provider.clone()))
+            .collect())
+    }
+}
+
+/// `Provider` only accepts the signatures made with the Ethereum private key.
+fn packed_signature(
+    signature: Option<TxEthSignature>,
+) -> Result<Option<PackedEthSignature>, ClientError> {
+    match signature {
+        None => Ok(None),
+        Some(TxEthSignature::EthereumSignature(signature)) => Ok(Some(signature)),
+        Some(TxEthSignature::EIP1271Signature(_)) => Err(ClientError::NotSupported(
+            "EIP-1271 signatures can't be submitted via `Provider`".into(),
+        )),
+    }
+}
+
+fn file_error(err: impl ToString) -> ClientError {
+    ClientError::FileError(err.to_string())
+}
+
+fn load_json<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, ClientError> {
+    let contents = fs::read(path).map_err(file_error)?;
+    serde_json::from_slice(&contents).map_err(file_error)
+}
+
+fn save_json<T: Serialize>(value: &T, path: impl AsRef<Path>) -> Result<(), ClientError> {
+    let contents = serde_json::to_vec_pretty(value).map_err(file_error)?;
+    fs::write(path, contents).map_err(file_error)
+}
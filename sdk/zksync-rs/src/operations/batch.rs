@@ -0,0 +1,430 @@
+use num::{BigUint, Zero};
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_eth_signer::{error::SignerError, EthereumSigner};
+use zksync_types::{
+    helpers::{
+        closest_greater_or_eq_packable_fee_amount, closest_packable_fee_amount,
+        closest_packable_token_amount, is_fee_amount_packable,
+    },
+    tx::{PackedEthSignature, TimeRange},
+    Address, MintNFT, Nonce, Token, TokenId, TokenLike, Transfer, TxFeeTypes, Withdraw,
+    WithdrawNFT, ZkSyncTx, H256,
+};
+
+use crate::{
+    error::ClientError, offline::SignedBatch, operations::SyncTransactionHandle,
+    provider::Provider, types::NFT, wallet::Wallet,
+};
+
+#[derive(Debug, Clone)]
+enum BatchItem {
+    Transfer {
+        to: Address,
+        token: Token,
+        amount: BigUint,
+    },
+    Withdraw {
+        to: Address,
+        token: Token,
+        amount: BigUint,
+    },
+    TransferNFT {
+        to: Address,
+        nft: NFT,
+    },
+    WithdrawNFT {
+        to: Address,
+        token: TokenId,
+    },
+    MintNFT {
+        recipient: Address,
+        content_hash: H256,
+    },
+}
+
+impl BatchItem {
+    /// Checks whether the fee in the given token can be attached to this transaction.
+    fn can_pay_fee(&self, fee_token: &Token) -> bool {
+        match self {
+            Self::Transfer { token, .. } | Self::Withdraw { token, .. } => token.id == fee_token.id,
+            Self::TransferNFT { .. } => false,
+            Self::WithdrawNFT { .. } | Self::MintNFT { .. } => true,
+        }
+    }
+
+    fn fee_type(&self) -> (TxFeeTypes, Address) {
+        match self {
+            Self::Transfer { to, .. } | Self::TransferNFT { to, .. } => (TxFeeTypes::Transfer, *to),
+            Self::Withdraw { to, .. } => (TxFeeTypes::Withdraw, *to),
+            Self::WithdrawNFT { to, .. } => (TxFeeTypes::WithdrawNFT, *to),
+            Self::MintNFT { recipient, .. } => (TxFeeTypes::MintNFT, *recipient),
+        }
+    }
+}
+
+/// Builder of the transactions batch sent by the wallet and signed with a single
+/// Ethereum signature, e.g. for the payouts to many recipients at once.
+///
+/// Nonces of the transactions are assigned sequentially in the order they were added.
+/// The fee for the whole batch is paid by one of its transactions, see `BatchBuilder::fee_payer`.
+#[derive(Debug)]
+pub struct BatchBuilder<'a, S: EthereumSigner, P: Provider> {
+    wallet: &'a Wallet<S, P>,
+    items: Vec<BatchItem>,
+    fee_token: Option<Token>,
+    fee: Option<BigUint>,
+    fee_payer: Option<usize>,
+    nonce: Option<Nonce>,
+    valid_from: Option<u64>,
+    valid_until: Option<u64>,
+}
+
+impl<'a, S, P> BatchBuilder<'a, S, P>
+where
+    S: EthereumSigner,
+    P: Provider + Clone,
+{
+    /// Initializes a transactions batch building process.
+    pub fn new(wallet: &'a Wallet<S, P>) -> Self {
+        Self {
+            wallet,
+            items: Vec::new(),
+            fee_token: None,
+            fee: None,
+            fee_payer: None,
+            nonce: None,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    /// Directly returns the signed transactions of the batch along with the batch Ethereum
+    /// signature for the subsequent usage.
+    ///
+    /// Transactions have no individual Ethereum signatures, since the batch one covers all of them.
+    #[allow(clippy::type_complexity)]
+    pub async fn txs(
+        self,
+    ) -> Result<
+        (
+            Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
+            Option<PackedEthSignature>,
+        ),
+        ClientError,
+    > {
+        let fee_token = self
+            .fee_token
+            .ok_or_else(|| ClientError::MissingRequiredField("fee_token".into()))?;
+        let mut items = self.items;
+        if items.is_empty() {
+            return Err(ClientError::MissingRequiredField("transactions".into()));
+        }
+        let account_id = self
+            .wallet
+            .signer
+            .account_id
+            .ok_or(ClientError::SigningError(SignerError::NoSigningKey))?;
+        let valid_from = self.valid_from.unwrap_or(0);
+        let valid_until = self.valid_until.unwrap_or(u64::MAX);
+        let time_range = TimeRange::new(valid_from, valid_until);
+
+        let fee_payer = match self.fee_payer {
+            Some(index) => {
+                let can_pay = items
+                    .get(index)
+                    .map(|item| item.can_pay_fee(&fee_token))
+                    .unwrap_or(false);
+                if !can_pay {
+                    return Err(ClientError::IncorrectInput);
+                }
+                index
+            }
+            None => match items.iter().position(|item| item.can_pay_fee(&fee_token)) {
+                Some(index) => index,
+                None => {
+                    // There is no transaction in the fee token, so a separate fee transfer is needed.
+                    items.push(BatchItem::Transfer {
+                        to: self.wallet.address(),
+                        token: fee_token.clone(),
+                        amount: BigUint::zero(),
+                    });
+                    items.len() - 1
+                }
+            },
+        };
+
+        let fee = match self.fee {
+            Some(fee) => fee,
+            None => {
+                let (tx_types, addresses): (Vec<_>, Vec<_>) =
+                    items.iter().map(BatchItem::fee_type).unzip();
+                let fee = self
+                    .wallet
+                    .provider
+                    .get_txs_batch_fee(tx_types, addresses, fee_token.id)
+                    .await?;
+                // Rounding down would make the batch underpay, so the fee is rounded up.
+                closest_greater_or_eq_packable_fee_amount(&fee)
+            }
+        };
+
+        let count = items.len() as u32;
+        let (nonce, reserved) = match self.nonce {
+            Some(nonce) => (nonce, false),
+            None => (self.wallet.next_nonce(count).await?, true),
+        };
+
+        let result: Result<_, ClientError> = async {
+            let signer = &self.wallet.signer;
+            let mut txs = Vec::with_capacity(items.len());
+            for (index, item) in items.into_iter().enumerate() {
+                let tx_nonce = nonce + index as u32;
+                let tx_fee = if index == fee_payer {
+                    fee.clone()
+                } else {
+                    BigUint::zero()
+                };
+
+                let tx: Result<(ZkSyncTx, Token), _> = match item {
+                    BatchItem::Transfer { to, token, amount } => Transfer::new_signed(
+                        account_id,
+                        signer.address,
+                        to,
+                        token.id,
+                        amount,
+                        tx_fee,
+                        tx_nonce,
+                        time_range,
+                        &signer.private_key,
+                    )
+                    .map(|tx| (tx.into(), token)),
+                    BatchItem::Withdraw { to, token, amount } => Withdraw::new_signed(
+                        account_id,
+                        signer.address,
+                        to,
+                        token.id,
+                        amount,
+                        tx_fee,
+                        tx_nonce,
+                        time_range,
+                        &signer.private_key,
+                    )
+                    .map(|tx| (tx.into(), token)),
+                    BatchItem::TransferNFT { to, nft } => Transfer::new_signed(
+                        account_id,
+                        signer.address,
+                        to,
+                        nft.id,
+                        BigUint::from(1u16),
+                        tx_fee,
+                        tx_nonce,
+                        time_range,
+                        &signer.private_key,
+                    )
+                    .map(|tx| (tx.into(), Token::new_nft(nft.id, &nft.symbol))),
+                    BatchItem::WithdrawNFT { to, token } => WithdrawNFT::new_signed(
+                        account_id,
+                        signer.address,
+                        to,
+                        token,
+                        fee_token.id,
+                        tx_fee,
+                        tx_nonce,
+                        time_range,
+                        &signer.private_key,
+                    )
+                    .map(|tx| (ZkSyncTx::WithdrawNFT(Box::new(tx)), fee_token.clone())),
+                    BatchItem::MintNFT {
+                        recipient,
+                        content_hash,
+                    } => MintNFT::new_signed(
+                        account_id,
+                        signer.address,
+                        content_hash,
+                        recipient,
+                        tx_fee,
+                        fee_token.id,
+                        tx_nonce,
+                        &signer.private_key,
+                    )
+                    .map(|tx| (tx.into(), fee_token.clone())),
+                };
+                let tx = tx.map_err(|err| {
+                    ClientError::SigningError(SignerError::SigningFailed(err.to_string()))
+                })?;
+                txs.push(tx);
+            }
+
+            let eth_signature = signer
+                .sign_batch(txs.clone())
+                .await
+                .map_err(ClientError::SigningError)?;
+
+            Ok((
+                txs.into_iter().map(|(tx, _)| (tx, None)).collect(),
+                eth_signature,
+            ))
+        }
+        .await;
+        if result.is_err() && reserved {
+            self.wallet.release_nonces(nonce, count)?;
+        }
+        result
+    }
+
+    /// Returns the signed batch in the form suitable for storing and the subsequent
+    /// submission via `SignedBatch::send`, see the [offline](../offline/index.html) module.
+    pub async fn signed(self) -> Result<SignedBatch, ClientError> {
+        let (txs, eth_signature) = self.txs().await?;
+        Ok(SignedBatch::new(txs, eth_signature))
+    }
+
+    /// Sends the transactions batch, returning the handles for its transactions.
+    pub async fn send(self) -> Result<Vec<SyncTransactionHandle<P>>, ClientError> {
+        let wallet = self.wallet;
+        let provider = wallet.provider.clone();
+
+        let (txs, eth_signature) = self.txs().await?;
+        let nonces: Vec<_> = txs.iter().map(|(tx, _)| tx.nonce()).collect();
+        let tx_hashes = provider.send_txs_batch(txs, eth_signature).await;
+        wallet.track_submission(&nonces, tx_hashes.as_deref());
+
+        Ok(tx_hashes?
+            .into_iter()
+            .map(|tx_hash| SyncTransactionHandle::new(tx_hash, provider.clone()))
+            .collect())
+    }
+
+    /// Adds a transfer to the batch. If the provided amount is not packable,
+    /// rounds it to the closest packable amount.
+    /// Returns an error if token is not supported by zkSync.
+    pub fn add_transfer(
+        mut self,
+        to: Address,
+        token: impl Into<TokenLike>,
+        amount: impl Into<BigUint>,
+    ) -> Result<Self, ClientError> {
+        let token = self
+            .wallet
+            .tokens
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)?;
+        let amount = closest_packable_token_amount(&amount.into());
+
+        self.items.push(BatchItem::Transfer { to, token, amount });
+
+        Ok(self)
+    }
+
+    /// Adds a withdrawal to the batch. If the provided amount is not packable,
+    /// rounds it to the closest packable amount.
+    /// Returns an error if token is not supported by zkSync.
+    pub fn add_withdraw(
+        mut self,
+        to: Address,
+        token: impl Into<TokenLike>,
+        amount: impl Into<BigUint>,
+    ) -> Result<Self, ClientError> {
+        let token = self
+            .wallet
+            .tokens
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)?;
+        let amount = closest_packable_token_amount(&amount.into());
+
+        self.items.push(BatchItem::Withdraw { to, token, amount });
+
+        Ok(self)
+    }
+
+    /// Adds a transfer of the NFT to the batch.
+    pub fn add_transfer_nft(mut self, to: Address, nft: NFT) -> Self {
+        self.items.push(BatchItem::TransferNFT { to, nft });
+        self
+    }
+
+    /// Adds a withdrawal of the NFT to the batch. Returns an error if token is not an NFT.
+    pub fn add_withdraw_nft(mut self, to: Address, token: TokenId) -> Result<Self, ClientError> {
+        if token.0 < MIN_NFT_TOKEN_ID {
+            return Err(ClientError::UnknownToken);
+        }
+        self.items.push(BatchItem::WithdrawNFT { to, token });
+
+        Ok(self)
+    }
+
+    /// Adds a minting of the NFT with the given content hash to the batch.
+    pub fn add_mint_nft(mut self, recipient: Address, content_hash: H256) -> Self {
+        self.items.push(BatchItem::MintNFT {
+            recipient,
+            content_hash,
+        });
+        self
+    }
+
+    /// Sets the token the fee is paid in. Returns an error if token is not supported by zkSync.
+    pub fn fee_token(mut self, token: impl Into<TokenLike>) -> Result<Self, ClientError> {
+        let token = self
+            .wallet
+            .tokens
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)?;
+
+        self.fee_token = Some(token);
+
+        Ok(self)
+    }
+
+    /// Set the fee amount for the whole batch. If the provided fee is not packable,
+    /// rounds it to the closest packable fee amount.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee(mut self, fee: impl Into<BigUint>) -> Self {
+        let fee = closest_packable_fee_amount(&fee.into());
+        self.fee = Some(fee);
+
+        self
+    }
+
+    /// Set the fee amount for the whole batch. If the provided fee is not packable,
+    /// returns an error.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee_exact(mut self, fee: impl Into<BigUint>) -> Result<Self, ClientError> {
+        let fee = fee.into();
+        if !is_fee_amount_packable(&fee) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.fee = Some(fee);
+
+        Ok(self)
+    }
+
+    /// Sets the index of the transaction which pays the fee for the whole batch.
+    /// It must be either a transfer or withdrawal in the fee token, or an NFT minting or withdrawal.
+    ///
+    /// By default, the first suitable transaction is chosen. If there is none,
+    /// a zero transfer to the wallet itself is appended to pay the fee.
+    pub fn fee_payer(mut self, index: usize) -> Self {
+        self.fee_payer = Some(index);
+        self
+    }
+
+    /// Sets the nonce of the first transaction in the batch.
+    pub fn nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the unix format timestamp of the first moment when the transactions can be executed.
+    pub fn valid_from(mut self, valid_from: u64) -> Self {
+        self.valid_from = Some(valid_from);
+        self
+    }
+
+    /// Sets the unix format timestamp of the last moment when the transactions can be executed.
+    pub fn valid_until(mut self, valid_until: u64) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+}
@@ -0,0 +1,176 @@
+use num::{BigUint, Zero};
+use zksync_eth_signer::EthereumSigner;
+use zksync_types::{
+    helpers::{closest_packable_token_amount, is_token_amount_packable},
+    tx::TimeRange,
+    Address, Nonce, Token, TokenLike,
+};
+
+use crate::{error::ClientError, provider::Provider, types::SignedOrder, wallet::Wallet};
+
+/// Builder of the orders which can be handed to a counterparty to be executed in a `Swap`.
+///
+/// If the amount is not set, a limit order is created: swaps don't increment the nonce
+/// for such orders, so it can be filled several times until the account nonce changes.
+#[derive(Debug)]
+pub struct OrderBuilder<'a, S: EthereumSigner, P: Provider> {
+    wallet: &'a Wallet<S, P>,
+    token_sell: Option<Token>,
+    token_buy: Option<Token>,
+    ratio: Option<(BigUint, BigUint)>,
+    amount: Option<BigUint>,
+    recipient: Option<Address>,
+    nonce: Option<Nonce>,
+    valid_from: Option<u64>,
+    valid_until: Option<u64>,
+}
+
+impl<'a, S, P> OrderBuilder<'a, S, P>
+where
+    S: EthereumSigner,
+    P: Provider + Clone,
+{
+    /// Initializes an order building process.
+    pub fn new(wallet: &'a Wallet<S, P>) -> Self {
+        Self {
+            wallet,
+            token_sell: None,
+            token_buy: None,
+            ratio: None,
+            amount: None,
+            recipient: None,
+            nonce: None,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    /// Directly returns the signed order for the subsequent usage.
+    pub async fn order(self) -> Result<SignedOrder, ClientError> {
+        let token_sell = self
+            .token_sell
+            .ok_or_else(|| ClientError::MissingRequiredField("token_sell".into()))?;
+        let token_buy = self
+            .token_buy
+            .ok_or_else(|| ClientError::MissingRequiredField("token_buy".into()))?;
+        let ratio = self
+            .ratio
+            .ok_or_else(|| ClientError::MissingRequiredField("ratio".into()))?;
+        let amount = self.amount.unwrap_or_else(BigUint::zero);
+        let recipient = self.recipient.unwrap_or_else(|| self.wallet.address());
+        let valid_from = self.valid_from.unwrap_or(0);
+        let valid_until = self.valid_until.unwrap_or(u64::MAX);
+
+        let nonce = match self.nonce {
+            Some(nonce) => nonce,
+            None => {
+                let account_info = self
+                    .wallet
+                    .provider
+                    .account_info(self.wallet.address())
+                    .await?;
+                account_info.committed.nonce
+            }
+        };
+
+        self.wallet
+            .signer
+            .sign_order(
+                recipient,
+                token_sell,
+                token_buy,
+                ratio,
+                amount,
+                nonce,
+                TimeRange::new(valid_from, valid_until),
+            )
+            .await
+            .map(|(order, eth_signature)| SignedOrder {
+                order,
+                eth_signature,
+            })
+            .map_err(ClientError::SigningError)
+    }
+
+    /// Sets the token to sell. Returns an error if token is not supported by zkSync.
+    pub fn token_sell(mut self, token: impl Into<TokenLike>) -> Result<Self, ClientError> {
+        let token = self
+            .wallet
+            .tokens
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)?;
+
+        self.token_sell = Some(token);
+
+        Ok(self)
+    }
+
+    /// Sets the token to buy. Returns an error if token is not supported by zkSync.
+    pub fn token_buy(mut self, token: impl Into<TokenLike>) -> Result<Self, ClientError> {
+        let token = self
+            .wallet
+            .tokens
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)?;
+
+        self.token_buy = Some(token);
+
+        Ok(self)
+    }
+
+    /// Sets the exchange ratio: `sell` units of the sold token for `buy` units of the bought one.
+    /// Both values are expected in the smallest units of the tokens (e.g. wei).
+    pub fn ratio(mut self, sell: impl Into<BigUint>, buy: impl Into<BigUint>) -> Self {
+        self.ratio = Some((sell.into(), buy.into()));
+        self
+    }
+
+    /// Set the amount of the sold token. If the provided amount is not packable,
+    /// rounds it to the closest packable amount.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn amount(mut self, amount: impl Into<BigUint>) -> Self {
+        let amount = closest_packable_token_amount(&amount.into());
+        self.amount = Some(amount);
+
+        self
+    }
+
+    /// Set the amount of the sold token. If the provided amount is not packable,
+    /// returns an error.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn amount_exact(mut self, amount: impl Into<BigUint>) -> Result<Self, ClientError> {
+        let amount = amount.into();
+        if !is_token_amount_packable(&amount) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.amount = Some(amount);
+
+        Ok(self)
+    }
+
+    /// Sets the recipient of the bought tokens. By default, it's the wallet address.
+    pub fn recipient(mut self, recipient: Address) -> Self {
+        self.recipient = Some(recipient);
+        self
+    }
+
+    /// Sets the unix format timestamp of the first moment when the order can be executed.
+    pub fn valid_from(mut self, valid_from: u64) -> Self {
+        self.valid_from = Some(valid_from);
+        self
+    }
+
+    /// Sets the unix format timestamp of the last moment when the order can be executed.
+    pub fn valid_until(mut self, valid_until: u64) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+
+    /// Sets the order nonce.
+    pub fn nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+}
@@ -0,0 +1,232 @@
+use num::{BigUint, Zero};
+use zksync_eth_signer::EthereumSigner;
+use zksync_types::{
+    helpers::{
+        closest_packable_fee_amount, closest_packable_token_amount, is_fee_amount_packable,
+        is_token_amount_packable,
+    },
+    tx::PackedEthSignature,
+    Nonce, Swap, Token, TokenLike, TxFeeTypes,
+};
+
+use crate::{
+    error::ClientError, offline::SignedTransaction, operations::SyncTransactionHandle,
+    provider::Provider, types::SignedOrder, wallet::Wallet,
+};
+
+/// Builder of the `Swap` transaction, which is submitted (and paid for) by the wallet
+/// and executes two matching orders signed by their owners.
+#[derive(Debug)]
+pub struct SwapBuilder<'a, S: EthereumSigner, P: Provider> {
+    wallet: &'a Wallet<S, P>,
+    orders: Option<(SignedOrder, SignedOrder)>,
+    amounts: Option<(BigUint, BigUint)>,
+    fee_token: Option<Token>,
+    fee: Option<BigUint>,
+    nonce: Option<Nonce>,
+}
+
+impl<'a, S, P> SwapBuilder<'a, S, P>
+where
+    S: EthereumSigner,
+    P: Provider + Clone,
+{
+    /// Initializes a swap transaction building process.
+    pub fn new(wallet: &'a Wallet<S, P>) -> Self {
+        Self {
+            wallet,
+            orders: None,
+            amounts: None,
+            fee_token: None,
+            fee: None,
+            nonce: None,
+        }
+    }
+
+    /// Directly returns the signed swap transaction for the subsequent usage.
+    ///
+    /// Ethereum signatures of the orders are not included, see `SwapBuilder::send`.
+    pub async fn tx(self) -> Result<(Swap, Option<PackedEthSignature>), ClientError> {
+        let orders = self
+            .orders
+            .ok_or_else(|| ClientError::MissingRequiredField("orders".into()))?;
+        let fee_token = self
+            .fee_token
+            .ok_or_else(|| ClientError::MissingRequiredField("fee_token".into()))?;
+        // Amounts of the non-limit orders must match the swap amounts exactly.
+        let amounts = match self.amounts {
+            Some(amounts) => amounts,
+            None if !orders.0.order.amount.is_zero() && !orders.1.order.amount.is_zero() => {
+                (orders.0.order.amount.clone(), orders.1.order.amount.clone())
+            }
+            None => return Err(ClientError::MissingRequiredField("amounts".into())),
+        };
+
+        let fee = match self.fee {
+            Some(fee) => fee,
+            None => {
+                let fee = self
+                    .wallet
+                    .provider
+                    .get_tx_fee(TxFeeTypes::Swap, self.wallet.address(), fee_token.id)
+                    .await?;
+                fee.total_fee
+            }
+        };
+
+        let (nonce, reserved) = match self.nonce {
+            Some(nonce) => (nonce, false),
+            None => (self.wallet.next_nonce(1).await?, true),
+        };
+
+        let result = self
+            .wallet
+            .signer
+            .sign_swap(
+                (orders.0.order, orders.1.order),
+                amounts,
+                fee_token,
+                fee,
+                nonce,
+            )
+            .await
+            .map_err(ClientError::SigningError);
+        if result.is_err() && reserved {
+            self.wallet.release_nonces(nonce, 1)?;
+        }
+        result
+    }
+
+    /// Returns the signed swap along with the Ethereum signatures of the orders in the form
+    /// suitable for storing and the subsequent submission via `SignedTransaction::send`,
+    /// see the [offline](../offline/index.html) module.
+    pub async fn signed(self) -> Result<SignedTransaction, ClientError> {
+        let orders_signatures = self
+            .orders
+            .as_ref()
+            .map(|orders| {
+                (
+                    orders.0.eth_signature.clone(),
+                    orders.1.eth_signature.clone(),
+                )
+            })
+            .unwrap_or_default();
+
+        let (swap, eth_signature) = self.tx().await?;
+        Ok(SignedTransaction::swap(
+            swap,
+            eth_signature,
+            orders_signatures,
+        ))
+    }
+
+    /// Sends the transaction along with the Ethereum signatures of the orders,
+    /// returning the handle for its awaiting.
+    pub async fn send(self) -> Result<SyncTransactionHandle<P>, ClientError> {
+        let wallet = self.wallet;
+        let provider = wallet.provider.clone();
+        let orders_signatures = self
+            .orders
+            .as_ref()
+            .map(|orders| {
+                (
+                    orders.0.eth_signature.clone(),
+                    orders.1.eth_signature.clone(),
+                )
+            })
+            .unwrap_or_default();
+
+        let (swap, eth_signature) = self.tx().await?;
+        let nonce = swap.nonce;
+        let tx_hash = provider
+            .send_swap(swap, eth_signature, orders_signatures)
+            .await;
+        wallet.track_submission(&[nonce], tx_hash.as_ref().map(std::slice::from_ref));
+
+        Ok(SyncTransactionHandle::new(tx_hash?, provider))
+    }
+
+    /// Sets the orders to be executed. The first order is the one which sells
+    /// the token bought by the second order and vice versa.
+    pub fn orders(mut self, first: SignedOrder, second: SignedOrder) -> Self {
+        self.orders = Some((first, second));
+        self
+    }
+
+    /// Sets the amounts sold by the first and the second order respectively.
+    /// If the provided amounts are not packable, rounds them to the closest packable amounts.
+    ///
+    /// Amounts are required if any of the orders is a limit one, otherwise
+    /// the order amounts are used by default.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn amounts(mut self, first: impl Into<BigUint>, second: impl Into<BigUint>) -> Self {
+        self.amounts = Some((
+            closest_packable_token_amount(&first.into()),
+            closest_packable_token_amount(&second.into()),
+        ));
+        self
+    }
+
+    /// Sets the amounts sold by the first and the second order respectively.
+    /// If the provided amounts are not packable, returns an error.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn amounts_exact(
+        mut self,
+        first: impl Into<BigUint>,
+        second: impl Into<BigUint>,
+    ) -> Result<Self, ClientError> {
+        let (first, second) = (first.into(), second.into());
+        if !is_token_amount_packable(&first) || !is_token_amount_packable(&second) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.amounts = Some((first, second));
+
+        Ok(self)
+    }
+
+    /// Sets the token the fee is paid in. Returns an error if token is not supported by zkSync.
+    pub fn fee_token(mut self, token: impl Into<TokenLike>) -> Result<Self, ClientError> {
+        let token = self
+            .wallet
+            .tokens
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)?;
+
+        self.fee_token = Some(token);
+
+        Ok(self)
+    }
+
+    /// Set the fee amount. If the provided fee is not packable,
+    /// rounds it to the closest packable fee amount.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee(mut self, fee: impl Into<BigUint>) -> Self {
+        let fee = closest_packable_fee_amount(&fee.into());
+        self.fee = Some(fee);
+
+        self
+    }
+
+    /// Set the fee amount. If the provided fee is not packable,
+    /// returns an error.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee_exact(mut self, fee: impl Into<BigUint>) -> Result<Self, ClientError> {
+        let fee = fee.into();
+        if !is_fee_amount_packable(&fee) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.fee = Some(fee);
+
+        Ok(self)
+    }
+
+    /// Sets the transaction nonce.
+    pub fn nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+}
@@ -10,7 +10,8 @@ use zksync_types::{
 };
 
 use crate::{
-    error::ClientError, operations::SyncTransactionHandle, provider::Provider, wallet::Wallet,
+    error::ClientError, offline::SignedTransaction, operations::SyncTransactionHandle,
+    provider::Provider, wallet::Wallet,
 };
 use zksync_types::tx::TimeRange;
 
@@ -59,18 +60,6 @@ where
         let valid_from = self.valid_from.unwrap_or(0);
         let valid_until = self.valid_until.unwrap_or(u64::MAX);
 
-        let nonce = match self.nonce {
-            Some(nonce) => nonce,
-            None => {
-                let account_info = self
-                    .wallet
-                    .provider
-                    .account_info(self.wallet.address())
-                    .await?;
-                account_info.committed.nonce
-            }
-        };
-
         let fee = match self.fee {
             Some(fee) => fee,
             None => {
@@ -83,7 +72,13 @@ where
             }
         };
 
-        self.wallet
+        let (nonce, reserved) = match self.nonce {
+            Some(nonce) => (nonce, false),
+            None => (self.wallet.next_nonce(1).await?, true),
+        };
+
+        let result = self
+            .wallet
             .signer
             .sign_transfer(
                 token,
@@ -95,17 +90,31 @@ where
             )
             .await
             .map(|(tx, signature)| (ZkSyncTx::Transfer(Box::new(tx)), signature))
-            .map_err(ClientError::SigningError)
+            .map_err(ClientError::SigningError);
+        if result.is_err() && reserved {
+            self.wallet.release_nonces(nonce, 1)?;
+        }
+        result
+    }
+
+    /// Returns the signed transaction in the form suitable for storing and the subsequent
+    /// submission via `SignedTransaction::send`, see the [offline](../offline/index.html) module.
+    pub async fn signed(self) -> Result<SignedTransaction, ClientError> {
+        let (tx, eth_signature) = self.tx().await?;
+        Ok(SignedTransaction::new(tx, eth_signature))
     }
 
     /// Sends the transaction, returning the handle for its awaiting.
     pub async fn send(self) -> Result<SyncTransactionHandle<P>, ClientError> {
-        let provider = self.wallet.provider.clone();
+        let wallet = self.wallet;
+        let provider = wallet.provider.clone();
 
         let (tx, eth_signature) = self.tx().await?;
-        let tx_hash = provider.send_tx(tx, eth_signature).await?;
+        let nonce = tx.nonce();
+        let tx_hash = provider.send_tx(tx, eth_signature).await;
+        wallet.track_submission(&[nonce], tx_hash.as_ref().map(std::slice::from_ref));
 
-        Ok(SyncTransactionHandle::new(tx_hash, provider))
+        Ok(SyncTransactionHandle::new(tx_hash?, provider))
     }
 
     /// Sets the transaction token. Returns an error if token is not supported by zkSync.
@@ -7,8 +7,8 @@ use zksync_types::{
 };
 
 use crate::{
-    error::ClientError, operations::SyncTransactionHandle, provider::Provider, types::NFT,
-    wallet::Wallet,
+    error::ClientError, offline::SignedBatch, operations::SyncTransactionHandle,
+    provider::Provider, types::NFT, wallet::Wallet,
 };
 use zksync_types::tx::TimeRange;
 
@@ -65,18 +65,6 @@ where
         let valid_from = self.valid_from.unwrap_or(0);
         let valid_until = self.valid_until.unwrap_or(u64::MAX);
 
-        let nonce = match self.nonce {
-            Some(nonce) => nonce,
-            None => {
-                let account_info = self
-                    .wallet
-                    .provider
-                    .account_info(self.wallet.address())
-                    .await?;
-                account_info.committed.nonce
-            }
-        };
-
         let fee = match self.fee {
             Some(fee) => fee,
             None => {
@@ -91,48 +79,69 @@ where
             }
         };
 
-        let nft_token = Token::new_nft(nft.id, &nft.symbol);
-        let (tx_nft, tx_nft_signature) = self
-            .wallet
-            .signer
-            .sign_transfer(
-                nft_token,
-                BigUint::from(1u16),
-                BigUint::from(0u16),
-                to,
-                nonce,
-                TimeRange::new(valid_from, valid_until),
-            )
-            .await
-            .map(|(tx, signature)| (ZkSyncTx::Transfer(Box::new(tx)), signature))
-            .map_err(ClientError::SigningError)?;
-        let (tx_fee, tx_fee_signature) = self
-            .wallet
-            .signer
-            .sign_transfer(
-                fee_token,
-                BigUint::from(0u16),
-                fee,
-                to,
-                nonce + 1,
-                TimeRange::new(valid_from, valid_until),
-            )
-            .await
-            .map(|(tx, signature)| (ZkSyncTx::Transfer(Box::new(tx)), signature))
-            .map_err(ClientError::SigningError)?;
-
-        Ok(((tx_nft, tx_nft_signature), (tx_fee, tx_fee_signature)))
+        let (nonce, reserved) = match self.nonce {
+            Some(nonce) => (nonce, false),
+            None => (self.wallet.next_nonce(2).await?, true),
+        };
+
+        let result: Result<_, ClientError> = async {
+            let nft_token = Token::new_nft(nft.id, &nft.symbol);
+            let (tx_nft, tx_nft_signature) = self
+                .wallet
+                .signer
+                .sign_transfer(
+                    nft_token,
+                    BigUint::from(1u16),
+                    BigUint::from(0u16),
+                    to,
+                    nonce,
+                    TimeRange::new(valid_from, valid_until),
+                )
+                .await
+                .map(|(tx, signature)| (ZkSyncTx::Transfer(Box::new(tx)), signature))
+                .map_err(ClientError::SigningError)?;
+            let (tx_fee, tx_fee_signature) = self
+                .wallet
+                .signer
+                .sign_transfer(
+                    fee_token,
+                    BigUint::from(0u16),
+                    fee,
+                    to,
+                    nonce + 1,
+                    TimeRange::new(valid_from, valid_until),
+                )
+                .await
+                .map(|(tx, signature)| (ZkSyncTx::Transfer(Box::new(tx)), signature))
+                .map_err(ClientError::SigningError)?;
+
+            Ok(((tx_nft, tx_nft_signature), (tx_fee, tx_fee_signature)))
+        }
+        .await;
+        if result.is_err() && reserved {
+            self.wallet.release_nonces(nonce, 2)?;
+        }
+        result
+    }
+
+    /// Returns the signed batch in the form suitable for storing and the subsequent
+    /// submission via `SignedBatch::send`, see the [offline](../offline/index.html) module.
+    pub async fn signed(self) -> Result<SignedBatch, ClientError> {
+        let (tx_nft, tx_fee) = self.tx().await?;
+        Ok(SignedBatch::new(vec![tx_nft, tx_fee], None))
     }
 
     /// Sends the transaction batch, returning the hashes of its transactions.
     pub async fn send(self) -> Result<Vec<SyncTransactionHandle<P>>, ClientError> {
-        let provider = self.wallet.provider.clone();
+        let wallet = self.wallet;
+        let provider = wallet.provider.clone();
 
         let (tx_nft, tx_fee) = self.tx().await?;
-        println!("tx is got");
-        let tx_hashes = provider.send_txs_batch(vec![tx_nft, tx_fee], None).await?;
+        let nonces = [tx_nft.0.nonce(), tx_fee.0.nonce()];
+        let tx_hashes = provider.send_txs_batch(vec![tx_nft, tx_fee], None).await;
+        wallet.track_submission(&nonces, tx_hashes.as_deref());
 
-        Ok(tx_hashes
+        Ok(tx_hashes?
             .into_iter()
             .map(|tx_hash| SyncTransactionHandle::new(tx_hash, provider.clone()))
             .collect())
@@ -13,7 +13,7 @@ use num::BigUint;
 use zksync_types::{
     network::Network,
     tx::{PackedEthSignature, TxHash, ZkSyncTx},
-    Address, TokenLike, TxFeeTypes,
+    Address, Swap, TokenLike, TxFeeTypes,
 };
 
 // Local uses
@@ -86,6 +86,20 @@ pub trait Provider {
         eth_signature: Option<PackedEthSignature>,
     ) -> ResponseResult<TxHash>;
 
+    /// Submits a swap to the zkSync network along with the Ethereum signatures of its orders.
+    /// Returns the hash of the created transaction.
+    ///
+    /// The default implementation returns `ClientError::NotSupported`, so the providers
+    /// implemented before the swaps support keep compiling.
+    async fn send_swap(
+        &self,
+        _swap: Swap,
+        _eth_signature: Option<PackedEthSignature>,
+        _orders_signatures: (Option<PackedEthSignature>, Option<PackedEthSignature>),
+    ) -> ResponseResult<TxHash> {
+        Err(ClientError::NotSupported("swaps".into()))
+    }
+
     /// Submits a batch of transactions to the zkSync network.
     /// Returns the hashes of the created transactions.
     async fn send_txs_batch(
@@ -174,6 +188,16 @@ impl Provider for RpcProvider {
         self.send_and_deserialize(&msg).await
     }
 
+    async fn send_swap(
+        &self,
+        swap: Swap,
+        eth_signature: Option<PackedEthSignature>,
+        orders_signatures: (Option<PackedEthSignature>, Option<PackedEthSignature>),
+    ) -> ResponseResult<TxHash> {
+        let msg = JsonRpcRequest::submit_swap(swap, eth_signature, orders_signatures);
+        self.send_and_deserialize(&msg).await
+    }
+
     async fn send_txs_batch(
         &self,
         txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
@@ -324,8 +348,8 @@ impl RpcProvider {
 mod messages {
     use serde::Serialize;
     use zksync_types::{
-        tx::{PackedEthSignature, TxEthSignature, TxHash, ZkSyncTx},
-        Address, TokenLike, TxFeeTypes,
+        tx::{PackedEthSignature, TxEthSignature, TxEthSignatureVariant, TxHash, ZkSyncTx},
+        Address, Swap, TokenLike, TxFeeTypes,
     };
 
     #[derive(Debug, Serialize)]
@@ -373,6 +397,20 @@ mod messages {
             Self::create("tx_submit", params)
         }
 
+        pub fn submit_swap(
+            swap: Swap,
+            eth_signature: Option<PackedEthSignature>,
+            orders_signatures: (Option<PackedEthSignature>, Option<PackedEthSignature>),
+        ) -> Self {
+            let signatures = TxEthSignatureVariant::Triple(
+                eth_signature.map(TxEthSignature::EthereumSignature),
+                orders_signatures.0.map(TxEthSignature::EthereumSignature),
+                orders_signatures.1.map(TxEthSignature::EthereumSignature),
+            );
+            let params = json_values![ZkSyncTx::from(swap), signatures];
+            Self::create("tx_submit", params)
+        }
+
         pub fn submit_tx_batch(
             txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
             eth_signature: Option<PackedEthSignature>,
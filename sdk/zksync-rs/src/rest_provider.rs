@@ -0,0 +1,444 @@
+//! `Provider` implementation on top of the REST API v0.2.
+//!
+//! Unlike the JSON RPC API, REST API distinguishes the committed and finalized states
+//! and allows to paginate through the account transactions history.
+
+// External uses
+use async_trait::async_trait;
+use num::BigUint;
+use serde::{de::DeserializeOwned, Deserialize};
+
+// Workspace uses
+use zksync_api_client::rest::client::{Client, ClientError as RestClientError};
+use zksync_api_types::{
+    v02::{
+        account::AccountState as ApiAccountState,
+        fee::{ApiFee, ApiTxFeeTypes, TxInBatchFeeRequest},
+        pagination::{ApiEither, Paginated, PaginationDirection, PaginationQuery, MAX_LIMIT},
+        token::ApiToken,
+        transaction::{
+            L2Transaction, Receipt, SubmitBatchResponse, Transaction, TransactionData, TxData,
+        },
+        Response,
+    },
+    TxWithSignature,
+};
+use zksync_types::{
+    network::Network,
+    tokens::ChangePubKeyFeeTypeArg,
+    tx::{
+        ChangePubKeyType, EthBatchSignatures, PackedEthSignature, TxEthSignature,
+        TxEthSignatureVariant, TxHash, ZkSyncTx,
+    },
+    Address, Swap, Token, TokenId, TokenKind, TokenLike, TxFeeTypes,
+};
+
+// Local uses
+use crate::{
+    error::ClientError,
+    provider::{Provider, ResponseResult},
+    types::*,
+};
+
+/// Returns a corresponding REST API address for a provided network name.
+pub fn get_rest_api_addr(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => "https://api.zksync.io",
+        Network::Rinkeby => "https://rinkeby-api.zksync.io",
+        Network::Ropsten => "https://ropsten-api.zksync.io",
+        Network::Localhost => "http://127.0.0.1:3001",
+        Network::Unknown => panic!("Attempt to create a provider from an unknown network"),
+        Network::Test => panic!("Attempt to create a provider from an test network"),
+        Network::Goerli => "https://goerli-api.zksync.io",
+        Network::Sepolia => "https://sepolia-api.zksync.io",
+    }
+}
+
+/// Part of the `config` endpoint response required by the provider.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiConfig {
+    contract: Address,
+    gov_contract: Address,
+}
+
+/// `RestProvider` is capable of interacting with the zkSync node via its
+/// REST API v0.2.
+#[derive(Debug, Clone)]
+pub struct RestProvider {
+    client: Client,
+    network: Network,
+}
+
+#[async_trait]
+impl Provider for RestProvider {
+    /// Requests and returns information about a zkSync account given its address.
+    /// The `verified` state of the returned account is the finalized one.
+    async fn account_info(&self, address: Address) -> ResponseResult<AccountInfo> {
+        let response = self
+            .client
+            .account_full_info(&format!("{:?}", address))
+            .await;
+        let state: ApiAccountState = response_result(response)?;
+
+        let id = state
+            .committed
+            .as_ref()
+            .or_else(|| state.finalized.as_ref())
+            .map(|account| account.account_id);
+        Ok(AccountInfo {
+            address,
+            id,
+            depositing: state.depositing.into(),
+            committed: state.committed.map(Into::into).unwrap_or_default(),
+            verified: state.finalized.map(Into::into).unwrap_or_default(),
+        })
+    }
+
+    async fn tokens(&self) -> ResponseResult<Tokens> {
+        let mut tokens = Tokens::new();
+        let mut from = TokenId(0);
+        loop {
+            let query = PaginationQuery {
+                from: ApiEither::from(from),
+                limit: MAX_LIMIT,
+                direction: PaginationDirection::Newer,
+            };
+            let response = self.client.token_pagination(&query).await;
+            let page: Paginated<ApiToken, TokenId> = response_result(response)?;
+
+            let last_id = match page.list.last() {
+                Some(token) => token.id,
+                None => break,
+            };
+            let page_len = page.list.len();
+            tokens.extend(page.list.into_iter().map(|token| {
+                let token = Token::new(
+                    token.id,
+                    token.address,
+                    &token.symbol,
+                    token.decimals,
+                    TokenKind::ERC20,
+                );
+                (token.symbol.clone(), token)
+            }));
+
+            if page_len < MAX_LIMIT as usize {
+                break;
+            }
+            // Pagination is inclusive, so the next page starts right after the last token.
+            from = last_id + 1;
+        }
+        Ok(tokens)
+    }
+
+    async fn tx_info(&self, tx_hash: TxHash) -> ResponseResult<TransactionInfo> {
+        let response = self.client.tx_status(tx_hash).await;
+        let receipt: Option<Receipt> = response_result(response)?;
+
+        Ok(receipt.map(Into::into).unwrap_or(TransactionInfo {
+            executed: false,
+            success: None,
+            fail_reason: None,
+            block: None,
+        }))
+    }
+
+    /// Obtains minimum fee required to process transaction in zkSync network.
+    ///
+    /// REST API doesn't provide the gas amount and price, so these fields are zero,
+    /// and the fee type of the transfers to new accounts is reported as `Transfer`.
+    async fn get_tx_fee(
+        &self,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<Fee> {
+        let response = self
+            .client
+            .get_txs_fee(tx_type.into(), address, token.into())
+            .await;
+        let fee: ApiFee = response_result(response)?;
+
+        Ok(Fee {
+            fee_type: output_fee_type(tx_type),
+            gas_tx_amount: BigUint::default(),
+            gas_price_wei: BigUint::default(),
+            gas_fee: fee.gas_fee,
+            zkp_fee: fee.zkp_fee,
+            total_fee: fee.total_fee,
+        })
+    }
+
+    async fn get_txs_batch_fee(
+        &self,
+        tx_types: Vec<TxFeeTypes>,
+        addresses: Vec<Address>,
+        token: impl Into<TokenLike> + Send + 'async_trait,
+    ) -> ResponseResult<BigUint> {
+        let transactions = tx_types
+            .into_iter()
+            .zip(addresses)
+            .map(|(tx_type, address)| TxInBatchFeeRequest {
+                tx_type: ApiTxFeeTypes::from(tx_type),
+                address,
+            })
+            .collect();
+        let response = self.client.get_batch_fee(transactions, token.into()).await;
+        let fee: ApiFee = response_result(response)?;
+
+        Ok(fee.total_fee)
+    }
+
+    /// REST API identifies priority operations by their Ethereum transaction hashes,
+    /// so this method is not supported. Use `RestProvider::tx_info` with the hash
+    /// of the Ethereum transaction instead.
+    async fn ethop_info(&self, _serial_id: u32) -> ResponseResult<EthOpInfo> {
+        Err(ClientError::NotSupported(
+            "priority operations by serial id".into(),
+        ))
+    }
+
+    async fn get_eth_tx_for_withdrawal(
+        &self,
+        withdrawal_hash: TxHash,
+    ) -> ResponseResult<Option<String>> {
+        let response = self.client.tx_data(withdrawal_hash).await;
+        let data: Option<TxData> = response_result(response)?;
+
+        let eth_tx_hash = data.and_then(|data| match data.tx.op {
+            TransactionData::L2(L2Transaction::Withdraw(withdraw)) => withdraw.eth_tx_hash,
+            TransactionData::L2(L2Transaction::WithdrawNFT(withdraw)) => withdraw.eth_tx_hash,
+            TransactionData::L2(L2Transaction::ForcedExit(forced_exit)) => forced_exit.eth_tx_hash,
+            _ => None,
+        });
+        Ok(eth_tx_hash.map(|hash| format!("{:?}", hash)))
+    }
+
+    async fn contract_address(&self) -> ResponseResult<ContractAddress> {
+        let response = self.client.config().await;
+        let config: ApiConfig = response_result(response)?;
+
+        Ok(ContractAddress {
+            main_contract: format!("{:?}", config.contract),
+            gov_contract: format!("{:?}", config.gov_contract),
+        })
+    }
+
+    async fn send_tx(
+        &self,
+        tx: ZkSyncTx,
+        eth_signature: Option<PackedEthSignature>,
+    ) -> ResponseResult<TxHash> {
+        let signature =
+            TxEthSignatureVariant::Single(eth_signature.map(TxEthSignature::EthereumSignature));
+        let response = self.client.submit_tx(tx, signature).await;
+        response_result(response)
+    }
+
+    async fn send_swap(
+        &self,
+        swap: Swap,
+        eth_signature: Option<PackedEthSignature>,
+        orders_signatures: (Option<PackedEthSignature>, Option<PackedEthSignature>),
+    ) -> ResponseResult<TxHash> {
+        let signature = TxEthSignatureVariant::Triple(
+            eth_signature.map(TxEthSignature::EthereumSignature),
+            orders_signatures.0.map(TxEthSignature::EthereumSignature),
+            orders_signatures.1.map(TxEthSignature::EthereumSignature),
+        );
+        let response = self
+            .client
+            .submit_tx(ZkSyncTx::Swap(Box::new(swap)), signature)
+            .await;
+        response_result(response)
+    }
+
+    async fn send_txs_batch(
+        &self,
+        txs_signed: Vec<(ZkSyncTx, Option<PackedEthSignature>)>,
+        eth_signature: Option<PackedEthSignature>,
+    ) -> ResponseResult<Vec<TxHash>> {
+        let txs = txs_signed
+            .into_iter()
+            .map(|(tx, signature)| TxWithSignature {
+                tx,
+                signature: TxEthSignatureVariant::Single(
+                    signature.map(TxEthSignature::EthereumSignature),
+                ),
+            })
+            .collect();
+        let signature = eth_signature.map(|signature| {
+            EthBatchSignatures::Single(TxEthSignature::EthereumSignature(signature))
+        });
+
+        let response = self.client.submit_batch(txs, signature).await;
+        let response: SubmitBatchResponse = response_result(response)?;
+        Ok(response
+            .transaction_hashes
+            .into_iter()
+            .map(|tx_hash| tx_hash.0)
+            .collect())
+    }
+
+    fn network(&self) -> Network {
+        self.network
+    }
+}
+
+impl RestProvider {
+    /// Creates a new `RestProvider` connected to the desired zkSync network.
+    pub fn new(network: Network) -> Self {
+        Self::from_addr_and_network(get_rest_api_addr(network), network)
+    }
+
+    /// Creates a new `RestProvider` connected to a custom address and the desired zkSync network.
+    /// The address is expected without the API version scope, e.g. `http://127.0.0.1:3001`.
+    pub fn from_addr_and_network(rest_api_addr: impl Into<String>, network: Network) -> Self {
+        Self {
+            client: Client::new(rest_api_addr.into()),
+            network,
+        }
+    }
+
+    /// Requests a page of the account transactions history.
+    ///
+    /// The page starts with the transaction `from` (inclusive) or with the latest transaction
+    /// of the account if it's not set.
+    pub async fn account_txs(
+        &self,
+        address: Address,
+        from: Option<TxHash>,
+        limit: u32,
+        direction: PaginationDirection,
+    ) -> ResponseResult<Vec<Transaction>> {
+        let from = match from {
+            Some(tx_hash) => ApiEither::from(tx_hash),
+            None => "latest".parse().unwrap(),
+        };
+        let query = PaginationQuery {
+            from,
+            limit,
+            direction,
+        };
+        let response = self
+            .client
+            .account_txs(&query, &format!("{:?}", address))
+            .await;
+        let page: Paginated<Transaction, serde_json::Value> = response_result(response)?;
+
+        Ok(page.list)
+    }
+
+    /// Returns the paginator over the account transactions history,
+    /// starting with the latest transaction of the account.
+    pub fn account_history(&self, address: Address) -> AccountHistory<'_> {
+        AccountHistory {
+            provider: self,
+            address,
+            last_tx_hash: None,
+            finished: false,
+        }
+    }
+}
+
+/// Paginator over the account transactions history, from the newest transactions to the oldest ones.
+///
+/// Created by `RestProvider::account_history`.
+#[derive(Debug)]
+pub struct AccountHistory<'a> {
+    provider: &'a RestProvider,
+    address: Address,
+    last_tx_hash: Option<TxHash>,
+    finished: bool,
+}
+
+impl AccountHistory<'_> {
+    /// Requests the next page of the history. Returns `None` once all the transactions were received.
+    pub async fn next_page(&mut self) -> ResponseResult<Option<Vec<Transaction>>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let mut txs = self
+            .provider
+            .account_txs(
+                self.address,
+                self.last_tx_hash,
+                MAX_LIMIT,
+                PaginationDirection::Older,
+            )
+            .await?;
+        if txs.len() < MAX_LIMIT as usize {
+            self.finished = true;
+        }
+        // Pagination is inclusive, so the last received transaction is skipped.
+        if self.last_tx_hash.is_some() && !txs.is_empty() {
+            txs.remove(0);
+        }
+
+        if txs.is_empty() {
+            self.finished = true;
+            return Ok(None);
+        }
+        self.last_tx_hash = txs.last().map(|tx| tx.tx_hash);
+        Ok(Some(txs))
+    }
+}
+
+/// Extracts the result of the REST API response, turning the error responses into `ClientError`.
+fn response_result<T: DeserializeOwned>(
+    response: Result<Response, RestClientError>,
+) -> ResponseResult<T> {
+    let response = response.map_err(|err| ClientError::NetworkError(err.to_string()))?;
+    if let Some(error) = response.error {
+        let message = error
+            .get("message")
+            .and_then(|message| message.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| error.to_string());
+        let code = error
+            .get("code")
+            .and_then(|code| code.as_u64())
+            .unwrap_or_default() as u16;
+        return Err(ClientError::RestApiError { code, message });
+    }
+
+    let result = response.result.unwrap_or(serde_json::Value::Null);
+    serde_json::from_value(result).map_err(|err| ClientError::MalformedResponse(err.to_string()))
+}
+
+fn output_fee_type(tx_type: TxFeeTypes) -> OutputFeeType {
+    match tx_type {
+        TxFeeTypes::Transfer => OutputFeeType::Transfer,
+        TxFeeTypes::Withdraw => OutputFeeType::Withdraw,
+        TxFeeTypes::FastWithdraw => OutputFeeType::FastWithdraw,
+        TxFeeTypes::MintNFT => OutputFeeType::MintNFT,
+        TxFeeTypes::WithdrawNFT => OutputFeeType::WithdrawNFT,
+        TxFeeTypes::FastWithdrawNFT => OutputFeeType::FastWithdrawNFT,
+        TxFeeTypes::Swap => OutputFeeType::Swap,
+        TxFeeTypes::ChangePubKey(arg) => {
+            let fee_type = match arg {
+                ChangePubKeyFeeTypeArg::PreContracts4Version {
+                    onchain_pubkey_auth: true,
+                } => ChangePubKeyFeeType::Onchain,
+                ChangePubKeyFeeTypeArg::PreContracts4Version {
+                    onchain_pubkey_auth: false,
+                } => ChangePubKeyFeeType::ECDSA,
+                ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyType::Onchain) => {
+                    ChangePubKeyFeeType::Onchain
+                }
+                ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyType::ECDSA) => {
+                    ChangePubKeyFeeType::ECDSA
+                }
+                ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyType::CREATE2) => {
+                    ChangePubKeyFeeType::CREATE2
+                }
+                ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyType::EIP712) => {
+                    ChangePubKeyFeeType::EIP712
+                }
+            };
+            OutputFeeType::ChangePubKey(fee_type)
+        }
+    }
+}
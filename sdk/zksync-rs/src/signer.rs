@@ -8,11 +8,11 @@ use zksync_eth_signer::{error::SignerError, EthereumSigner};
 use zksync_types::{
     tx::{
         eip712_signature::Eip712Domain, ChangePubKey, ChangePubKeyECDSAData,
-        ChangePubKeyEIP712Data, ChangePubKeyEthAuthData, PackedEthSignature, TimeRange,
-        TxEthSignature,
+        ChangePubKeyEIP712Data, ChangePubKeyEthAuthData, EthBatchSignData, PackedEthSignature,
+        TimeRange, TxEthSignature,
     },
-    AccountId, Address, ChainId, ForcedExit, MintNFT, Nonce, PubKeyHash, Token, TokenId, Transfer,
-    Withdraw, WithdrawNFT, H256,
+    AccountId, Address, ChainId, ForcedExit, MintNFT, Nonce, Order, PubKeyHash, Swap, Token,
+    TokenId, Transfer, Withdraw, WithdrawNFT, ZkSyncTx, H256,
 };
 // Local imports
 use crate::WalletCredentials;
@@ -395,4 +395,123 @@ impl<S: EthereumSigner> Signer<S> {
 
         Ok((withdraw_nft, eth_signature))
     }
+
+    /// Signs an order to exchange `token_sell` for `token_buy` with the given ratio.
+    ///
+    /// Zero `amount` stands for the limit order, which can be filled by several swaps.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sign_order(
+        &self,
+        recipient: Address,
+        token_sell: Token,
+        token_buy: Token,
+        ratio: (BigUint, BigUint),
+        amount: BigUint,
+        nonce: Nonce,
+        time_range: TimeRange,
+    ) -> Result<(Order, Option<PackedEthSignature>), SignerError> {
+        let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
+
+        let order = Order::new_signed(
+            account_id,
+            recipient,
+            nonce,
+            token_sell.id,
+            token_buy.id,
+            ratio,
+            amount,
+            time_range,
+            &self.private_key,
+        )
+        .map_err(signing_failed_error)?;
+
+        let eth_signature = match &self.eth_signer {
+            Some(signer) => {
+                let message = order.get_ethereum_sign_message(
+                    &token_sell.symbol,
+                    &token_buy.symbol,
+                    token_sell.decimals,
+                );
+                let signature = signer.sign_message(message.as_bytes()).await?;
+
+                if let TxEthSignature::EthereumSignature(packed_signature) = signature {
+                    Some(packed_signature)
+                } else {
+                    return Err(SignerError::MissingEthSigner);
+                }
+            }
+            _ => None,
+        };
+
+        Ok((order, eth_signature))
+    }
+
+    /// Signs a swap of the two orders submitted by this account.
+    pub async fn sign_swap(
+        &self,
+        orders: (Order, Order),
+        amounts: (BigUint, BigUint),
+        fee_token: Token,
+        fee: BigUint,
+        nonce: Nonce,
+    ) -> Result<(Swap, Option<PackedEthSignature>), SignerError> {
+        let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
+
+        let swap = Swap::new_signed(
+            account_id,
+            self.address,
+            nonce,
+            orders,
+            amounts,
+            fee,
+            fee_token.id,
+            &self.private_key,
+        )
+        .map_err(signing_failed_error)?;
+
+        let eth_signature = match &self.eth_signer {
+            Some(signer) => {
+                let message = swap.get_ethereum_sign_message(&fee_token.symbol, fee_token.decimals);
+                let signature = signer.sign_message(message.as_bytes()).await?;
+
+                if let TxEthSignature::EthereumSignature(packed_signature) = signature {
+                    Some(packed_signature)
+                } else {
+                    return Err(SignerError::MissingEthSigner);
+                }
+            }
+            _ => None,
+        };
+
+        Ok((swap, eth_signature))
+    }
+
+    /// Signs the batch of transactions sent by this account with a single Ethereum signature.
+    /// Each transaction is expected to be paired with the token used in its Ethereum message.
+    ///
+    /// Returns `None` if there is no Ethereum signer.
+    pub async fn sign_batch(
+        &self,
+        txs: Vec<(ZkSyncTx, Token)>,
+    ) -> Result<Option<PackedEthSignature>, SignerError> {
+        let eth_signature = match &self.eth_signer {
+            Some(signer) => {
+                let message = EthBatchSignData::get_batch_sign_message(
+                    txs.into_iter()
+                        .map(|(tx, token)| (tx, token, self.address))
+                        .collect(),
+                );
+                let signature = signer.sign_message(&message).await?;
+
+                if let TxEthSignature::EthereumSignature(packed_signature) = signature {
+                    Some(packed_signature)
+                } else {
+                    return Err(SignerError::MissingEthSigner);
+                }
+            }
+            _ => None,
+        };
+
+        Ok(eth_signature)
+    }
 }
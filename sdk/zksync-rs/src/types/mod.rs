@@ -3,7 +3,14 @@ use std::collections::HashMap;
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 
-use zksync_types::{AccountId, Address, Nonce, PubKeyHash, Token, TokenId, H256};
+use zksync_api_types::v02::{
+    account::{Account, DepositingAccountBalances as ApiDepositingAccountBalances},
+    token::NFT as ApiNFT,
+    transaction::{Receipt, TxInBlockStatus},
+};
+use zksync_types::{
+    tx::PackedEthSignature, AccountId, Address, Nonce, Order, PubKeyHash, Token, TokenId, H256,
+};
 use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 
 pub type Tokens = HashMap<String, Token>;
@@ -17,6 +24,17 @@ pub struct NFT {
     pub content_hash: H256,
 }
 
+impl From<ApiNFT> for NFT {
+    fn from(nft: ApiNFT) -> Self {
+        Self {
+            id: nft.id,
+            symbol: nft.symbol,
+            creator_id: nft.creator_id,
+            content_hash: nft.content_hash,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountState {
@@ -26,6 +44,21 @@ pub struct AccountState {
     pub pub_key_hash: PubKeyHash,
 }
 
+impl From<Account> for AccountState {
+    fn from(account: Account) -> Self {
+        Self {
+            balances: account.balances.into_iter().collect(),
+            nfts: account
+                .nfts
+                .into_iter()
+                .map(|(id, nft)| (id, nft.into()))
+                .collect(),
+            nonce: account.nonce,
+            pub_key_hash: account.pub_key_hash,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct DepositingFunds {
@@ -40,6 +73,23 @@ pub struct DepositingAccountBalances {
     balances: HashMap<String, DepositingFunds>,
 }
 
+impl From<ApiDepositingAccountBalances> for DepositingAccountBalances {
+    fn from(depositing: ApiDepositingAccountBalances) -> Self {
+        let balances = depositing
+            .balances
+            .into_iter()
+            .map(|(token, funds)| {
+                let funds = DepositingFunds {
+                    amount: funds.amount,
+                    expected_accept_block: funds.expected_accept_block,
+                };
+                (token, funds)
+            })
+            .collect();
+        Self { balances }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum BlockStatus {
@@ -81,6 +131,32 @@ impl TransactionInfo {
     }
 }
 
+impl From<Receipt> for TransactionInfo {
+    fn from(receipt: Receipt) -> Self {
+        let (status, rollup_block, fail_reason) = match receipt {
+            Receipt::L1(receipt) => (receipt.status, receipt.rollup_block, None),
+            Receipt::L2(receipt) => (receipt.status, receipt.rollup_block, receipt.fail_reason),
+        };
+        let block = rollup_block.map(|block_number| BlockInfo {
+            block_number: i64::from(*block_number),
+            committed: status != TxInBlockStatus::Queued,
+            verified: status == TxInBlockStatus::Finalized,
+        });
+        let (executed, success) = match status {
+            TxInBlockStatus::Queued => (false, None),
+            TxInBlockStatus::Committed | TxInBlockStatus::Finalized => (true, Some(true)),
+            TxInBlockStatus::Rejected => (true, Some(false)),
+        };
+
+        Self {
+            executed,
+            success,
+            fail_reason,
+            block,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct EthOpInfo {
@@ -95,6 +171,14 @@ impl EthOpInfo {
     }
 }
 
+/// Order signed by its owner, which can be handed to the counterparty to be submitted in a `Swap`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedOrder {
+    pub order: Order,
+    pub eth_signature: Option<PackedEthSignature>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractAddress {
@@ -126,6 +210,7 @@ pub enum OutputFeeType {
     MintNFT,
     WithdrawNFT,
     FastWithdrawNFT,
+    Swap,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
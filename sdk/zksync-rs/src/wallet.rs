@@ -1,11 +1,13 @@
+use jsonrpc_core::ErrorCode;
 use num::BigUint;
 use zksync_eth_signer::EthereumSigner;
-use zksync_types::{AccountId, Address, TokenId, TokenLike};
+use zksync_types::{tx::TxHash, AccountId, Address, Nonce, TokenId, TokenLike};
 
 use crate::{
     credentials::WalletCredentials,
     error::ClientError,
     ethereum::EthereumProvider,
+    nonce_manager::NonceManager,
     operations::*,
     provider::Provider,
     signer::Signer,
@@ -18,6 +20,8 @@ pub struct Wallet<S: EthereumSigner, P: Provider> {
     pub provider: P,
     pub signer: Signer<S>,
     pub tokens: TokensCache,
+    /// Optional local nonce manager, see `Wallet::with_nonce_manager`.
+    pub nonce_manager: Option<NonceManager>,
 }
 
 impl<S, P> Wallet<S, P>
@@ -37,9 +41,81 @@ where
             provider,
             signer,
             tokens,
+            nonce_manager: None,
         })
     }
 
+    /// Makes the wallet reserve the nonces of the transactions via the given nonce manager
+    /// instead of taking them from the committed account state, so that several transactions
+    /// can be built and sent concurrently.
+    ///
+    /// Note that the nonces are reserved by the `tx` methods of the builders, so the nonces
+    /// of the transactions which were built but not sent should be released manually.
+    /// The nonces of the transactions which failed to reach the server stay reserved as well,
+    /// since the server could have accepted them; `NonceManager::reset` starts over in that case.
+    pub fn with_nonce_manager(mut self, nonce_manager: NonceManager) -> Self {
+        self.nonce_manager = Some(nonce_manager);
+        self
+    }
+
+    /// Returns the nonce for the next transaction, or the first of `count` nonces
+    /// for a batch of transactions.
+    pub(crate) async fn next_nonce(&self, count: u32) -> Result<Nonce, ClientError> {
+        match &self.nonce_manager {
+            Some(nonce_manager) => {
+                nonce_manager
+                    .reserve(&self.provider, self.address(), count)
+                    .await
+            }
+            None => {
+                let account_info = self.provider.account_info(self.address()).await?;
+                Ok(account_info.committed.nonce)
+            }
+        }
+    }
+
+    /// Reports the result of the transactions submission to the nonce manager, if any.
+    ///
+    /// The submission is recorded in memory even if the state can't be saved to the file,
+    /// so the transactions which reached the server are still returned to the caller.
+    /// The state is written to the file along with the next change.
+    pub(crate) fn track_submission(
+        &self,
+        nonces: &[Nonce],
+        result: Result<&[TxHash], &ClientError>,
+    ) {
+        let nonce_manager = match &self.nonce_manager {
+            Some(nonce_manager) => nonce_manager,
+            None => return,
+        };
+        match result {
+            Ok(tx_hashes) => {
+                for (nonce, tx_hash) in nonces.iter().zip(tx_hashes) {
+                    let _ = nonce_manager.track(*nonce, *tx_hash);
+                }
+            }
+            // The transactions could be accepted even if the response was lost,
+            // so only the nonces of the rejected transactions can be reused.
+            Err(error) if is_rejected_by_server(error) => {
+                for nonce in nonces {
+                    let _ = nonce_manager.release(*nonce);
+                }
+            }
+            Err(_) => {}
+        }
+    }
+
+    /// Returns `count` nonces reserved by `next_nonce` to the nonce manager, if any.
+    /// Must be called if the transactions were not built after the nonces were reserved.
+    pub(crate) fn release_nonces(&self, first: Nonce, count: u32) -> Result<(), ClientError> {
+        if let Some(nonce_manager) = &self.nonce_manager {
+            for offset in 0..count {
+                nonce_manager.release(first + offset)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Updates account ID stored in the wallet.
     /// This method must be invoked if the wallet was created for a non-existent account,
     /// and it was initialized after creation (e.g. by doing a deposit).
@@ -158,6 +234,21 @@ where
         WithdrawNFTBuilder::new(self)
     }
 
+    /// Initializes signing of an `Order`, which can be handed to a counterparty.
+    pub fn start_order(&self) -> OrderBuilder<'_, S, P> {
+        OrderBuilder::new(self)
+    }
+
+    /// Initializes `Swap` transaction sending, with this wallet being the submitter.
+    pub fn start_swap(&self) -> SwapBuilder<'_, S, P> {
+        SwapBuilder::new(self)
+    }
+
+    /// Initializes sending of a transactions batch signed with a single Ethereum signature.
+    pub fn start_batch(&self) -> BatchBuilder<'_, S, P> {
+        BatchBuilder::new(self)
+    }
+
     /// Creates an `EthereumProvider` to interact with the Ethereum network.
     ///
     /// Returns an error if wallet was created without providing an Ethereum private key.
@@ -181,3 +272,28 @@ where
         }
     }
 }
+
+/// Codes of the JSON RPC errors returned by the server for the transactions failing validation,
+/// e.g. because of a nonce mismatch, an incorrect signature or a too low fee.
+/// The generic `300` code is not listed, since it's also returned for the database errors.
+const RPC_TX_REJECTION_CODES: [i64; 13] = [
+    101, 103, 104, 105, 200, 201, 202, 203, 301, 302, 303, 304, 305,
+];
+
+/// Codes of the REST API errors returned by the server for the transactions failing validation.
+/// The `605` code is not listed, since it's also returned for the database errors.
+const REST_TX_REJECTION_CODES: [u16; 7] = [601, 602, 603, 604, 606, 608, 609];
+
+/// Checks whether the error is the server response rejecting the submitted transactions.
+/// The internal and communication errors don't guarantee that the transactions were not accepted.
+fn is_rejected_by_server(error: &ClientError) -> bool {
+    match error {
+        ClientError::RpcError(failure) => match failure.error.code {
+            ErrorCode::InvalidParams => true,
+            ErrorCode::ServerError(code) => RPC_TX_REJECTION_CODES.contains(&code),
+            _ => false,
+        },
+        ClientError::RestApiError { code, .. } => REST_TX_REJECTION_CODES.contains(code),
+        _ => false,
+    }
+}
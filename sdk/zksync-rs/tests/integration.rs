@@ -39,7 +39,7 @@ use zksync::{
     zksync_types::{
         tx::PackedEthSignature, PriorityOp, PriorityOpId, Token, TokenLike, TxFeeTypes, ZkSyncTx,
     },
-    EthereumProvider, Network, RpcProvider, Wallet, WalletCredentials,
+    EthereumProvider, Network, RestProvider, RpcProvider, Wallet, WalletCredentials,
 };
 use zksync_eth_signer::{EthereumSigner, PrivateKeySigner};
 
@@ -907,3 +907,37 @@ async fn batch_transfer() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[cfg_attr(not(feature = "integration-tests"), ignore)]
+async fn rest_provider() -> Result<(), anyhow::Error> {
+    let wallet = init_account_with_one_ether().await?;
+    let rest_provider = RestProvider::new(Network::Localhost);
+
+    let account_info = rest_provider.account_info(wallet.address()).await?;
+    assert_eq!(account_info.id, wallet.account_id());
+    assert!(rest_provider.tokens().await?.contains_key("ETH"));
+
+    let handle = wallet
+        .start_transfer()
+        .to(wallet.address())
+        .token("ETH")?
+        .amount(1_000_000u64)
+        .send()
+        .await?;
+    let tx_hash = handle.hash();
+    handle
+        .commit_timeout(Duration::from_secs(180))
+        .wait_for_commit()
+        .await?;
+
+    let tx_info = rest_provider.tx_info(tx_hash).await?;
+    assert_eq!(tx_info.success, Some(true));
+
+    // History starts with the latest transaction.
+    let mut history = rest_provider.account_history(wallet.address());
+    let page = history.next_page().await?.expect("History is empty");
+    assert_eq!(page[0].tx_hash, tx_hash);
+
+    Ok(())
+}
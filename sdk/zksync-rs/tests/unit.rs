@@ -60,6 +60,55 @@ fn test_tokens_cache() {
     assert!(!tokens_cache.is_eth((&token_dai.symbol as &str).into()));
 }
 
+#[test]
+fn test_transaction_info_from_receipt() {
+    use zksync::types::TransactionInfo;
+    use zksync_api_types::v02::transaction::{L2Receipt, Receipt, TxInBlockStatus};
+    use zksync_types::{tx::TxHash, BlockNumber};
+
+    let receipt = |status, rollup_block, fail_reason| {
+        Receipt::L2(L2Receipt {
+            tx_hash: TxHash::default(),
+            rollup_block,
+            status,
+            fail_reason,
+        })
+    };
+
+    let info = TransactionInfo::from(receipt(TxInBlockStatus::Queued, None, None));
+    assert!(!info.executed);
+    assert!(info.block.is_none());
+
+    let info = TransactionInfo::from(receipt(
+        TxInBlockStatus::Committed,
+        Some(BlockNumber(5)),
+        None,
+    ));
+    assert!(info.executed);
+    assert_eq!(info.success, Some(true));
+    let block = info.block.unwrap();
+    assert_eq!(block.block_number, 5);
+    assert!(block.committed);
+    assert!(!block.verified);
+
+    let info = TransactionInfo::from(receipt(
+        TxInBlockStatus::Finalized,
+        Some(BlockNumber(5)),
+        None,
+    ));
+    assert!(info.is_verified());
+
+    let info = TransactionInfo::from(receipt(
+        TxInBlockStatus::Rejected,
+        Some(BlockNumber(6)),
+        Some("Nonce mismatch".into()),
+    ));
+    assert!(info.executed);
+    assert_eq!(info.success, Some(false));
+    assert_eq!(info.fail_reason.as_deref(), Some("Nonce mismatch"));
+    assert!(!info.is_verified());
+}
+
 fn priv_key_from_raw(raw: &[u8]) -> Option<PrivateKey> {
     use zksync_crypto::{
         bellman::{pairing::ff::PrimeField, PrimeFieldRepr},
@@ -153,10 +202,10 @@ mod utils_with_vectors {
 mod signatures_with_vectors {
     use super::*;
     use zksync::{signer::Signer, WalletCredentials};
-    use zksync_config::test_config::unit_vectors::TxData;
+    use zksync_config::test_config::unit_vectors::{Order as TestOrder, TxData};
     use zksync_eth_signer::PrivateKeySigner;
     use zksync_types::tx::{ChangePubKeyECDSAData, ChangePubKeyEthAuthData};
-    use zksync_types::{network::Network, AccountId, Address, H256};
+    use zksync_types::{network::Network, AccountId, Address, Order, H256};
 
     async fn get_signer(
         eth_private_key_raw: &[u8],
@@ -506,27 +555,165 @@ mod signatures_with_vectors {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_order_signature() {
+        let test_vectors = TestVectorsConfig::load();
+        for TestEntry { inputs, outputs } in test_vectors.transactions.items {
+            if let TxData::Order {
+                data: order,
+                eth_sign_data: sign_data,
+            } = &inputs.data
+            {
+                let signer = get_signer(
+                    &inputs.eth_private_key,
+                    order.recipient_address,
+                    order.account_id,
+                )
+                .await;
+
+                // Test vectors format the order amount with 3 decimals.
+                let token_sell = Token::new(
+                    order.token_sell,
+                    Default::default(),
+                    &sign_data.token_sell,
+                    3,
+                    TokenKind::ERC20,
+                );
+                let token_buy = Token::new(
+                    order.token_buy,
+                    Default::default(),
+                    &sign_data.token_buy,
+                    3,
+                    TokenKind::ERC20,
+                );
+                let (signed_order, eth_signature) = signer
+                    .sign_order(
+                        order.recipient_address,
+                        token_sell,
+                        token_buy,
+                        order.ratio.clone(),
+                        order.amount.clone(),
+                        order.nonce,
+                        order.time_range,
+                    )
+                    .await
+                    .expect("Order signing error");
+
+                assert_eq!(signed_order.get_bytes(), outputs.sign_bytes);
+                assert_tx_signature(
+                    &signed_order.signature,
+                    &outputs.signature.pub_key,
+                    &outputs.signature.signature,
+                );
+
+                assert_eq!(
+                    signed_order
+                        .get_ethereum_sign_message(&sign_data.token_sell, &sign_data.token_buy, 3)
+                        .into_bytes(),
+                    outputs.eth_sign_message.unwrap()
+                );
+
+                if let Some(expected_eth_signature) = outputs.eth_signature {
+                    let eth_signature = eth_signature.unwrap().serialize_packed();
+                    assert_eq!(&eth_signature[..], expected_eth_signature.as_slice());
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_swap_signature() {
+        let test_vectors = TestVectorsConfig::load();
+        for TestEntry { inputs, outputs } in test_vectors.transactions.items {
+            if let TxData::Swap {
+                data: swap,
+                eth_sign_data: sign_data,
+            } = &inputs.data
+            {
+                let signer = get_signer(
+                    &inputs.eth_private_key,
+                    swap.submitter_address,
+                    swap.submitter_id,
+                )
+                .await;
+
+                // Signatures of the orders are not the part of the swap signed data.
+                let to_order = |order: &TestOrder| Order {
+                    account_id: order.account_id,
+                    recipient_address: order.recipient_address,
+                    nonce: order.nonce,
+                    token_buy: order.token_buy,
+                    token_sell: order.token_sell,
+                    price: order.ratio.clone(),
+                    amount: order.amount.clone(),
+                    time_range: order.time_range,
+                    signature: Default::default(),
+                };
+                // Test vectors format the swap fee with 1 decimal.
+                let fee_token = Token::new(
+                    swap.fee_token,
+                    Default::default(),
+                    &sign_data.fee_token,
+                    1,
+                    TokenKind::ERC20,
+                );
+                let (signed_swap, eth_signature) = signer
+                    .sign_swap(
+                        (to_order(&swap.orders.0), to_order(&swap.orders.1)),
+                        swap.amounts.clone(),
+                        fee_token,
+                        swap.fee.clone(),
+                        swap.nonce,
+                    )
+                    .await
+                    .expect("Swap signing error");
+
+                assert_eq!(signed_swap.get_sign_bytes(), outputs.sign_bytes);
+                assert_tx_signature(
+                    &signed_swap.signature,
+                    &outputs.signature.pub_key,
+                    &outputs.signature.signature,
+                );
+
+                assert_eq!(
+                    signed_swap
+                        .get_ethereum_sign_message(&sign_data.fee_token, 1)
+                        .into_bytes(),
+                    outputs.eth_sign_message.unwrap()
+                );
+
+                if let Some(expected_eth_signature) = outputs.eth_signature {
+                    let eth_signature = eth_signature.unwrap().serialize_packed();
+                    assert_eq!(&eth_signature[..], expected_eth_signature.as_slice());
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod wallet_tests {
     use super::*;
+    use jsonrpc_core::{ErrorCode, Id, Version};
     use num::{BigUint, ToPrimitive};
     use zksync::{
-        error::ClientError,
+        error::{ClientError, RpcFailure},
+        nonce_manager::NonceManager,
+        offline::{OfflineProvider, SignedBatch, SignedTransaction},
         provider::Provider,
         signer::Signer,
         types::{
             AccountInfo, AccountState, BlockStatus, ContractAddress, EthOpInfo, Fee, Tokens,
-            TransactionInfo,
+            TransactionInfo, NFT,
         },
         Network, Wallet, WalletCredentials,
     };
     use zksync_eth_signer::PrivateKeySigner;
     use zksync_types::{
         tokens::get_genesis_token_list,
-        tx::{PackedEthSignature, TxHash},
-        Address, PubKeyHash, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H256,
+        tx::{EthBatchSignData, PackedEthSignature, TxHash},
+        Address, PubKeyHash, Swap, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H256,
     };
 
     #[derive(Debug, Clone)]
@@ -535,6 +722,8 @@ mod wallet_tests {
     struct MockProvider {
         network: Network,
         eth_private_key: H256,
+        /// Code of the JSON RPC error returned for the sent transactions.
+        send_error_code: Option<i64>,
     }
 
     impl MockProvider {
@@ -650,6 +839,24 @@ mod wallet_tests {
             &self,
             _tx: ZkSyncTx,
             _eth_signature: Option<PackedEthSignature>,
+        ) -> Result<TxHash, ClientError> {
+            let code = self.send_error_code.expect("Transactions can't be sent");
+            Err(ClientError::RpcError(RpcFailure {
+                jsonrpc: Some(Version::V2),
+                error: jsonrpc_core::Error {
+                    code: ErrorCode::from(code),
+                    message: "Transaction is not accepted".to_string(),
+                    data: None,
+                },
+                id: Id::Num(1),
+            }))
+        }
+
+        async fn send_swap(
+            &self,
+            _swap: Swap,
+            _eth_signature: Option<PackedEthSignature>,
+            _orders_signatures: (Option<PackedEthSignature>, Option<PackedEthSignature>),
         ) -> Result<TxHash, ClientError> {
             unreachable!()
         }
@@ -682,6 +889,7 @@ mod wallet_tests {
         let provider = MockProvider {
             network,
             eth_private_key: private_key,
+            send_error_code: None,
         };
         Wallet::new(provider, creds).await.unwrap()
     }
@@ -774,4 +982,333 @@ mod wallet_tests {
         let expected_address: Vec<_> = (0..20).collect();
         assert_eq!(eth_provider.contract_address().as_bytes(), expected_address);
     }
+
+    #[tokio::test]
+    async fn test_wallet_batch() {
+        let wallet = get_test_wallet(&[60; 32], Network::Mainnet).await;
+        let dai = wallet.tokens.resolve("DAI".into()).unwrap();
+        let recipients = [Address::repeat_byte(1), Address::repeat_byte(2)];
+
+        let (txs, eth_signature) = wallet
+            .start_batch()
+            .add_transfer(recipients[0], "DAI", 100u32)
+            .unwrap()
+            .add_withdraw(recipients[1], "DAI", 200u32)
+            .unwrap()
+            .fee_token("DAI")
+            .unwrap()
+            .fee(10u32)
+            .fee_payer(1)
+            .txs()
+            .await
+            .unwrap();
+
+        assert_eq!(txs.len(), 2);
+        let fees: Vec<_> = txs
+            .iter()
+            .map(|(tx, signature)| {
+                assert!(signature.is_none());
+                match tx {
+                    ZkSyncTx::Transfer(tx) => tx.fee.clone(),
+                    ZkSyncTx::Withdraw(tx) => tx.fee.clone(),
+                    _ => panic!("Unexpected transaction type"),
+                }
+            })
+            .collect();
+        assert_eq!(fees, vec![BigUint::from(0u32), BigUint::from(10u32)]);
+        assert_eq!(txs[0].0.nonce(), Nonce(0));
+        assert_eq!(txs[1].0.nonce(), Nonce(1));
+
+        let message = EthBatchSignData::get_batch_sign_message(
+            txs.into_iter()
+                .map(|(tx, _)| (tx, dai.clone(), wallet.address()))
+                .collect(),
+        );
+        let signer = eth_signature
+            .unwrap()
+            .signature_recover_signer_from_raw_message(&message)
+            .unwrap();
+        assert_eq!(signer, wallet.address());
+    }
+
+    #[tokio::test]
+    async fn test_wallet_batch_fee_transfer() {
+        let wallet = get_test_wallet(&[60; 32], Network::Mainnet).await;
+
+        let (txs, _) = wallet
+            .start_batch()
+            .add_transfer(Address::repeat_byte(1), "DAI", 100u32)
+            .unwrap()
+            .fee_token("USDC")
+            .unwrap()
+            .fee(10u32)
+            .nonce(Nonce(5))
+            .txs()
+            .await
+            .unwrap();
+
+        // The fee is paid by the transfer appended to the batch.
+        assert_eq!(txs.len(), 2);
+        match &txs[1].0 {
+            ZkSyncTx::Transfer(tx) => {
+                assert_eq!(tx.to, wallet.address());
+                assert_eq!(tx.amount, BigUint::from(0u32));
+                assert_eq!(tx.fee, BigUint::from(10u32));
+                assert_eq!(tx.nonce, Nonce(6));
+            }
+            _ => panic!("Unexpected transaction type"),
+        }
+
+        let result = wallet
+            .start_batch()
+            .add_transfer(Address::repeat_byte(1), "DAI", 100u32)
+            .unwrap()
+            .fee_token("USDC")
+            .unwrap()
+            .fee(10u32)
+            .fee_payer(0)
+            .txs()
+            .await;
+        assert_eq!(result.unwrap_err(), ClientError::IncorrectInput);
+    }
+
+    #[tokio::test]
+    async fn test_nonce_manager_reserve() {
+        let wallet = get_test_wallet(&[70; 32], Network::Mainnet).await;
+        let nonce_manager = NonceManager::new();
+        let reserve = |count| nonce_manager.reserve(&wallet.provider, wallet.address(), count);
+
+        assert_eq!(reserve(1).await.unwrap(), Nonce(0));
+        assert_eq!(reserve(2).await.unwrap(), Nonce(1));
+        assert_eq!(reserve(1).await.unwrap(), Nonce(3));
+
+        // Released nonce in the middle becomes a gap, which is filled first.
+        nonce_manager.release(Nonce(1)).unwrap();
+        assert_eq!(reserve(2).await.unwrap(), Nonce(4));
+        assert_eq!(reserve(1).await.unwrap(), Nonce(1));
+
+        // Released last nonces roll the next nonce back.
+        nonce_manager.release(Nonce(5)).unwrap();
+        nonce_manager.release(Nonce(4)).unwrap();
+        assert_eq!(reserve(1).await.unwrap(), Nonce(4));
+
+        // Nonces which were not reserved are ignored.
+        nonce_manager.release(Nonce(10)).unwrap();
+        assert_eq!(reserve(1).await.unwrap(), Nonce(5));
+    }
+
+    #[tokio::test]
+    async fn test_nonce_manager_persistence() {
+        let wallet = get_test_wallet(&[70; 32], Network::Mainnet).await;
+        let path = std::env::temp_dir().join(format!(
+            "zksync_nonce_manager_test_{}.json",
+            std::process::id()
+        ));
+
+        let nonce_manager = NonceManager::with_file(&path).unwrap();
+        let nonce = nonce_manager
+            .reserve(&wallet.provider, wallet.address(), 2)
+            .await
+            .unwrap();
+        nonce_manager.track(nonce, TxHash::default()).unwrap();
+
+        // The restored manager doesn't reuse the reserved nonces.
+        let restored = NonceManager::with_file(&path).unwrap();
+        assert_eq!(restored.pending().len(), 1);
+        assert_eq!(restored.pending()[0].nonce, Nonce(0));
+        let nonce = restored
+            .reserve(&wallet.provider, wallet.address(), 1)
+            .await
+            .unwrap();
+        assert_eq!(nonce, Nonce(2));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wallet_with_nonce_manager() {
+        let wallet = get_test_wallet(&[70; 32], Network::Mainnet)
+            .await
+            .with_nonce_manager(NonceManager::new());
+
+        let build_transfer = || {
+            wallet
+                .start_transfer()
+                .to(Address::repeat_byte(1))
+                .token("DAI")
+                .unwrap()
+                .amount(100u32)
+                .fee(10u32)
+                .tx()
+        };
+        let (first, _) = build_transfer().await.unwrap();
+        let (second, _) = build_transfer().await.unwrap();
+
+        assert_eq!(first.nonce(), Nonce(0));
+        assert_eq!(second.nonce(), Nonce(1));
+    }
+
+    async fn send_transfer(
+        wallet: &Wallet<PrivateKeySigner, MockProvider>,
+    ) -> Result<(), ClientError> {
+        wallet
+            .start_transfer()
+            .to(Address::repeat_byte(1))
+            .token("DAI")?
+            .amount(100u32)
+            .fee(10u32)
+            .send()
+            .await
+            .map(drop)
+    }
+
+    #[tokio::test]
+    async fn test_nonce_manager_failed_submissions() {
+        let mut wallet = get_test_wallet(&[70; 32], Network::Mainnet)
+            .await
+            .with_nonce_manager(NonceManager::new());
+
+        // Transaction failing validation is rejected, so its nonce is reused.
+        wallet.provider.send_error_code = Some(103);
+        assert!(send_transfer(&wallet).await.is_err());
+        // The generic server error doesn't guarantee that the transaction was not accepted.
+        wallet.provider.send_error_code = Some(300);
+        assert!(send_transfer(&wallet).await.is_err());
+
+        let (tx, _) = wallet
+            .start_transfer()
+            .to(Address::repeat_byte(1))
+            .token("DAI")
+            .unwrap()
+            .amount(100u32)
+            .fee(10u32)
+            .tx()
+            .await
+            .unwrap();
+        assert_eq!(tx.nonce(), Nonce(1));
+    }
+
+    async fn get_offline_wallet() -> Wallet<PrivateKeySigner, OfflineProvider> {
+        let private_key = H256::repeat_byte(70);
+        let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+        let creds = WalletCredentials::from_eth_signer(
+            address,
+            PrivateKeySigner::new(private_key),
+            Network::Mainnet,
+        )
+        .await
+        .unwrap();
+        let genesis_tokens = get_genesis_token_list(&Network::Mainnet.to_string()).unwrap();
+        let tokens = (1..).zip(&genesis_tokens[..3]).map(|(id, token)| {
+            Token::new(
+                TokenId(id),
+                token.address,
+                &token.symbol,
+                token.decimals,
+                TokenKind::ERC20,
+            )
+        });
+        let provider = OfflineProvider::new(Network::Mainnet, tokens, AccountId(42), Nonce(5));
+        Wallet::new(provider, creds).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_offline_signing() {
+        let wallet = get_offline_wallet().await;
+        assert_eq!(wallet.account_id(), Some(AccountId(42)));
+
+        // The fee can't be requested from the server.
+        let error = wallet
+            .start_transfer()
+            .to(Address::repeat_byte(1))
+            .token("DAI")
+            .unwrap()
+            .amount(100u32)
+            .signed()
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ClientError::NotSupported(_)));
+
+        let signed = wallet
+            .start_transfer()
+            .to(Address::repeat_byte(1))
+            .token("DAI")
+            .unwrap()
+            .amount(100u32)
+            .fee(10u32)
+            .signed()
+            .await
+            .unwrap();
+        assert_eq!(signed.tx.nonce(), Nonce(5));
+
+        let path = std::env::temp_dir().join(format!(
+            "zksync_offline_tx_test_{}.json",
+            std::process::id()
+        ));
+        signed.save(&path).unwrap();
+        let loaded = SignedTransaction::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.hash(), signed.hash());
+        assert_eq!(loaded.eth_signature, signed.eth_signature);
+
+        // Broadcasting requires the network access.
+        let error = loaded.send(&wallet.provider).await.unwrap_err();
+        assert!(matches!(error, ClientError::NotSupported(_)));
+    }
+
+    #[tokio::test]
+    async fn test_offline_batch_signing() {
+        let wallet = get_offline_wallet().await;
+        let nft = NFT {
+            id: TokenId(65536),
+            symbol: "NFT-65536".to_string(),
+            creator_id: AccountId(1),
+            content_hash: H256::repeat_byte(2),
+        };
+
+        let signed = wallet
+            .start_transfer_nft()
+            .to(Address::repeat_byte(1))
+            .nft(nft)
+            .fee_token("DAI")
+            .unwrap()
+            .fee(10u32)
+            .signed()
+            .await
+            .unwrap();
+        let nonces: Vec<_> = signed.txs.iter().map(|signed| signed.tx.nonce()).collect();
+        assert_eq!(nonces, vec![Nonce(5), Nonce(6)]);
+        assert!(signed.eth_signature.is_none());
+
+        let signed = wallet
+            .start_batch()
+            .add_transfer(Address::repeat_byte(1), "DAI", 100u32)
+            .unwrap()
+            .add_withdraw(Address::repeat_byte(2), "DAI", 200u32)
+            .unwrap()
+            .fee_token("DAI")
+            .unwrap()
+            .fee(10u32)
+            .signed()
+            .await
+            .unwrap();
+        assert_eq!(signed.txs.len(), 2);
+        assert!(signed.eth_signature.is_some());
+
+        let path = std::env::temp_dir().join(format!(
+            "zksync_offline_batch_test_{}.json",
+            std::process::id()
+        ));
+        signed.save(&path).unwrap();
+        let loaded = SignedBatch::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.hashes(), signed.hashes());
+        assert_eq!(loaded.eth_signature, signed.eth_signature);
+
+        // Broadcasting requires the network access.
+        let error = loaded.send(&wallet.provider).await.unwrap_err();
+        assert!(matches!(error, ClientError::NotSupported(_)));
+    }
 }